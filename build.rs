@@ -5,23 +5,38 @@
 // Author:
 // - Erkan Tairi <erkan.tairi@gmail.com>
 //
+#![allow(non_snake_case)]
+
 extern crate cc;
 
+#[path = "build/fixed_base.rs"]
+mod fixed_base;
+
 use std::env;
+use std::path::Path;
 
 fn main() {
     let target = env::var("TARGET").unwrap();
     let host = env::var("HOST").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=build");
+    println!("cargo:rerun-if-changed=src/constants.rs");
+    println!("cargo:rerun-if-changed=src/backend");
+    fixed_base::generate(Path::new(&out_dir));
 
     if cfg!(target_arch = "x86_64") {
         if target.contains("msvc") && host.contains("windows") {
             cc::Build::new()
                 .file("src/backend/x64/amd64/fp_x64.asm")
+                .file("src/backend/x64/amd64/fp_x64_mulx.asm")
                 .file("src/backend/x64/amd64/util_x64.asm")
                 .compile("sidh_helpers");
         } else {
             cc::Build::new()
                 .file("src/backend/x64/amd64/fp_x64.S")
+                .file("src/backend/x64/amd64/fp_x64_mulx.S")
                 .file("src/backend/x64/amd64/util_x64.S")
                 .compile("sidh_helpers");
         }
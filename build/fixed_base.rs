@@ -0,0 +1,325 @@
+// This file is part of sidh-rs.
+// Copyright (c) 2017 Erkan Tairi
+// See LICENSE for licensing information.
+//
+// Author:
+// - Erkan Tairi <erkan.tairi@gmail.com>
+//
+
+// Generation of the fixed-base comb tables for the SIDH bases `P_A` and `P_B`,
+// and of the fixed-basis ladder tables x([2^i]Q) for Q = \tau(P), used by
+// `src/fixed_base.rs`, which describes their layout.
+//
+// The points live on the twisted Edwards curve `2x^2 + y^2 = 1 - 2x^2y^2` over
+// `F_p`, birational to the Montgomery curve `E_0 : y^2 = x^3 + x`. This uses its
+// own small Montgomery-form arithmetic modulo `p`, as the build script cannot use
+// the crate's field arithmetic.
+
+use std::fmt::Write as FmtWrite;
+use std::fs;
+use std::path::Path;
+
+const NUM_LIMBS: usize = 12;
+
+type Limbs = [u64; NUM_LIMBS];
+
+// p = 2^372 * 3^239 - 1, as in src/field.rs.
+const P: Limbs = [0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0xeeafffffffffffff,
+                  0xe3ec968549f878a8, 0xda959b1a13f7cc76, 0x084e9867d6ebe876, 0x8562b5045cb25748, 0x0e12909f97badc66, 0x6fe5d541f71c];
+
+// (2^768)^2 mod p, as in src/field.rs.
+const RSQ: Limbs = [0x233046449dad4058, 0xdb010161a696452a, 0x5e36941472e3fd8e, 0xf40bfe2082a2e706, 0x4932cca8904f8751, 0x1f735f1f1ee7fc81,
+                    0xa24f4d80c1048e18, 0xb56c383ccdb607c5, 0x441dd47b735f9c90, 0x5673ed2c6a6ac82a, 0x06c905261132294b, 0x41ad830f1f35];
+
+// The comb parameters, as in src/fixed_base.rs.
+const TEETH: usize = 6;
+const BLOCKS: usize = 2;
+const SPACING: usize = 64;
+const BLOCK_SIZE: usize = 32;
+
+// The largest scalar length in bits for the fixed-basis ladder tables.
+const LADDER_BITS: usize = 384;
+
+// Compute a - b, returning the difference and the final borrow.
+fn sub_limbs(a: &Limbs, b: &Limbs) -> (Limbs, bool) {
+    let mut result = [0u64; NUM_LIMBS];
+    let mut borrow = false;
+    for i in 0..NUM_LIMBS {
+        let (t0, b0) = a[i].overflowing_sub(b[i]);
+        let (t1, b1) = t0.overflowing_sub(borrow as u64);
+        result[i] = t1;
+        borrow = b0 | b1;
+    }
+    (result, borrow)
+}
+
+// The elements of F_p below are all in Montgomery form a*2^768 mod p, in [0, p).
+fn add(a: &Limbs, b: &Limbs) -> Limbs {
+    let mut sum = [0u64; NUM_LIMBS];
+    let mut carry = false;
+    for i in 0..NUM_LIMBS {
+        let (t0, c0) = a[i].overflowing_add(b[i]);
+        let (t1, c1) = t0.overflowing_add(carry as u64);
+        sum[i] = t1;
+        carry = c0 | c1;
+    }
+    // As p < 2^751, the sum does not overflow.
+    let (reduced, borrow) = sub_limbs(&sum, &P);
+    if borrow { sum } else { reduced }
+}
+
+fn sub(a: &Limbs, b: &Limbs) -> Limbs {
+    let (difference, borrow) = sub_limbs(a, b);
+    if borrow { add_p(&difference) } else { difference }
+}
+
+fn add_p(a: &Limbs) -> Limbs {
+    let mut sum = [0u64; NUM_LIMBS];
+    let mut carry = false;
+    for i in 0..NUM_LIMBS {
+        let (t0, c0) = a[i].overflowing_add(P[i]);
+        let (t1, c1) = t0.overflowing_add(carry as u64);
+        sum[i] = t1;
+        carry = c0 | c1;
+    }
+    sum
+}
+
+fn neg(a: &Limbs) -> Limbs {
+    sub(&[0u64; NUM_LIMBS], a)
+}
+
+// Compute a*b*2^(-768) mod p, by CIOS Montgomery multiplication with
+// -p^(-1) = 1 mod 2^64.
+fn mul(a: &Limbs, b: &Limbs) -> Limbs {
+    let mut t = [0u64; NUM_LIMBS + 2];
+    for &b_i in b.iter() {
+        let mut carry = 0u64;
+        for j in 0..NUM_LIMBS {
+            let uv = (t[j] as u128) + (a[j] as u128) * (b_i as u128) + (carry as u128);
+            t[j] = uv as u64;
+            carry = (uv >> 64) as u64;
+        }
+        let uv = (t[NUM_LIMBS] as u128) + (carry as u128);
+        t[NUM_LIMBS] = uv as u64;
+        t[NUM_LIMBS + 1] = (uv >> 64) as u64;
+
+        let m = t[0] as u128;
+        let uv = (t[0] as u128) + m * (P[0] as u128);
+        carry = (uv >> 64) as u64;
+        for j in 1..NUM_LIMBS {
+            let uv = (t[j] as u128) + m * (P[j] as u128) + (carry as u128);
+            t[j - 1] = uv as u64;
+            carry = (uv >> 64) as u64;
+        }
+        let uv = (t[NUM_LIMBS] as u128) + (carry as u128);
+        t[NUM_LIMBS - 1] = uv as u64;
+        t[NUM_LIMBS] = t[NUM_LIMBS + 1] + ((uv >> 64) as u64);
+    }
+    let mut result = [0u64; NUM_LIMBS];
+    result.copy_from_slice(&t[..NUM_LIMBS]);
+    let (reduced, borrow) = sub_limbs(&result, &P);
+    if borrow { result } else { reduced }
+}
+
+fn from_u64(x: u64) -> Limbs {
+    let mut limbs = [0u64; NUM_LIMBS];
+    limbs[0] = x;
+    mul(&limbs, &RSQ)
+}
+
+// Convert out of Montgomery form, to the canonical integer in [0, p).
+fn to_canonical(a: &Limbs) -> Limbs {
+    let mut one = [0u64; NUM_LIMBS];
+    one[0] = 1;
+    mul(a, &one)
+}
+
+// Compute 1/a = a^(p-2), by square-and-multiply.
+fn inv(a: &Limbs) -> Limbs {
+    let (exponent, _) = sub_limbs(&P, &[2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    let mut result = from_u64(1);
+    for i in (0..64 * NUM_LIMBS).rev() {
+        result = mul(&result, &result);
+        if (exponent[i / 64] >> (i % 64)) & 1 == 1 {
+            result = mul(&result, a);
+        }
+    }
+    result
+}
+
+// Invert all of the elements of xs, which must be nonzero, with a single
+// inversion by Montgomery's trick.
+fn batch_inv(xs: &[Limbs]) -> Vec<Limbs> {
+    let mut prefix = Vec::with_capacity(xs.len());
+    let mut acc = from_u64(1);
+    for x in xs.iter() {
+        prefix.push(acc);
+        acc = mul(&acc, x);
+    }
+    let mut acc_inv = inv(&acc);
+    let mut result = vec![[0u64; NUM_LIMBS]; xs.len()];
+    for i in (0..xs.len()).rev() {
+        result[i] = mul(&acc_inv, &prefix[i]);
+        acc_inv = mul(&acc_inv, &xs[i]);
+    }
+    result
+}
+
+// Parse a decimal integer in [0, p) into Montgomery form.
+fn from_decimal(s: &str) -> Limbs {
+    let ten = from_u64(10);
+    s.bytes().fold([0u64; NUM_LIMBS], |acc, digit| {
+        assert!(digit.is_ascii_digit(), "invalid digit in constant");
+        add(&mul(&acc, &ten), &from_u64((digit - b'0') as u64))
+    })
+}
+
+// Read the decimal string of the PrimeFieldElement constant with the given name.
+fn read_constant(constants: &str, name: &str) -> Limbs {
+    let start = constants.find(&format!("pub const {}:", name)).expect("constant not found");
+    let rest = &constants[start..];
+    let open = rest.find('"').expect("constant is not a string") + 1;
+    let close = open + rest[open..].find('"').expect("unterminated string");
+    from_decimal(&rest[open..close])
+}
+
+// A point (X:Y:Z:T) on the twisted Edwards curve, with x = X/Z, y = Y/Z, T = XY/Z.
+#[derive(Copy, Clone)]
+struct EdwardsPoint {
+    X: Limbs,
+    Y: Limbs,
+    Z: Limbs,
+    T: Limbs,
+}
+
+impl EdwardsPoint {
+    // Map the point (u, v) on E_0 to (u/v, (u - 1)/(u + 1)).
+    fn from_montgomery(u: &Limbs, v: &Limbs) -> EdwardsPoint {
+        let one = from_u64(1);
+        let x = mul(u, &inv(v));
+        let y = mul(&sub(u, &one), &inv(&add(u, &one)));
+        EdwardsPoint{ X: x, Y: y, Z: one, T: mul(&x, &y) }
+    }
+
+    // The unified addition formulas of Hisil, Wong, Carter and Dawson, with a = 2
+    // and d = -2, which are complete as a is a square and d is not.
+    fn add(&self, other: &EdwardsPoint) -> EdwardsPoint {
+        let a = mul(&self.X, &other.X);
+        let b = mul(&self.Y, &other.Y);
+        let tt = mul(&self.T, &other.T);
+        let c = neg(&add(&tt, &tt));                                      // = d*T1*T2
+        let d = mul(&self.Z, &other.Z);
+        let e = sub(&sub(&mul(&add(&self.X, &self.Y), &add(&other.X, &other.Y)), &a), &b);
+        let f = sub(&d, &c);
+        let g = add(&d, &c);
+        let h = sub(&b, &add(&a, &a));                                    // = B - a*A
+        EdwardsPoint{ X: mul(&e, &f), Y: mul(&g, &h), Z: mul(&f, &g), T: mul(&e, &h) }
+    }
+
+    // Returns the canonical (x, y, d*x*y).
+    fn to_affine(self) -> [Limbs; 3] {
+        let z_inv = inv(&self.Z);
+        let x = mul(&self.X, &z_inv);
+        let y = mul(&self.Y, &z_inv);
+        let xy = mul(&x, &y);
+        let dxy = neg(&add(&xy, &xy));
+        [to_canonical(&x), to_canonical(&y), to_canonical(&dxy)]
+    }
+}
+
+// Compute the table of sum_j u_j [2^(j*SPACING + b*BLOCK_SIZE)]base for each
+// block b and 0 < u < 2^TEETH.
+fn comb_table(base: &EdwardsPoint) -> Vec<Vec<[Limbs; 3]>> {
+    let mut teeth = vec![vec![*base; TEETH]; BLOCKS];
+    let mut Q = *base;
+    for i in 0..SPACING * TEETH {
+        if i % BLOCK_SIZE == 0 {
+            teeth[(i % SPACING) / BLOCK_SIZE][i / SPACING] = Q;
+        }
+        Q = Q.add(&Q);
+    }
+    teeth.iter().map(|block| {
+        let mut entries: Vec<EdwardsPoint> = Vec::with_capacity(1 << TEETH);
+        entries.push(EdwardsPoint{ X: [0; NUM_LIMBS], Y: from_u64(1), Z: from_u64(1), T: [0; NUM_LIMBS] });
+        for u in 1..(1usize << TEETH) {
+            let top = 63 - (u as u64).leading_zeros() as usize;
+            let entry = entries[u - (1 << top)].add(&block[top]);
+            entries.push(entry);
+        }
+        entries[1..].iter().map(|entry| entry.to_affine()).collect()
+    }).collect()
+}
+
+// Compute the affine x([2^i]Q) = -u([2^i]base) on E_0 for Q = \tau(base), for
+// each i until [2^i]base = O or i = LADDER_BITS.
+fn ladder_table(base: &EdwardsPoint) -> Vec<Limbs> {
+    let mut numerators = Vec::with_capacity(LADDER_BITS);
+    let mut denominators = Vec::with_capacity(LADDER_BITS);
+    let mut Q = *base;
+    for _ in 0..LADDER_BITS {
+        // u = (1 + y)/(1 - y) = (Z + Y)/(Z - Y), and y = 1 only for O.
+        let denominator = sub(&Q.Z, &Q.Y);
+        if to_canonical(&denominator) == [0u64; NUM_LIMBS] {
+            break;
+        }
+        numerators.push(add(&Q.Z, &Q.Y));
+        denominators.push(denominator);
+        Q = Q.add(&Q);
+    }
+    numerators.iter().zip(batch_inv(&denominators).iter())
+              .map(|(n, d_inv)| to_canonical(&neg(&mul(n, d_inv))))
+              .collect()
+}
+
+fn write_element(out: &mut String, limbs: &Limbs) {
+    out.push_str("PrimeFieldElement::from_u64_limbs([");
+    for (i, limb) in limbs.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        write!(out, "{:#x}", limb).unwrap();
+    }
+    out.push_str("])");
+}
+
+fn write_table(out: &mut String, name: &str, table: &[Vec<[Limbs; 3]>]) {
+    writeln!(out, "pub(crate) static {}: FixedBaseTable = [", name).unwrap();
+    for block in table.iter() {
+        out.push_str("    [\n");
+        for entry in block.iter() {
+            out.push_str("        AffineEdwardsPoint{ x: ");
+            write_element(out, &entry[0]);
+            out.push_str(", y: ");
+            write_element(out, &entry[1]);
+            out.push_str(", dxy: ");
+            write_element(out, &entry[2]);
+            out.push_str(" },\n");
+        }
+        out.push_str("    ],\n");
+    }
+    out.push_str("];\n");
+}
+
+fn write_ladder_table(out: &mut String, name: &str, table: &[Limbs]) {
+    writeln!(out, "pub(crate) static {}: [PrimeFieldElement; {}] = [", name, table.len()).unwrap();
+    for entry in table.iter() {
+        out.push_str("    ");
+        write_element(out, entry);
+        out.push_str(",\n");
+    }
+    out.push_str("];\n");
+}
+
+/// Write the comb and ladder tables for `P_A` and `P_B` to `fixed_base_tables.rs` in `out_dir`.
+pub fn generate(out_dir: &Path) {
+    let constants = fs::read_to_string("src/constants.rs").expect("cannot read src/constants.rs");
+    let mut out = String::from("// Generated by build.rs from the bases in src/constants.rs.\n\n");
+    for &(table, ladder, x, y) in [("FIXED_BASE_TABLE_PA", "FIXED_BASIS_TABLE_PA", "AFFINE_X_PA", "AFFINE_Y_PA"),
+                                   ("FIXED_BASE_TABLE_PB", "FIXED_BASIS_TABLE_PB", "AFFINE_X_PB", "AFFINE_Y_PB")].iter() {
+        let base = EdwardsPoint::from_montgomery(&read_constant(&constants, x), &read_constant(&constants, y));
+        write_table(&mut out, table, &comb_table(&base));
+        write_ladder_table(&mut out, ladder, &ladder_table(&base));
+    }
+    fs::write(out_dir.join("fixed_base_tables.rs"), out).expect("cannot write fixed_base_tables.rs");
+}
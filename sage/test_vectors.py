@@ -0,0 +1,361 @@
+"""
+    Test vectors for the pairings and torsion bases of src/pairing.rs.
+
+    This is a plain Python 3 script, so it runs without Sage:
+
+    > python3 test_vectors.py
+
+    The torsion bases are computed by a transcription of generate_2_torsion_basis
+    and generate_3_torsion_basis of sidh_curve_and_isogeny_arithmetic.sage, which
+    reads the Elligator constants from sidh_parameters.sage. The pairings are
+    computed by textbook Miller loops with affine chord-and-tangent lines, which
+    share nothing with the doubling-only and tripling-only loops of
+    sidh_pairings.sage and of the Rust code.
+"""
+
+import os
+import re
+
+eA, eB = 372, 239
+p = 2**eA * 3**eB - 1
+oA, oB = 2**eA, 3**eB
+
+here = os.path.dirname(os.path.abspath(__file__))
+with open(os.path.join(here, "sidh_parameters.sage")) as params:
+    source = params.read()
+sqrt17 = int(re.search(r"sqrt17 = Fp\((\d+)\)", source).group(1))
+elligator = [int(n) for n in re.findall(r"\d+", re.search(r"\nlist = \[(.*?)\]", source, re.S).group(1))]
+
+# -----------------------------------------------------------------------------
+# F_p^2 = F_p(i), with i^2 = -1, as pairs (a, b) = a + b*i.
+
+def add(x, y): return ((x[0] + y[0]) % p, (x[1] + y[1]) % p)
+def sub(x, y): return ((x[0] - y[0]) % p, (x[1] - y[1]) % p)
+def neg(x): return (-x[0] % p, -x[1] % p)
+def mul(x, y): return ((x[0] * y[0] - x[1] * y[1]) % p, (x[0] * y[1] + x[1] * y[0]) % p)
+def sqr(x): return mul(x, x)
+def inv(x):
+    n = pow(x[0] * x[0] + x[1] * x[1], p - 2, p)
+    return (x[0] * n % p, -x[1] * n % p)
+def div(x, y): return mul(x, inv(y))
+def fp(n): return (n % p, 0)
+def power(x, e):
+    r = fp(1)
+    for bit in bin(e)[2:]:
+        r = sqr(r)
+        if bit == "1":
+            r = mul(r, x)
+    return r
+
+ZERO, ONE = fp(0), fp(1)
+
+# -----------------------------------------------------------------------------
+# x-only arithmetic, as in sidh_curve_and_isogeny_arithmetic.sage.
+
+def xDBL(X, Z, A24, C24):
+    t0 = sqr(sub(X, Z))
+    t1 = sqr(add(X, Z))
+    Z2 = mul(C24, t0)
+    X2 = mul(Z2, t1)
+    t1 = sub(t1, t0)
+    Z2 = mul(add(Z2, mul(A24, t1)), t1)
+    return X2, Z2
+
+def xDBLe(X, Z, A, C, e):
+    A24den = add(add(C, C), add(C, C))
+    A24num = add(add(C, C), A)
+    for _ in range(e):
+        X, Z = xDBL(X, Z, A24num, A24den)
+    return X, Z
+
+def xTPL(X, Z, A24, C24):
+    t2 = sub(X, Z)
+    t3 = add(X, Z)
+    t0 = sqr(t2)
+    t1 = sqr(t3)
+    t4 = mul(C24, t0)
+    t5 = mul(t4, t1)
+    t1 = sub(t1, t0)
+    t4 = mul(add(t4, mul(A24, t1)), t1)
+    t0 = mul(t2, add(t5, t4))
+    t1 = mul(t3, sub(t5, t4))
+    t4 = sqr(sub(t0, t1))
+    t5 = sqr(add(t0, t1))
+    return mul(Z, t5), mul(X, t4)
+
+def xTPLe(X, Z, A, C, e):
+    A24 = add(A, add(C, C))
+    C24 = mul(fp(4), C)
+    for _ in range(e):
+        X, Z = xTPL(X, Z, A24, C24)
+    return X, Z
+
+def is_zero(x): return x == ZERO
+
+# -----------------------------------------------------------------------------
+# Square roots, cube test and Elligator, as in sidh_field_arithmetic.sage and
+# sidh_curve_and_isogeny_arithmetic.sage.
+
+def sqrt_fp2_frac(u, v):
+    u0, u1 = u
+    v0, v1 = v
+    t0 = (v0 * v0 + v1 * v1) % p
+    t1 = (u0 * v0 + u1 * v1) % p
+    t2 = (u1 * v0 - u0 * v1) % p
+    t3 = (t1 * t1 + t2 * t2) % p
+    t = pow(t3, (p + 1) // 4, p)
+    t = 2 * (t + t1) % p
+    t3 = t0 * t0 * t0 * t % p
+    t3 = pow(t3, (p - 3) // 4, p)
+    t3 = t0 * t3 % p
+    t1 = t * t3 % p
+    y0 = t1 * pow(2, p - 2, p) % p
+    y1 = t3 * t2 % p
+    t1 = t1 * t1 * t0 % p
+    if t1 != t:
+        y0, y1 = y1, y0
+    t0 = ((y0 * y0 - y1 * y1) * v0 - 2 * y0 * y1 * v1) % p
+    if t0 != u0:
+        y1 = -y1 % p
+    return (y0, y1)
+
+def is_cube_fp2(u):
+    u0, u1 = u
+    t0 = pow(u0 * u0 + u1 * u1, p - 2, p)
+    v = ((u0 * u0 - u1 * u1) * t0 % p, -2 * u0 * u1 * t0 % p)
+    for _ in range(eA):
+        v = sqr(v)
+    for _ in range(eB - 1):
+        v = mul(sqr(v), v)
+    return v == ONE
+
+def get_X_on_curve(r, A0, A1):
+    r1 = elligator[2 * r - 2]
+    r0 = elligator[2 * r - 1]
+    rsq = r * r
+    v0 = (A0 * r0 - A1 * r1) % p
+    v1 = (A0 * r1 + A1 * r0) % p
+    t0 = (v0 + A0) % p
+    t1 = (v1 + A1) % p
+    t2 = 2 * v0 * v1 % p
+    a = (v0 - t2 * A1) % p
+    b = (t2 * A0 + v1) % p
+    t2 = (2 * v0 + t0) % p
+    t3 = v0 * v0 % p
+    a = (a + t0 * t3) % p
+    t0 = v1 * v1 % p
+    a = (a - t0 * t2) % p
+    b = (b - t0 * t1) % p
+    t1 = (t1 + 2 * v1) * t3 % p
+    b = (t1 + b) % p
+    t0 = (a * a + b * b) % p
+    t1 = pow(t0, (p + 1) // 4, p)
+    if t1 * t1 % p != t0:
+        x0 = rsq * (4 * v0 - v1) % p
+        x1 = rsq * (4 * v1 + v0) % p
+        a, b = rsq * (4 * a - b) % p, rsq * (4 * b + a) % p
+        t1 = t1 * rsq * sqrt17 % p
+    else:
+        x0, x1 = v0, v1
+    return x0, x1, t1, a, b
+
+def get_pt_on_curve(r, A0, A1):
+    x0, x1, t1, a, b = get_X_on_curve(r, A0, A1)
+    t0 = (a + t1) * pow(2, p - 2, p) % p
+    t1 = pow(t0, (p - 3) // 4, p)
+    t3 = t0 * t1 % p
+    t2 = t3 * t3 % p
+    t1 = t1 * pow(2, p - 2, p) * b % p
+    if t2 == 0:
+        y0, y1 = t3, t1
+    else:
+        y0, y1 = t1, -t3 % p
+    return x0, x1, y0, y1
+
+def get_point_notin_2E(alpha, A0, A1):
+    X0 = (8 * (2 * A0 - A1) - A0) % p
+    X1 = (8 * (A0 + 2 * A1) - A1) % p
+    while True:
+        alpha += 1
+        x0 = ((X0 + 52 * alpha) * alpha + 4) % p
+        x1 = ((X1 + 47 * alpha) * alpha + 1) % p
+        t0 = alpha * alpha * (x0 * x0 + x1 * x1) % p
+        if pow(t0, (p + 1) // 2, p) == t0:
+            return alpha
+
+def y_of(A, X, Z):
+    # Y with Y^2 Z = X^3 + A X^2 Z + X Z^2, as in generate_*_torsion_basis.
+    u = mul(add(mul(add(X, mul(A, Z)), X), sqr(Z)), X)
+    v = mul(sqr(Z), Z)
+    return mul(sqrt_fp2_frac(u, v), Z)
+
+def generate_2_torsion_basis(A):
+    A0, A1 = A
+    alpha = get_point_notin_2E(0, A0, A1)
+    X1, Z1 = xTPLe((4 * alpha % p, alpha), ONE, A, ONE, eB)
+    XP, ZP = xDBLe(X1, Z1, A, ONE, eA - 1)
+    while True:
+        alpha = get_point_notin_2E(alpha, A0, A1)
+        X2, Z2 = xTPLe((4 * alpha % p, alpha), ONE, A, ONE, eB)
+        XQ, ZQ = xDBLe(X2, Z2, A, ONE, eA - 1)
+        if not is_zero(sub(mul(XP, ZQ), mul(XQ, ZP))):
+            break
+    return X1, y_of(A, X1, Z1), Z1, X2, y_of(A, X2, Z2), Z2
+
+def get_3_torsion_elt(A, r):
+    x0, x1, _, _, _ = get_X_on_curve(r, A[0], A[1])
+    X, Z = xDBLe((x0, x1), ONE, A, ONE, eA)
+    XX, ZZ = X, Z
+    triples = 0
+    while not is_zero(ZZ):
+        X3, Z3 = XX, ZZ
+        XX, ZZ = xTPL(XX, ZZ, add(A, fp(2)), fp(4))
+        triples += 1
+    return X3, Z3, X, Z, triples
+
+def generate_3_torsion_basis(A):
+    r = 1
+    X3, Z3, X, Z, triples = get_3_torsion_elt(A, r)
+    if triples == eB:
+        pts_found = 1
+        X1, Z1 = X, Z
+        Y1 = y_of(A, X1, Z1)
+    else:
+        pts_found = 0
+
+    Y3 = y_of(A, X3, Z3)
+    f0 = sqr(X3)
+    t0 = sqr(Z3)
+    fX = mul(A, mul(X3, Z3))
+    fX = add(add(add(fX, fX), t0), add(add(f0, f0), f0))
+    f0 = sub(t0, f0)
+    fX = mul(fX, Z3)
+    fY = neg(add(mul(Y3, Z3), mul(Y3, Z3)))
+    c = mul(add(fY, fY), Y3)
+    fY = mul(fY, Z3)
+    f0 = mul(f0, X3)
+    fX, fY, f0 = mul(c, fX), mul(c, fY), mul(c, f0)
+
+    while True:
+        while pts_found < 2:
+            r += 1
+            x0, x1, y0, y1 = get_pt_on_curve(r, A[0], A[1])
+            f = add(add(mul(fX, (x0, x1)), mul(fY, (y0, y1))), f0)
+            if not is_cube_fp2(f):
+                X, Z = xDBLe((x0, x1), ONE, A, ONE, eA)
+                Y = y_of(A, X, Z)
+                if pts_found == 0:
+                    X1, Y1, Z1 = X, Y, Z
+                    X3, Z3 = xTPLe(X1, Z1, A, ONE, eB - 1)
+                else:
+                    X2, Y2, Z2 = X, Y, Z
+                    X4, Z4 = xTPLe(X2, Z2, A, ONE, eB - 1)
+                pts_found += 1
+        pts_found -= 1
+        if not is_zero(sub(mul(X3, Z4), mul(X4, Z3))):
+            break
+    return X1, Y1, Z1, X2, Y2, Z2
+
+# -----------------------------------------------------------------------------
+# Affine points on y^2 = x^3 + a x^2 + x, with None for the point at infinity,
+# and textbook Miller loops.
+
+def ec_add(a, P, Q):
+    if P is None: return Q
+    if Q is None: return P
+    if P[0] == Q[0] and add(P[1], Q[1]) == ZERO:
+        return None
+    if P == Q:
+        lam = div(add(add(mul(fp(3), sqr(P[0])), mul(mul(fp(2), a), P[0])), ONE), add(P[1], P[1]))
+    else:
+        lam = div(sub(Q[1], P[1]), sub(Q[0], P[0]))
+    x = sub(sub(sub(sqr(lam), a), P[0]), Q[0])
+    return (x, sub(mul(lam, sub(P[0], x)), P[1]))
+
+def ec_mul(a, k, P):
+    R = None
+    for bit in bin(k)[2:]:
+        R = ec_add(a, R, R)
+        if bit == "1":
+            R = ec_add(a, R, P)
+    return R
+
+def on_curve(a, P):
+    x, y = P
+    return sqr(y) == add(add(mul(sqr(x), x), mul(a, sqr(x))), x)
+
+# The value at S of the line through T and U, divided by the vertical line at
+# T + U. All functions are normalised at infinity.
+def line(a, T, U, S):
+    if T[0] == U[0] and add(T[1], U[1]) == ZERO:
+        return sub(S[0], T[0])
+    if T == U:
+        lam = div(add(add(mul(fp(3), sqr(T[0])), mul(mul(fp(2), a), T[0])), ONE), add(T[1], T[1]))
+    else:
+        lam = div(sub(U[1], T[1]), sub(U[0], T[0]))
+    V = ec_add(a, T, U)
+    l = sub(sub(S[1], T[1]), mul(lam, sub(S[0], T[0])))
+    return div(l, sub(S[0], V[0]))
+
+# f_{n,P}(Q), with div(f_{n,P}) = n(P) - n(O).
+def miller(a, n, P, Q):
+    f, T = ONE, P
+    for bit in bin(n)[3:]:
+        f = mul(sqr(f), line(a, T, T, Q))
+        T = ec_add(a, T, T)
+        if bit == "1":
+            f = mul(f, line(a, T, P, Q))
+            T = ec_add(a, T, P)
+    assert T is None
+    return f
+
+def tate(a, n, P, Q):
+    return power(miller(a, n, P, Q), (p * p - 1) // n)
+
+def weil(a, n, P, Q):
+    w = div(miller(a, n, P, Q), miller(a, n, Q, P))
+    return neg(w) if n % 2 == 1 else w
+
+def has_order(x, l, e):
+    y = power(x, l**(e - 1))
+    return y != ONE and power(y, l) == ONE
+
+# -----------------------------------------------------------------------------
+
+def affine(X, Y, Z):
+    z = inv(Z)
+    return (mul(X, z), mul(Y, z))
+
+def rust(name, x):
+    print('        const %s: ExtensionFieldElement = ExtensionFieldElement::from_str_const(' % name)
+    print('            "%d",' % x[0])
+    print('            "%d");' % x[1])
+
+if __name__ == "__main__":
+    # The curve of the pairing tests in src/pairing.rs.
+    A = (2341043197209941991220519211619242041294598820532266398372157975001515989197428110084845584470281249404904414134892150192999032184099327420653892619511306818637883211283599346309298278898128478867136153639350095319994399832439,
+         9221111434819607248328703668015403090416189270230638775720430459490474958686394301316049708684118082289706142929973344975240500415268374403185247960921533090119620475701346181489922176944655998757187110091076852079193907186348)
+    C = (5567862735586870181692075744408061194115543800802669412303557080130253262030656668387767993794760218773725317600308695815314499693866785155373972856268355830657718660853133331641855944113898691502448967534707048084356647543388,
+         8427682645450728827990654303677966176180390740375467148708597865629881552256142685168798117480464164548157967542807262837573300376067227525407707032292192722554697622100407172562937983328378484595610432440688689052570271569373)
+    a = div(A, C)
+
+    X1, Y1, Z1, X2, Y2, Z2 = generate_2_torsion_basis(a)
+    P2, Q2 = affine(X1, Y1, Z1), affine(X2, Y2, Z2)
+    X1, Y1, Z1, X2, Y2, Z2 = generate_3_torsion_basis(a)
+    P3, Q3 = affine(X1, Y1, Z1), affine(X2, Y2, Z2)
+
+    for (P, Q, l, e) in [(P2, Q2, 2, eA), (P3, Q3, 3, eB)]:
+        n = l**e
+        for S in [P, Q]:
+            assert on_curve(a, S)
+            assert ec_mul(a, n // l, S) is not None and ec_mul(a, n, S) is None
+        assert has_order(weil(a, n, P, Q), l, e)
+
+    t2, w2 = tate(a, oA, P2, Q2), weil(a, oA, P2, Q2)
+    t3, w3 = tate(a, oB, P3, Q3), weil(a, oB, P3, Q3)
+
+    for name, x in [("BASIS_XP2", P2[0]), ("BASIS_YP2", P2[1]), ("BASIS_XQ2", Q2[0]), ("BASIS_YQ2", Q2[1]),
+                    ("TATE_2E", t2), ("WEIL_2E", w2),
+                    ("BASIS_XP3", P3[0]), ("BASIS_YP3", P3[1]), ("BASIS_XQ3", Q3[0]), ("BASIS_YQ3", Q3[1]),
+                    ("TATE_3E", t3), ("WEIL_3E", w3)]:
+        rust(name, x)
@@ -0,0 +1,1577 @@
+;*******************************************************************************************
+; SIDH: an efficient supersingular isogeny cryptography library
+;
+; Abstract: field arithmetic in x64 assembly for P751 on Windows, using the MULX (BMI2)
+;           and ADCX/ADOX (ADX) instructions
+;*******************************************************************************************
+
+  .const
+; Under Windows we need to use RCX, RDX and R8 as registers for the first three parameters as that 
+; is the usual calling convention (cf. https://docs.microsoft.com/en-us/cpp/build/parameter-passing).
+reg_p1 equ rcx
+reg_p2 equ rdx
+reg_p3 equ r8
+
+; p751 + 1
+p751p1_5  equ 0EEB0000000000000h
+p751p1_6  equ 0E3EC968549F878A8h
+p751p1_7  equ 0DA959B1A13F7CC76h
+p751p1_8  equ 0084E9867D6EBE876h
+p751p1_9  equ 08562B5045CB25748h
+p751p1_10 equ 00E12909F97BADC66h
+p751p1_11 equ 000006FE5D541F71Ch
+
+  .code
+;***********************************************************************
+;  Integer multiplication using MULX/ADCX/ADOX
+;  Schoolbook method, with two interleaved carry chains and the
+;  accumulator kept in registers
+;  Operation: c [reg_p3] = a [reg_p1] * b [reg_p2]
+;  NOTE: a=c or b=c are not allowed
+;***********************************************************************
+mul751_mulx_asm proc
+  push   r12
+  push   r13
+  push   r14
+  push   r15
+  push   rbx
+  push   rdi
+  push   rsi
+  ; MULX uses DX as an implicit source operand, so move b and c to RSI and RDI.
+  mov    rsi, reg_p2
+  mov    rdi, reg_p3
+
+  ; a * b[0-5]
+  xor    r9, r9
+  xor    r10, r10
+  xor    r11, r11
+  xor    r12, r12
+  xor    r13, r13
+  xor    r14, r14
+
+  mov    rdx, [reg_p1]             ; rdx <- a[0]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r8, rbx, [rsi]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r8, rbx, [rsi+8]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r8, rbx, [rsi+16]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+24]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+32]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r15, rbx, [rsi+40]
+  adox   r14, rbx
+  adcx   r15, rax
+  adox   r15, rax
+  mov    [rdi], r9
+
+  mov    rdx, [reg_p1+8]           ; rdx <- a[1]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r8, rbx, [rsi]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r8, rbx, [rsi+8]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+16]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+24]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+32]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r9, rbx, [rsi+40]
+  adox   r15, rbx
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rdi+8], r10
+
+  mov    rdx, [reg_p1+16]          ; rdx <- a[2]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r8, rbx, [rsi]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+8]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+16]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+24]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+32]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r10, rbx, [rsi+40]
+  adox   r9, rbx
+  adcx   r10, rax
+  adox   r10, rax
+  mov    [rdi+16], r11
+
+  mov    rdx, [reg_p1+24]          ; rdx <- a[3]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r8, rbx, [rsi]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+8]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+16]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+24]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r8, rbx, [rsi+32]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r11, rbx, [rsi+40]
+  adox   r10, rbx
+  adcx   r11, rax
+  adox   r11, rax
+  mov    [rdi+24], r12
+
+  mov    rdx, [reg_p1+32]          ; rdx <- a[4]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r8, rbx, [rsi]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+8]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+16]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r8, rbx, [rsi+24]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r8, rbx, [rsi+32]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r12, rbx, [rsi+40]
+  adox   r11, rbx
+  adcx   r12, rax
+  adox   r12, rax
+  mov    [rdi+32], r13
+
+  mov    rdx, [reg_p1+40]          ; rdx <- a[5]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r8, rbx, [rsi]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+8]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r8, rbx, [rsi+16]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r8, rbx, [rsi+24]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r8, rbx, [rsi+32]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r13, rbx, [rsi+40]
+  adox   r12, rbx
+  adcx   r13, rax
+  adox   r13, rax
+  mov    [rdi+40], r14
+
+  mov    rdx, [reg_p1+48]          ; rdx <- a[6]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r8, rbx, [rsi]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r8, rbx, [rsi+8]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r8, rbx, [rsi+16]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r8, rbx, [rsi+24]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+32]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r14, rbx, [rsi+40]
+  adox   r13, rbx
+  adcx   r14, rax
+  adox   r14, rax
+  mov    [rdi+48], r15
+
+  mov    rdx, [reg_p1+56]          ; rdx <- a[7]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r8, rbx, [rsi]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r8, rbx, [rsi+8]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r8, rbx, [rsi+16]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+24]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+32]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r15, rbx, [rsi+40]
+  adox   r14, rbx
+  adcx   r15, rax
+  adox   r15, rax
+  mov    [rdi+56], r9
+
+  mov    rdx, [reg_p1+64]          ; rdx <- a[8]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r8, rbx, [rsi]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r8, rbx, [rsi+8]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+16]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+24]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+32]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r9, rbx, [rsi+40]
+  adox   r15, rbx
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rdi+64], r10
+
+  mov    rdx, [reg_p1+72]          ; rdx <- a[9]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r8, rbx, [rsi]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+8]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+16]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+24]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+32]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r10, rbx, [rsi+40]
+  adox   r9, rbx
+  adcx   r10, rax
+  adox   r10, rax
+  mov    [rdi+72], r11
+
+  mov    rdx, [reg_p1+80]          ; rdx <- a[10]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r8, rbx, [rsi]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+8]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+16]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+24]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r8, rbx, [rsi+32]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r11, rbx, [rsi+40]
+  adox   r10, rbx
+  adcx   r11, rax
+  adox   r11, rax
+  mov    [rdi+80], r12
+
+  mov    rdx, [reg_p1+88]          ; rdx <- a[11]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r8, rbx, [rsi]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+8]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+16]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r8, rbx, [rsi+24]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r8, rbx, [rsi+32]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r12, rbx, [rsi+40]
+  adox   r11, rbx
+  adcx   r12, rax
+  adox   r12, rax
+  mov    [rdi+88], r13
+
+  mov    [rdi+96], r14
+  mov    [rdi+104], r15
+  mov    [rdi+112], r9
+  mov    [rdi+120], r10
+  mov    [rdi+128], r11
+  mov    [rdi+136], r12
+
+  ; c[6-23] += a * b[6-11]
+  xor    r9, r9
+  xor    r10, r10
+  xor    r11, r11
+  xor    r12, r12
+  xor    r13, r13
+  xor    r14, r14
+
+  mov    rdx, [reg_p1]             ; rdx <- a[0]
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r9, [rdi+48]
+  mulx   r8, rbx, [rsi+48]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r8, rbx, [rsi+56]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r8, rbx, [rsi+64]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+72]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+80]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r15, rbx, [rsi+88]
+  adox   r14, rbx
+  adcx   r15, rax
+  adox   r15, rax
+  mov    [rdi+48], r9
+
+  mov    rdx, [reg_p1+8]           ; rdx <- a[1]
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r10, [rdi+56]
+  mulx   r8, rbx, [rsi+48]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r8, rbx, [rsi+56]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+64]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+72]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+80]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r9, rbx, [rsi+88]
+  adox   r15, rbx
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rdi+56], r10
+
+  mov    rdx, [reg_p1+16]          ; rdx <- a[2]
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r11, [rdi+64]
+  mulx   r8, rbx, [rsi+48]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+56]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+64]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+72]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+80]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r10, rbx, [rsi+88]
+  adox   r9, rbx
+  adcx   r10, rax
+  adox   r10, rax
+  mov    [rdi+64], r11
+
+  mov    rdx, [reg_p1+24]          ; rdx <- a[3]
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r12, [rdi+72]
+  mulx   r8, rbx, [rsi+48]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+56]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+64]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+72]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r8, rbx, [rsi+80]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r11, rbx, [rsi+88]
+  adox   r10, rbx
+  adcx   r11, rax
+  adox   r11, rax
+  mov    [rdi+72], r12
+
+  mov    rdx, [reg_p1+32]          ; rdx <- a[4]
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r13, [rdi+80]
+  mulx   r8, rbx, [rsi+48]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+56]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+64]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r8, rbx, [rsi+72]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r8, rbx, [rsi+80]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r12, rbx, [rsi+88]
+  adox   r11, rbx
+  adcx   r12, rax
+  adox   r12, rax
+  mov    [rdi+80], r13
+
+  mov    rdx, [reg_p1+40]          ; rdx <- a[5]
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r14, [rdi+88]
+  mulx   r8, rbx, [rsi+48]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+56]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r8, rbx, [rsi+64]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r8, rbx, [rsi+72]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r8, rbx, [rsi+80]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r13, rbx, [rsi+88]
+  adox   r12, rbx
+  adcx   r13, rax
+  adox   r13, rax
+  mov    [rdi+88], r14
+
+  mov    rdx, [reg_p1+48]          ; rdx <- a[6]
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r15, [rdi+96]
+  mulx   r8, rbx, [rsi+48]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r8, rbx, [rsi+56]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r8, rbx, [rsi+64]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r8, rbx, [rsi+72]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+80]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r14, rbx, [rsi+88]
+  adox   r13, rbx
+  adcx   r14, rax
+  adox   r14, rax
+  mov    [rdi+96], r15
+
+  mov    rdx, [reg_p1+56]          ; rdx <- a[7]
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r9, [rdi+104]
+  mulx   r8, rbx, [rsi+48]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r8, rbx, [rsi+56]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r8, rbx, [rsi+64]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+72]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+80]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r15, rbx, [rsi+88]
+  adox   r14, rbx
+  adcx   r15, rax
+  adox   r15, rax
+  mov    [rdi+104], r9
+
+  mov    rdx, [reg_p1+64]          ; rdx <- a[8]
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r10, [rdi+112]
+  mulx   r8, rbx, [rsi+48]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r8, rbx, [rsi+56]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+64]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+72]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+80]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r9, rbx, [rsi+88]
+  adox   r15, rbx
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rdi+112], r10
+
+  mov    rdx, [reg_p1+72]          ; rdx <- a[9]
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r11, [rdi+120]
+  mulx   r8, rbx, [rsi+48]
+  adox   r11, rbx
+  adcx   r12, r8
+  mulx   r8, rbx, [rsi+56]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+64]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+72]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+80]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r10, rbx, [rsi+88]
+  adox   r9, rbx
+  adcx   r10, rax
+  adox   r10, rax
+  mov    [rdi+120], r11
+
+  mov    rdx, [reg_p1+80]          ; rdx <- a[10]
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r12, [rdi+128]
+  mulx   r8, rbx, [rsi+48]
+  adox   r12, rbx
+  adcx   r13, r8
+  mulx   r8, rbx, [rsi+56]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+64]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+72]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r8, rbx, [rsi+80]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r11, rbx, [rsi+88]
+  adox   r10, rbx
+  adcx   r11, rax
+  adox   r11, rax
+  mov    [rdi+128], r12
+
+  mov    rdx, [reg_p1+88]          ; rdx <- a[11]
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r13, [rdi+136]
+  mulx   r8, rbx, [rsi+48]
+  adox   r13, rbx
+  adcx   r14, r8
+  mulx   r8, rbx, [rsi+56]
+  adox   r14, rbx
+  adcx   r15, r8
+  mulx   r8, rbx, [rsi+64]
+  adox   r15, rbx
+  adcx   r9, r8
+  mulx   r8, rbx, [rsi+72]
+  adox   r9, rbx
+  adcx   r10, r8
+  mulx   r8, rbx, [rsi+80]
+  adox   r10, rbx
+  adcx   r11, r8
+  mulx   r12, rbx, [rsi+88]
+  adox   r11, rbx
+  adcx   r12, rax
+  adox   r12, rax
+  mov    [rdi+136], r13
+
+  mov    [rdi+144], r14
+  mov    [rdi+152], r15
+  mov    [rdi+160], r9
+  mov    [rdi+168], r10
+  mov    [rdi+176], r11
+  mov    [rdi+184], r12
+
+  pop    rsi
+  pop    rdi
+  pop    rbx
+  pop    r15
+  pop    r14
+  pop    r13
+  pop    r12
+  ret
+mul751_mulx_asm endp
+
+;***********************************************************************
+;  Integer squaring using MULX/ADCX/ADOX
+;  Computes the off-diagonal products once, then doubles and adds the
+;  squares a[i]^2
+;  Operation: c [reg_p2] = a [reg_p1]^2
+;  NOTE: a=c is not allowed
+;***********************************************************************
+sqr751_mulx_asm proc
+  push   rdi
+  ; MULX uses DX as an implicit source operand, so move c to RDI.
+  mov    rdi, reg_p2
+  xor    rax, rax
+  mov    [rdi], rax
+  mov    [rdi+184], rax
+
+  mov    rdx, [reg_p1]             ; rdx <- a[0]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r9, r8, [reg_p1+8]
+  mov    [rdi+8], r8
+  mulx   r11, r10, [reg_p1+16]
+  adcx   r10, r9
+  mov    [rdi+16], r10
+  mulx   r9, r8, [reg_p1+24]
+  adcx   r8, r11
+  mov    [rdi+24], r8
+  mulx   r11, r10, [reg_p1+32]
+  adcx   r10, r9
+  mov    [rdi+32], r10
+  mulx   r9, r8, [reg_p1+40]
+  adcx   r8, r11
+  mov    [rdi+40], r8
+  mulx   r11, r10, [reg_p1+48]
+  adcx   r10, r9
+  mov    [rdi+48], r10
+  mulx   r9, r8, [reg_p1+56]
+  adcx   r8, r11
+  mov    [rdi+56], r8
+  mulx   r11, r10, [reg_p1+64]
+  adcx   r10, r9
+  mov    [rdi+64], r10
+  mulx   r9, r8, [reg_p1+72]
+  adcx   r8, r11
+  mov    [rdi+72], r8
+  mulx   r11, r10, [reg_p1+80]
+  adcx   r10, r9
+  mov    [rdi+80], r10
+  mulx   r9, r8, [reg_p1+88]
+  adcx   r8, r11
+  mov    [rdi+88], r8
+  adcx   r9, rax
+  mov    [rdi+96], r9
+
+  mov    rdx, [reg_p1+8]           ; rdx <- a[1]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r9, r8, [reg_p1+16]
+  adox   r8, [rdi+24]
+  mov    [rdi+24], r8
+  mulx   r11, r10, [reg_p1+24]
+  adcx   r10, r9
+  adox   r10, [rdi+32]
+  mov    [rdi+32], r10
+  mulx   r9, r8, [reg_p1+32]
+  adcx   r8, r11
+  adox   r8, [rdi+40]
+  mov    [rdi+40], r8
+  mulx   r11, r10, [reg_p1+40]
+  adcx   r10, r9
+  adox   r10, [rdi+48]
+  mov    [rdi+48], r10
+  mulx   r9, r8, [reg_p1+48]
+  adcx   r8, r11
+  adox   r8, [rdi+56]
+  mov    [rdi+56], r8
+  mulx   r11, r10, [reg_p1+56]
+  adcx   r10, r9
+  adox   r10, [rdi+64]
+  mov    [rdi+64], r10
+  mulx   r9, r8, [reg_p1+64]
+  adcx   r8, r11
+  adox   r8, [rdi+72]
+  mov    [rdi+72], r8
+  mulx   r11, r10, [reg_p1+72]
+  adcx   r10, r9
+  adox   r10, [rdi+80]
+  mov    [rdi+80], r10
+  mulx   r9, r8, [reg_p1+80]
+  adcx   r8, r11
+  adox   r8, [rdi+88]
+  mov    [rdi+88], r8
+  mulx   r11, r10, [reg_p1+88]
+  adcx   r10, r9
+  adox   r10, [rdi+96]
+  mov    [rdi+96], r10
+  adcx   r11, rax
+  adox   r11, rax
+  mov    [rdi+104], r11
+
+  mov    rdx, [reg_p1+16]          ; rdx <- a[2]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r9, r8, [reg_p1+24]
+  adox   r8, [rdi+40]
+  mov    [rdi+40], r8
+  mulx   r11, r10, [reg_p1+32]
+  adcx   r10, r9
+  adox   r10, [rdi+48]
+  mov    [rdi+48], r10
+  mulx   r9, r8, [reg_p1+40]
+  adcx   r8, r11
+  adox   r8, [rdi+56]
+  mov    [rdi+56], r8
+  mulx   r11, r10, [reg_p1+48]
+  adcx   r10, r9
+  adox   r10, [rdi+64]
+  mov    [rdi+64], r10
+  mulx   r9, r8, [reg_p1+56]
+  adcx   r8, r11
+  adox   r8, [rdi+72]
+  mov    [rdi+72], r8
+  mulx   r11, r10, [reg_p1+64]
+  adcx   r10, r9
+  adox   r10, [rdi+80]
+  mov    [rdi+80], r10
+  mulx   r9, r8, [reg_p1+72]
+  adcx   r8, r11
+  adox   r8, [rdi+88]
+  mov    [rdi+88], r8
+  mulx   r11, r10, [reg_p1+80]
+  adcx   r10, r9
+  adox   r10, [rdi+96]
+  mov    [rdi+96], r10
+  mulx   r9, r8, [reg_p1+88]
+  adcx   r8, r11
+  adox   r8, [rdi+104]
+  mov    [rdi+104], r8
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rdi+112], r9
+
+  mov    rdx, [reg_p1+24]          ; rdx <- a[3]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r9, r8, [reg_p1+32]
+  adox   r8, [rdi+56]
+  mov    [rdi+56], r8
+  mulx   r11, r10, [reg_p1+40]
+  adcx   r10, r9
+  adox   r10, [rdi+64]
+  mov    [rdi+64], r10
+  mulx   r9, r8, [reg_p1+48]
+  adcx   r8, r11
+  adox   r8, [rdi+72]
+  mov    [rdi+72], r8
+  mulx   r11, r10, [reg_p1+56]
+  adcx   r10, r9
+  adox   r10, [rdi+80]
+  mov    [rdi+80], r10
+  mulx   r9, r8, [reg_p1+64]
+  adcx   r8, r11
+  adox   r8, [rdi+88]
+  mov    [rdi+88], r8
+  mulx   r11, r10, [reg_p1+72]
+  adcx   r10, r9
+  adox   r10, [rdi+96]
+  mov    [rdi+96], r10
+  mulx   r9, r8, [reg_p1+80]
+  adcx   r8, r11
+  adox   r8, [rdi+104]
+  mov    [rdi+104], r8
+  mulx   r11, r10, [reg_p1+88]
+  adcx   r10, r9
+  adox   r10, [rdi+112]
+  mov    [rdi+112], r10
+  adcx   r11, rax
+  adox   r11, rax
+  mov    [rdi+120], r11
+
+  mov    rdx, [reg_p1+32]          ; rdx <- a[4]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r9, r8, [reg_p1+40]
+  adox   r8, [rdi+72]
+  mov    [rdi+72], r8
+  mulx   r11, r10, [reg_p1+48]
+  adcx   r10, r9
+  adox   r10, [rdi+80]
+  mov    [rdi+80], r10
+  mulx   r9, r8, [reg_p1+56]
+  adcx   r8, r11
+  adox   r8, [rdi+88]
+  mov    [rdi+88], r8
+  mulx   r11, r10, [reg_p1+64]
+  adcx   r10, r9
+  adox   r10, [rdi+96]
+  mov    [rdi+96], r10
+  mulx   r9, r8, [reg_p1+72]
+  adcx   r8, r11
+  adox   r8, [rdi+104]
+  mov    [rdi+104], r8
+  mulx   r11, r10, [reg_p1+80]
+  adcx   r10, r9
+  adox   r10, [rdi+112]
+  mov    [rdi+112], r10
+  mulx   r9, r8, [reg_p1+88]
+  adcx   r8, r11
+  adox   r8, [rdi+120]
+  mov    [rdi+120], r8
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rdi+128], r9
+
+  mov    rdx, [reg_p1+40]          ; rdx <- a[5]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r9, r8, [reg_p1+48]
+  adox   r8, [rdi+88]
+  mov    [rdi+88], r8
+  mulx   r11, r10, [reg_p1+56]
+  adcx   r10, r9
+  adox   r10, [rdi+96]
+  mov    [rdi+96], r10
+  mulx   r9, r8, [reg_p1+64]
+  adcx   r8, r11
+  adox   r8, [rdi+104]
+  mov    [rdi+104], r8
+  mulx   r11, r10, [reg_p1+72]
+  adcx   r10, r9
+  adox   r10, [rdi+112]
+  mov    [rdi+112], r10
+  mulx   r9, r8, [reg_p1+80]
+  adcx   r8, r11
+  adox   r8, [rdi+120]
+  mov    [rdi+120], r8
+  mulx   r11, r10, [reg_p1+88]
+  adcx   r10, r9
+  adox   r10, [rdi+128]
+  mov    [rdi+128], r10
+  adcx   r11, rax
+  adox   r11, rax
+  mov    [rdi+136], r11
+
+  mov    rdx, [reg_p1+48]          ; rdx <- a[6]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r9, r8, [reg_p1+56]
+  adox   r8, [rdi+104]
+  mov    [rdi+104], r8
+  mulx   r11, r10, [reg_p1+64]
+  adcx   r10, r9
+  adox   r10, [rdi+112]
+  mov    [rdi+112], r10
+  mulx   r9, r8, [reg_p1+72]
+  adcx   r8, r11
+  adox   r8, [rdi+120]
+  mov    [rdi+120], r8
+  mulx   r11, r10, [reg_p1+80]
+  adcx   r10, r9
+  adox   r10, [rdi+128]
+  mov    [rdi+128], r10
+  mulx   r9, r8, [reg_p1+88]
+  adcx   r8, r11
+  adox   r8, [rdi+136]
+  mov    [rdi+136], r8
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rdi+144], r9
+
+  mov    rdx, [reg_p1+56]          ; rdx <- a[7]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r9, r8, [reg_p1+64]
+  adox   r8, [rdi+120]
+  mov    [rdi+120], r8
+  mulx   r11, r10, [reg_p1+72]
+  adcx   r10, r9
+  adox   r10, [rdi+128]
+  mov    [rdi+128], r10
+  mulx   r9, r8, [reg_p1+80]
+  adcx   r8, r11
+  adox   r8, [rdi+136]
+  mov    [rdi+136], r8
+  mulx   r11, r10, [reg_p1+88]
+  adcx   r10, r9
+  adox   r10, [rdi+144]
+  mov    [rdi+144], r10
+  adcx   r11, rax
+  adox   r11, rax
+  mov    [rdi+152], r11
+
+  mov    rdx, [reg_p1+64]          ; rdx <- a[8]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r9, r8, [reg_p1+72]
+  adox   r8, [rdi+136]
+  mov    [rdi+136], r8
+  mulx   r11, r10, [reg_p1+80]
+  adcx   r10, r9
+  adox   r10, [rdi+144]
+  mov    [rdi+144], r10
+  mulx   r9, r8, [reg_p1+88]
+  adcx   r8, r11
+  adox   r8, [rdi+152]
+  mov    [rdi+152], r8
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rdi+160], r9
+
+  mov    rdx, [reg_p1+72]          ; rdx <- a[9]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r9, r8, [reg_p1+80]
+  adox   r8, [rdi+152]
+  mov    [rdi+152], r8
+  mulx   r11, r10, [reg_p1+88]
+  adcx   r10, r9
+  adox   r10, [rdi+160]
+  mov    [rdi+160], r10
+  adcx   r11, rax
+  adox   r11, rax
+  mov    [rdi+168], r11
+
+  mov    rdx, [reg_p1+80]          ; rdx <- a[10]
+  xor    rax, rax                  ; Clear CF and OF
+  mulx   r9, r8, [reg_p1+88]
+  adox   r8, [rdi+168]
+  mov    [rdi+168], r8
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rdi+176], r9
+
+  xor    rax, rax                  ; Double with CF, add squares with OF
+  mov    rdx, [reg_p1]
+  mulx   r9, r8, rdx
+  mov    r10, [rdi]
+  mov    r11, [rdi+8]
+  adcx   r10, r10
+  adcx   r11, r11
+  adox   r10, r8
+  adox   r11, r9
+  mov    [rdi], r10
+  mov    [rdi+8], r11
+  mov    rdx, [reg_p1+8]
+  mulx   r9, r8, rdx
+  mov    r10, [rdi+16]
+  mov    r11, [rdi+24]
+  adcx   r10, r10
+  adcx   r11, r11
+  adox   r10, r8
+  adox   r11, r9
+  mov    [rdi+16], r10
+  mov    [rdi+24], r11
+  mov    rdx, [reg_p1+16]
+  mulx   r9, r8, rdx
+  mov    r10, [rdi+32]
+  mov    r11, [rdi+40]
+  adcx   r10, r10
+  adcx   r11, r11
+  adox   r10, r8
+  adox   r11, r9
+  mov    [rdi+32], r10
+  mov    [rdi+40], r11
+  mov    rdx, [reg_p1+24]
+  mulx   r9, r8, rdx
+  mov    r10, [rdi+48]
+  mov    r11, [rdi+56]
+  adcx   r10, r10
+  adcx   r11, r11
+  adox   r10, r8
+  adox   r11, r9
+  mov    [rdi+48], r10
+  mov    [rdi+56], r11
+  mov    rdx, [reg_p1+32]
+  mulx   r9, r8, rdx
+  mov    r10, [rdi+64]
+  mov    r11, [rdi+72]
+  adcx   r10, r10
+  adcx   r11, r11
+  adox   r10, r8
+  adox   r11, r9
+  mov    [rdi+64], r10
+  mov    [rdi+72], r11
+  mov    rdx, [reg_p1+40]
+  mulx   r9, r8, rdx
+  mov    r10, [rdi+80]
+  mov    r11, [rdi+88]
+  adcx   r10, r10
+  adcx   r11, r11
+  adox   r10, r8
+  adox   r11, r9
+  mov    [rdi+80], r10
+  mov    [rdi+88], r11
+  mov    rdx, [reg_p1+48]
+  mulx   r9, r8, rdx
+  mov    r10, [rdi+96]
+  mov    r11, [rdi+104]
+  adcx   r10, r10
+  adcx   r11, r11
+  adox   r10, r8
+  adox   r11, r9
+  mov    [rdi+96], r10
+  mov    [rdi+104], r11
+  mov    rdx, [reg_p1+56]
+  mulx   r9, r8, rdx
+  mov    r10, [rdi+112]
+  mov    r11, [rdi+120]
+  adcx   r10, r10
+  adcx   r11, r11
+  adox   r10, r8
+  adox   r11, r9
+  mov    [rdi+112], r10
+  mov    [rdi+120], r11
+  mov    rdx, [reg_p1+64]
+  mulx   r9, r8, rdx
+  mov    r10, [rdi+128]
+  mov    r11, [rdi+136]
+  adcx   r10, r10
+  adcx   r11, r11
+  adox   r10, r8
+  adox   r11, r9
+  mov    [rdi+128], r10
+  mov    [rdi+136], r11
+  mov    rdx, [reg_p1+72]
+  mulx   r9, r8, rdx
+  mov    r10, [rdi+144]
+  mov    r11, [rdi+152]
+  adcx   r10, r10
+  adcx   r11, r11
+  adox   r10, r8
+  adox   r11, r9
+  mov    [rdi+144], r10
+  mov    [rdi+152], r11
+  mov    rdx, [reg_p1+80]
+  mulx   r9, r8, rdx
+  mov    r10, [rdi+160]
+  mov    r11, [rdi+168]
+  adcx   r10, r10
+  adcx   r11, r11
+  adox   r10, r8
+  adox   r11, r9
+  mov    [rdi+160], r10
+  mov    [rdi+168], r11
+  mov    rdx, [reg_p1+88]
+  mulx   r9, r8, rdx
+  mov    r10, [rdi+176]
+  mov    r11, [rdi+184]
+  adcx   r10, r10
+  adcx   r11, r11
+  adox   r10, r8
+  adox   r11, r9
+  mov    [rdi+176], r10
+  mov    [rdi+184], r11
+
+  pop    rdi
+  ret
+sqr751_mulx_asm endp
+
+;***********************************************************************
+;  Montgomery reduction using MULX/ADCX/ADOX
+;  Uses p751 = (p751+1) - 1 and -p751^-1 mod 2^64 = 1, so that the
+;  quotient words are the words of a; computed in three blocks of
+;  quotient words, since each block only depends on the previous ones
+;  Operation: c [reg_p2] = a [reg_p1]
+;  NOTE: a=c is not allowed
+;***********************************************************************
+rdc751_mulx_asm proc
+  push   r12
+  push   r13
+  push   rbx
+  push   rdi
+  push   rsi
+  ; MULX uses DX as an implicit source operand, so move c to RSI.
+  mov    rsi, reg_p2
+  sub    rsp, 96
+
+  ; c += (q[0-4] * (p751+1)) << 64*0
+  xor    r8, r8
+  xor    r9, r9
+  xor    r10, r10
+  xor    r11, r11
+  xor    rdi, rdi
+
+  mov    rdx, p751p1_5
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r8, [reg_p1+40]
+  mulx   r13, r12, [reg_p1]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r13, r12, [reg_p1+8]
+  adox   r9, r12
+  adcx   r10, r13
+  mulx   r13, r12, [reg_p1+16]
+  adox   r10, r12
+  adcx   r11, r13
+  mulx   r13, r12, [reg_p1+24]
+  adox   r11, r12
+  adcx   rdi, r13
+  mulx   rbx, r12, [reg_p1+32]
+  adox   rdi, r12
+  adcx   rbx, rax
+  adox   rbx, rax
+  mov    [rsp+40], r8
+
+  mov    rdx, p751p1_6
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r9, [reg_p1+48]
+  mulx   r13, r12, [reg_p1]
+  adox   r9, r12
+  adcx   r10, r13
+  mulx   r13, r12, [reg_p1+8]
+  adox   r10, r12
+  adcx   r11, r13
+  mulx   r13, r12, [reg_p1+16]
+  adox   r11, r12
+  adcx   rdi, r13
+  mulx   r13, r12, [reg_p1+24]
+  adox   rdi, r12
+  adcx   rbx, r13
+  mulx   r8, r12, [reg_p1+32]
+  adox   rbx, r12
+  adcx   r8, rax
+  adox   r8, rax
+  mov    [rsp+48], r9
+
+  mov    rdx, p751p1_7
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r10, [reg_p1+56]
+  mulx   r13, r12, [reg_p1]
+  adox   r10, r12
+  adcx   r11, r13
+  mulx   r13, r12, [reg_p1+8]
+  adox   r11, r12
+  adcx   rdi, r13
+  mulx   r13, r12, [reg_p1+16]
+  adox   rdi, r12
+  adcx   rbx, r13
+  mulx   r13, r12, [reg_p1+24]
+  adox   rbx, r12
+  adcx   r8, r13
+  mulx   r9, r12, [reg_p1+32]
+  adox   r8, r12
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rsp+56], r10
+
+  mov    rdx, p751p1_8
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r11, [reg_p1+64]
+  mulx   r13, r12, [reg_p1]
+  adox   r11, r12
+  adcx   rdi, r13
+  mulx   r13, r12, [reg_p1+8]
+  adox   rdi, r12
+  adcx   rbx, r13
+  mulx   r13, r12, [reg_p1+16]
+  adox   rbx, r12
+  adcx   r8, r13
+  mulx   r13, r12, [reg_p1+24]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r10, r12, [reg_p1+32]
+  adox   r9, r12
+  adcx   r10, rax
+  adox   r10, rax
+  mov    [rsp+64], r11
+
+  mov    rdx, p751p1_9
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   rdi, [reg_p1+72]
+  mulx   r13, r12, [reg_p1]
+  adox   rdi, r12
+  adcx   rbx, r13
+  mulx   r13, r12, [reg_p1+8]
+  adox   rbx, r12
+  adcx   r8, r13
+  mulx   r13, r12, [reg_p1+16]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r13, r12, [reg_p1+24]
+  adox   r9, r12
+  adcx   r10, r13
+  mulx   r11, r12, [reg_p1+32]
+  adox   r10, r12
+  adcx   r11, rax
+  adox   r11, rax
+  mov    [rsp+72], rdi
+
+  mov    rdx, p751p1_10
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   rbx, [reg_p1+80]
+  mulx   r13, r12, [reg_p1]
+  adox   rbx, r12
+  adcx   r8, r13
+  mulx   r13, r12, [reg_p1+8]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r13, r12, [reg_p1+16]
+  adox   r9, r12
+  adcx   r10, r13
+  mulx   r13, r12, [reg_p1+24]
+  adox   r10, r12
+  adcx   r11, r13
+  mulx   rdi, r12, [reg_p1+32]
+  adox   r11, r12
+  adcx   rdi, rax
+  adox   rdi, rax
+  mov    [rsp+80], rbx
+
+  mov    rdx, p751p1_11
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r8, [reg_p1+88]
+  mulx   r13, r12, [reg_p1]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r13, r12, [reg_p1+8]
+  adox   r9, r12
+  adcx   r10, r13
+  mulx   r13, r12, [reg_p1+16]
+  adox   r10, r12
+  adcx   r11, r13
+  mulx   r13, r12, [reg_p1+24]
+  adox   r11, r12
+  adcx   rdi, r13
+  mulx   rbx, r12, [reg_p1+32]
+  adox   rdi, r12
+  adcx   rbx, rax
+  adox   rbx, rax
+  mov    [rsp+88], r8
+
+  xor    rax, rax
+  adcx   r9, [reg_p1+96]
+  mov    [rsi], r9
+  adcx   r10, [reg_p1+104]
+  mov    [rsi+8], r10
+  adcx   r11, [reg_p1+112]
+  mov    [rsi+16], r11
+  adcx   rdi, [reg_p1+120]
+  mov    [rsi+24], rdi
+  adcx   rbx, [reg_p1+128]
+  mov    [rsi+32], rbx
+  mov    r12, [reg_p1+136]
+  adcx   r12, rax
+  mov    [rsi+40], r12
+  mov    r12, [reg_p1+144]
+  adcx   r12, rax
+  mov    [rsi+48], r12
+  mov    r12, [reg_p1+152]
+  adcx   r12, rax
+  mov    [rsi+56], r12
+  mov    r12, [reg_p1+160]
+  adcx   r12, rax
+  mov    [rsi+64], r12
+  mov    r12, [reg_p1+168]
+  adcx   r12, rax
+  mov    [rsi+72], r12
+  mov    r12, [reg_p1+176]
+  adcx   r12, rax
+  mov    [rsi+80], r12
+  mov    r12, [reg_p1+184]
+  adcx   r12, rax
+  mov    [rsi+88], r12
+
+  ; c += (q[5-9] * (p751+1)) << 64*5
+  xor    r8, r8
+  xor    r9, r9
+  xor    r10, r10
+  xor    r11, r11
+  xor    rdi, rdi
+
+  mov    rdx, p751p1_5
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r8, [rsp+80]
+  mulx   r13, r12, [rsp+40]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r13, r12, [rsp+48]
+  adox   r9, r12
+  adcx   r10, r13
+  mulx   r13, r12, [rsp+56]
+  adox   r10, r12
+  adcx   r11, r13
+  mulx   r13, r12, [rsp+64]
+  adox   r11, r12
+  adcx   rdi, r13
+  mulx   rbx, r12, [rsp+72]
+  adox   rdi, r12
+  adcx   rbx, rax
+  adox   rbx, rax
+  mov    [rsp+80], r8
+
+  mov    rdx, p751p1_6
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r9, [rsp+88]
+  mulx   r13, r12, [rsp+40]
+  adox   r9, r12
+  adcx   r10, r13
+  mulx   r13, r12, [rsp+48]
+  adox   r10, r12
+  adcx   r11, r13
+  mulx   r13, r12, [rsp+56]
+  adox   r11, r12
+  adcx   rdi, r13
+  mulx   r13, r12, [rsp+64]
+  adox   rdi, r12
+  adcx   rbx, r13
+  mulx   r8, r12, [rsp+72]
+  adox   rbx, r12
+  adcx   r8, rax
+  adox   r8, rax
+  mov    [rsp+88], r9
+
+  mov    rdx, p751p1_7
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r10, [rsi]
+  mulx   r13, r12, [rsp+40]
+  adox   r10, r12
+  adcx   r11, r13
+  mulx   r13, r12, [rsp+48]
+  adox   r11, r12
+  adcx   rdi, r13
+  mulx   r13, r12, [rsp+56]
+  adox   rdi, r12
+  adcx   rbx, r13
+  mulx   r13, r12, [rsp+64]
+  adox   rbx, r12
+  adcx   r8, r13
+  mulx   r9, r12, [rsp+72]
+  adox   r8, r12
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rsi], r10
+
+  mov    rdx, p751p1_8
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r11, [rsi+8]
+  mulx   r13, r12, [rsp+40]
+  adox   r11, r12
+  adcx   rdi, r13
+  mulx   r13, r12, [rsp+48]
+  adox   rdi, r12
+  adcx   rbx, r13
+  mulx   r13, r12, [rsp+56]
+  adox   rbx, r12
+  adcx   r8, r13
+  mulx   r13, r12, [rsp+64]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r10, r12, [rsp+72]
+  adox   r9, r12
+  adcx   r10, rax
+  adox   r10, rax
+  mov    [rsi+8], r11
+
+  mov    rdx, p751p1_9
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   rdi, [rsi+16]
+  mulx   r13, r12, [rsp+40]
+  adox   rdi, r12
+  adcx   rbx, r13
+  mulx   r13, r12, [rsp+48]
+  adox   rbx, r12
+  adcx   r8, r13
+  mulx   r13, r12, [rsp+56]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r13, r12, [rsp+64]
+  adox   r9, r12
+  adcx   r10, r13
+  mulx   r11, r12, [rsp+72]
+  adox   r10, r12
+  adcx   r11, rax
+  adox   r11, rax
+  mov    [rsi+16], rdi
+
+  mov    rdx, p751p1_10
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   rbx, [rsi+24]
+  mulx   r13, r12, [rsp+40]
+  adox   rbx, r12
+  adcx   r8, r13
+  mulx   r13, r12, [rsp+48]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r13, r12, [rsp+56]
+  adox   r9, r12
+  adcx   r10, r13
+  mulx   r13, r12, [rsp+64]
+  adox   r10, r12
+  adcx   r11, r13
+  mulx   rdi, r12, [rsp+72]
+  adox   r11, r12
+  adcx   rdi, rax
+  adox   rdi, rax
+  mov    [rsi+24], rbx
+
+  mov    rdx, p751p1_11
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r8, [rsi+32]
+  mulx   r13, r12, [rsp+40]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r13, r12, [rsp+48]
+  adox   r9, r12
+  adcx   r10, r13
+  mulx   r13, r12, [rsp+56]
+  adox   r10, r12
+  adcx   r11, r13
+  mulx   r13, r12, [rsp+64]
+  adox   r11, r12
+  adcx   rdi, r13
+  mulx   rbx, r12, [rsp+72]
+  adox   rdi, r12
+  adcx   rbx, rax
+  adox   rbx, rax
+  mov    [rsi+32], r8
+
+  xor    rax, rax
+  adcx   r9, [rsi+40]
+  mov    [rsi+40], r9
+  adcx   r10, [rsi+48]
+  mov    [rsi+48], r10
+  adcx   r11, [rsi+56]
+  mov    [rsi+56], r11
+  adcx   rdi, [rsi+64]
+  mov    [rsi+64], rdi
+  adcx   rbx, [rsi+72]
+  mov    [rsi+72], rbx
+  mov    r12, [rsi+80]
+  adcx   r12, rax
+  mov    [rsi+80], r12
+  mov    r12, [rsi+88]
+  adcx   r12, rax
+  mov    [rsi+88], r12
+
+  ; c += (q[10-11] * (p751+1)) << 64*10
+  xor    r8, r8
+  xor    r9, r9
+
+  mov    rdx, p751p1_5
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r8, [rsi+24]
+  mulx   r13, r12, [rsp+80]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r10, r12, [rsp+88]
+  adox   r9, r12
+  adcx   r10, rax
+  adox   r10, rax
+  mov    [rsi+24], r8
+
+  mov    rdx, p751p1_6
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r9, [rsi+32]
+  mulx   r13, r12, [rsp+80]
+  adox   r9, r12
+  adcx   r10, r13
+  mulx   r8, r12, [rsp+88]
+  adox   r10, r12
+  adcx   r8, rax
+  adox   r8, rax
+  mov    [rsi+32], r9
+
+  mov    rdx, p751p1_7
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r10, [rsi+40]
+  mulx   r13, r12, [rsp+80]
+  adox   r10, r12
+  adcx   r8, r13
+  mulx   r9, r12, [rsp+88]
+  adox   r8, r12
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rsi+40], r10
+
+  mov    rdx, p751p1_8
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r8, [rsi+48]
+  mulx   r13, r12, [rsp+80]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r10, r12, [rsp+88]
+  adox   r9, r12
+  adcx   r10, rax
+  adox   r10, rax
+  mov    [rsi+48], r8
+
+  mov    rdx, p751p1_9
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r9, [rsi+56]
+  mulx   r13, r12, [rsp+80]
+  adox   r9, r12
+  adcx   r10, r13
+  mulx   r8, r12, [rsp+88]
+  adox   r10, r12
+  adcx   r8, rax
+  adox   r8, rax
+  mov    [rsi+56], r9
+
+  mov    rdx, p751p1_10
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r10, [rsi+64]
+  mulx   r13, r12, [rsp+80]
+  adox   r10, r12
+  adcx   r8, r13
+  mulx   r9, r12, [rsp+88]
+  adox   r8, r12
+  adcx   r9, rax
+  adox   r9, rax
+  mov    [rsi+64], r10
+
+  mov    rdx, p751p1_11
+  xor    rax, rax                  ; Clear CF and OF
+  adcx   r8, [rsi+72]
+  mulx   r13, r12, [rsp+80]
+  adox   r8, r12
+  adcx   r9, r13
+  mulx   r10, r12, [rsp+88]
+  adox   r9, r12
+  adcx   r10, rax
+  adox   r10, rax
+  mov    [rsi+72], r8
+
+  xor    rax, rax
+  adcx   r9, [rsi+80]
+  mov    [rsi+80], r9
+  adcx   r10, [rsi+88]
+  mov    [rsi+88], r10
+
+  add    rsp, 96
+
+  pop    rsi
+  pop    rdi
+  pop    rbx
+  pop    r13
+  pop    r12
+  ret
+rdc751_mulx_asm endp
+
+  end
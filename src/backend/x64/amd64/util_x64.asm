@@ -51,7 +51,7 @@ checklt238_asm proc
 
   ; Save borrow flag indicating 3^238 - scalar < 0 as a mask in AX
   sbb    rax, 0
-  mov    [reg_p2], rax
+  mov    dword ptr [reg_p2], eax
 
   pop    r15
   pop    r14
@@ -7,12 +7,15 @@
 //
 
 use core::fmt::Debug;
+use core::sync::atomic::{AtomicU8, Ordering};
 
 use subtle::ConditionallySelectable;
 use subtle::Choice;
 
 #[cfg(test)]
 use quickcheck::{Arbitrary,Gen};
+#[cfg(test)]
+use rand::Rng;
 
 pub const FP751_NUM_WORDS: usize = 12;
 
@@ -26,15 +29,15 @@ pub struct Fp751ElementDist;
 impl ConditionallySelectable for Fp751Element {
     fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
         let mut bytes = [0u64; FP751_NUM_WORDS];
-        for i in 0..FP751_NUM_WORDS {
-            bytes[i] = u64::conditional_select(&a.0[i], &b.0[i], choice);
+        for (i, limb) in bytes.iter_mut().enumerate() {
+            *limb = u64::conditional_select(&a.0[i], &b.0[i], choice);
         }
 
         Fp751Element(bytes)
     }
 
     fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
-        unsafe { cswap751_asm(a, b, choice); }
+        unsafe { cswap751_asm(a, b, choice.unwrap_u8()); }
     }
 }
 
@@ -84,7 +87,7 @@ impl Fp751Element {
         Fp751Element([0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0])
     }
     /// Given an `Fp751Element` in Montgomery form, convert to little-endian bytes.
-    pub fn to_bytes(&self) -> [u8; 94] {
+    pub fn to_bytes(self) -> [u8; 94] {
         let mut bytes = [0u8; 94];
         let mut a = Fp751Element::zero();
         let mut aR = Fp751X2::zero();
@@ -147,44 +150,38 @@ pub const MONTGOMERY_R: Fp751Element = Fp751Element([149933, 0, 0, 0, 0, 9444048
 /// `(2^768)^2 mod p`
 pub const MONTGOMERY_RSQ: Fp751Element = Fp751Element([2535603850726686808, 15780896088201250090, 6788776303855402382, 17585428585582356230, 5274503137951975249, 2266259624764636289, 11695651972693921304, 13072885652150159301, 4908312795585420432, 6229583484603254826, 488927695601805643, 72213483953973]);
 
-extern {
+extern "C" {
     // If choice = 1, set x,y = y,x. Otherwise, leave x,y unchanged.
     // This function executes in constant time.
-    #[no_mangle]
-    fn cswap751_asm(x: &mut Fp751Element, y: &mut Fp751Element, choice: Choice);
+    fn cswap751_asm(x: &mut Fp751Element, y: &mut Fp751Element, choice: u8);
     // If choice = 1, assign y to x. Otherwise, leave x unchanged.
     // This function executes in constant time.
-    #[no_mangle]
     fn cassign751_asm(x: &mut Fp751Element, y: &Fp751Element, choice: u8);
     // Compute z = x + y (mod p).
-    #[no_mangle]
     fn fpadd751_asm(x: &Fp751Element, y: &Fp751Element, z: &mut Fp751Element);
     // Compute z = x - y (mod p).
-    #[no_mangle]
     fn fpsub751_asm(x: &Fp751Element, y: &Fp751Element, z: &mut Fp751Element);
     // Compute z = x * y.
-    #[no_mangle]
     fn mul751_asm(x: &Fp751Element, y: &Fp751Element, z: &mut Fp751X2);
+    // Compute z = x * y, using MULX and ADCX/ADOX.
+    fn mul751_mulx_asm(x: &Fp751Element, y: &Fp751Element, z: &mut Fp751X2);
+    // Compute z = x^2, using MULX and ADCX/ADOX.
+    fn sqr751_mulx_asm(x: &Fp751Element, z: &mut Fp751X2);
     // Perform Montgomery reduction: set z = x R^{-1} (mod p).
-    #[no_mangle]
     fn rdc751_asm(x: &Fp751X2, z: &mut Fp751Element);
+    // Perform Montgomery reduction: set z = x R^{-1} (mod p), using MULX and ADCX/ADOX.
+    fn rdc751_mulx_asm(x: &Fp751X2, z: &mut Fp751Element);
     // Reduce a field element in [0, 2*p) to one in [0,p).
-    #[no_mangle]
     fn srdc751_asm(x: &mut Fp751Element);
     // Compute z = x + y, without reducing mod p.
-    #[no_mangle]
     fn mp_add751_asm(x: &Fp751Element, y: &Fp751Element, z: &mut Fp751Element);
     // Compute z = x + y, without reducing mod p.
-    #[no_mangle]
     fn mp_add751x2_asm(x: &Fp751X2, y: &Fp751X2, z: &mut Fp751X2);
     // Compute z = x - y, without reducing mod p.
-    #[no_mangle]
     fn mp_sub751x2_asm(x: &Fp751X2, y: &Fp751X2, z: &mut Fp751X2);
     // Set result to zero if the input scalar is <= 3^238.
-    #[no_mangle]
     fn checklt238_asm(scalar: &[u8; 48], result: &mut u32);
     // Set scalar = 3*scalar.
-    #[no_mangle]
     fn mulby3_asm(scalar: &mut [u8; 48]);
 }
 
@@ -196,11 +193,76 @@ pub fn fpsub751(x: &Fp751Element, y: &Fp751Element, z: &mut Fp751Element) {
     unsafe { fpsub751_asm(x, y, z); }
 }
 
+const MULX_ADX_UNKNOWN: u8 = 0;
+const MULX_ADX_UNSUPPORTED: u8 = 1;
+const MULX_ADX_SUPPORTED: u8 = 2;
+
+// Cached result of the CPU feature detection in `has_mulx_adx`.
+static MULX_ADX: AtomicU8 = AtomicU8::new(MULX_ADX_UNKNOWN);
+
+// Check whether the CPU supports both BMI2 (for MULX) and ADX (for ADCX/ADOX).
+#[allow(unused_unsafe)]
+fn detect_mulx_adx() -> bool {
+    use core::arch::x86_64::{__cpuid, __cpuid_count};
+
+    let max_leaf = unsafe { __cpuid(0) }.eax;
+    if max_leaf < 7 {
+        return false;
+    }
+    let ebx = unsafe { __cpuid_count(7, 0) }.ebx;
+    let bmi2 = (ebx >> 8) & 1 == 1;
+    let adx = (ebx >> 19) & 1 == 1;
+    bmi2 && adx
+}
+
+/// Returns true if the MULX/ADX variants of the multiplication and reduction
+/// kernels are used. The CPU is queried once, and the result is cached.
+pub fn has_mulx_adx() -> bool {
+    match MULX_ADX.load(Ordering::Relaxed) {
+        MULX_ADX_SUPPORTED => true,
+        MULX_ADX_UNSUPPORTED => false,
+        _ => {
+            let supported = detect_mulx_adx();
+            let state = if supported { MULX_ADX_SUPPORTED } else { MULX_ADX_UNSUPPORTED };
+            MULX_ADX.store(state, Ordering::Relaxed);
+            supported
+        }
+    }
+}
+
 pub fn mul751(x: &Fp751Element, y: &Fp751Element, z: &mut Fp751X2) {
-    unsafe { mul751_asm(x, y, z); }
+    if has_mulx_adx() {
+        unsafe { mul751_mulx_asm(x, y, z); }
+    } else {
+        unsafe { mul751_asm(x, y, z); }
+    }
+}
+
+pub fn sqr751(x: &Fp751Element, z: &mut Fp751X2) {
+    if has_mulx_adx() {
+        unsafe { sqr751_mulx_asm(x, z); }
+    } else {
+        unsafe { mul751_asm(x, x, z); }
+    }
 }
 
 pub fn rdc751(x: &Fp751X2, z: &mut Fp751Element) {
+    if has_mulx_adx() {
+        unsafe { rdc751_mulx_asm(x, z); }
+    } else {
+        unsafe { rdc751_asm(x, z); }
+    }
+}
+
+// The generic kernels, regardless of the CPU features. Used to test the
+// MULX/ADX kernels against.
+#[cfg(test)]
+pub fn mul751_generic(x: &Fp751Element, y: &Fp751Element, z: &mut Fp751X2) {
+    unsafe { mul751_asm(x, y, z); }
+}
+
+#[cfg(test)]
+pub fn rdc751_generic(x: &Fp751X2, z: &mut Fp751Element) {
     unsafe { rdc751_asm(x, z); }
 }
 
@@ -14,6 +14,8 @@ use subtle::Choice;
 
 #[cfg(test)]
 use quickcheck::{Arbitrary,Gen};
+#[cfg(test)]
+use rand::Rng;
 
 // Macro to assign tuples, as Rust does not allow tuples as lvalue.
 macro_rules! assign{
@@ -175,6 +177,10 @@ pub fn mul751(x: &Fp751Element, y: &Fp751Element, z: &mut Fp751X2) {
     z.0[2*FP751_NUM_WORDS-1] = v;
 }
 
+pub fn sqr751(x: &Fp751Element, z: &mut Fp751X2) {
+    mul751(x, x, z);
+}
+
 pub fn rdc751(x: &Fp751X2, z: &mut Fp751Element) {
     let mut t: u32 = 0;
     let mut u: u32 = 0;
@@ -390,7 +396,7 @@ impl Fp751Element {
         Fp751Element([0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0, 0x0])
     }
     /// Given an `Fp751Element` in Montgomery form, convert to little-endian bytes.
-    pub fn to_bytes(&self) -> [u8; 94] {
+    pub fn to_bytes(self) -> [u8; 94] {
         let mut bytes = [0u8; 94];
         let mut a = Fp751Element::zero();
         let mut aR = Fp751X2::zero();
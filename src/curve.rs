@@ -6,16 +6,24 @@
 // - Erkan Tairi <erkan.tairi@gmail.com>
 //
 
-//! This module contains internal curve representation and operations 
-//! for SIDH, which is not part of the public API.
+//! This module contains curve representation and operations for SIDH.
+//!
+//! Curves are Montgomery curves `E_(A:C) : Cy^2 = Cx^3 + Ax^2 + Cx`, and points
+//! are represented by their x-coordinate on the Kummer line, or as full points
+//! `(X:Y:Z)` where the y-coordinate is needed.
 
 use field::{Fp751Element, PrimeFieldElement, ExtensionFieldElement};
 use constants::*;
+use fixed_base::{fixed_base_table, fixed_base_scalar_mul, fixed_basis_table, FIXED_BASE_SCALAR_BYTES};
 
 use core::fmt::Debug;
+use core::ops::Neg;
 use subtle::ConditionallySelectable;
+use subtle::ConstantTimeEq;
 use subtle::Choice;
 
+use rand_core::{CryptoRng, RngCore};
+
 #[cfg(test)]
 use quickcheck::{Arbitrary, Gen, QuickCheck};
 
@@ -86,6 +94,9 @@ impl ProjectiveCurveParameters {
         }
     }
     /// Recover the curve parameters from three points on the curve.
+    ///
+    /// The x-coordinates are not validated, so the result may be singular or
+    /// ordinary; see `is_singular` and `is_supersingular`.
     pub fn recover_curve_parameters(affine_xP: &ExtensionFieldElement, affine_xQ: &ExtensionFieldElement, affine_xQmP: &ExtensionFieldElement) -> 
                                 ProjectiveCurveParameters 
     {
@@ -128,6 +139,128 @@ impl ProjectiveCurveParameters {
 
         v0
     }
+    /// Deterministically generate a basis `(P, Q)` of `E[2^372]`, as affine
+    /// coordinates `(x_P, y_P, x_Q, y_Q)`.
+    ///
+    /// This is `generate_2_torsion_basis` of `sage/sidh_curve_and_isogeny_arithmetic.sage`.
+    /// The curve must be supersingular with `E(F_{p^2}) = (Z/(p+1)Z)^2`, as are all
+    /// curves in the SIDH isogeny graph of `E_0`. Takes variable time.
+    pub fn generate_2_torsion_basis(&self) -> (ExtensionFieldElement, ExtensionFieldElement, ExtensionFieldElement, ExtensionFieldElement) {
+        let a = &self.A * &self.C.inv();
+        let curve = ProjectiveCurveParameters::from_affine(&a);
+
+        // Points outside of [2]E have order divisible by 2^372, so clearing the
+        // cofactor 3^239 leaves a point of order 2^372.
+        let mut alpha = 0;
+        let xP = ProjectivePoint::from_affine(&get_point_notin_2E(&a, &mut alpha)).pow3k(&curve, E_B);
+        let xP2 = xP.pow2k(&curve, E_A - 1);
+        let xQ = loop {
+            let xR = ProjectivePoint::from_affine(&get_point_notin_2E(&a, &mut alpha)).pow3k(&curve, E_B);
+            // P and R generate E[2^372] if and only if their points of order 2 differ.
+            if !xR.pow2k(&curve, E_A - 1).vartime_eq(&xP2) {
+                break xR;
+            }
+        };
+
+        let affine_xP = xP.to_affine();
+        let affine_xQ = xQ.to_affine();
+        (affine_xP, affine_y(&a, &affine_xP), affine_xQ, affine_y(&a, &affine_xQ))
+    }
+    /// Deterministically generate a basis `(P, Q)` of `E[3^239]`, as affine
+    /// coordinates `(x_P, y_P, x_Q, y_Q)`.
+    ///
+    /// This is `generate_3_torsion_basis` of `sage/sidh_curve_and_isogeny_arithmetic.sage`.
+    /// The curve must be supersingular with `E(F_{p^2}) = (Z/(p+1)Z)^2`, as are all
+    /// curves in the SIDH isogeny graph of `E_0`. Takes variable time.
+    pub fn generate_3_torsion_basis(&self) -> (ExtensionFieldElement, ExtensionFieldElement, ExtensionFieldElement, ExtensionFieldElement) {
+        let a = &self.A * &self.C.inv();
+        let curve = ProjectiveCurveParameters::from_affine(&a);
+
+        // Find a point R of order 3^k, k > 0, and the point R3 of order 3 in <R>.
+        let mut r = 0;
+        let xR = loop {
+            r += 1;
+            if let Some(x) = get_x_on_curve(&a, r) {
+                let xR = ProjectivePoint::from_affine(&x).pow2k(&curve, E_A);
+                if !xR.Z.vartime_eq(&ExtensionFieldElement::zero()) {
+                    break xR;
+                }
+            }
+        };
+        let (xR3, triples) = get_3_torsion_elt(&curve, &xR);
+
+        // A point S is not in [3]E if and only if the 3-Tate pairing of R3 and S is
+        // nontrivial, that is, if l(S) is not a cube, where l is the tangent line
+        // y - y3 - lambda*(x - x3) at R3 = (x3, y3).
+        let x3 = xR3.to_affine();
+        let y3 = affine_y(&a, &x3);
+        let mut lambda = &(&x3 + &x3) + &x3;        // = 3x3
+        lambda = &(&lambda + &(&a + &a)) * &x3;      // = 3x3^2 + 2ax3
+        lambda = &lambda + &ExtensionFieldElement::one();
+        lambda = &lambda * &(&y3 + &y3).inv();       // = (3x3^2 + 2ax3 + 1)/2y3
+
+        // Returns x([2^372]S) and x([3^238 * 2^372]S) for the next point S not in [3]E.
+        let mut next_point = || loop {
+            r += 1;
+            if let Some((x, y)) = get_pt_on_curve(&a, r) {
+                let l = &(&y - &y3) - &(&lambda * &(&x - &x3));
+                if !l.is_cube() {
+                    let xS = ProjectivePoint::from_affine(&x).pow2k(&curve, E_A);
+                    return (xS, xS.pow3k(&curve, E_B - 1));
+                }
+            }
+        };
+
+        let (xP, xP3) = if triples == E_B { (xR, xR3) } else { next_point() };
+        let xQ = loop {
+            let (xS, xS3) = next_point();
+            // P and S generate E[3^239] if and only if their points of order 3
+            // generate distinct subgroups.
+            if !xS3.vartime_eq(&xP3) {
+                break xS;
+            }
+        };
+
+        let affine_xP = xP.to_affine();
+        let affine_xQ = xQ.to_affine();
+        (affine_xP, affine_y(&a, &affine_xP), affine_xQ, affine_y(&a, &affine_xQ))
+    }
+    /// Returns true if the curve `E_(A:C)` is singular, i.e. if `C = 0` or
+    /// `A^2 = 4C^2`. Takes variable time.
+    pub fn is_singular(&self) -> bool {
+        let C2 = &self.C + &self.C;
+        self.C.vartime_eq(&ExtensionFieldElement::zero()) || self.A.square().vartime_eq(&C2.square())
+    }
+    /// Returns true if `E(F_{p^2}) = (Z/(p+1)Z)^2`, so that `E_(A:C)` has `(p+1)^2`
+    /// points over `F_{p^2}`, as do all curves in the SIDH isogeny graph of `E_0`.
+    ///
+    /// The answer is always correct. Random points are sampled until they prove
+    /// it, needing a small expected number of samples. Takes variable time, so it
+    /// must only be used on public curves.
+    pub fn has_p_plus_1_squared_points<R: RngCore + CryptoRng>(&self, rng: &mut R) -> bool {
+        !self.is_singular() && has_full_torsion(&(&self.A * &self.C.inv()), false, rng)
+    }
+    /// Returns true if the curve `E_(A:C)` is supersingular.
+    ///
+    /// A nonsingular Montgomery curve is supersingular if and only if either it or
+    /// its quadratic twist has `(p+1)^2` points over `F_{p^2}`, and the other then
+    /// has `(p-1)^2` points. The answer is always correct. Takes variable time, so
+    /// it must only be used on public curves.
+    pub fn is_supersingular<R: RngCore + CryptoRng>(&self, rng: &mut R) -> bool {
+        if self.is_singular() {
+            return false;
+        }
+        let a = &self.A * &self.C.inv();
+        let curve = ProjectiveCurveParameters::from_affine(&a);
+
+        // A random point is on the side with (p+1)^2 points if and only if [p+1]P = O,
+        // unless the curve is ordinary.
+        let x = ExtensionFieldElement::random(rng);
+        let on_curve = montgomery_rhs(&a, &x).is_square();
+        let xP = ProjectivePoint::from_affine(&x).pow3k(&curve, E_B).pow2k(&curve, E_A); // = x([p+1]P)
+        let killed = xP.Z.vartime_eq(&ExtensionFieldElement::zero());
+        has_full_torsion(&a, on_curve ^ killed, rng)
+    }
     /// Compute cached parameters `A + 2C, 4C`.
     fn cached_params(&self) -> CachedCurveParameters {
         let mut Aplus2C = &self.C + &self.C; // = 2*C
@@ -145,6 +278,118 @@ impl ProjectiveCurveParameters {
     }
 }
 
+// = 4 + i, a nonsquare in F_{p^2}.
+const U: ExtensionFieldElement = ExtensionFieldElement::from_str_const("4", "1");
+
+// Returns x^3 + ax^2 + x.
+fn montgomery_rhs(a: &ExtensionFieldElement, x: &ExtensionFieldElement) -> ExtensionFieldElement {
+    let mut t0 = x + a;              // = x + a
+    t0 = &t0 * x;                    // = x^2 + ax
+    t0 = &t0 + &ExtensionFieldElement::one();
+    &t0 * x                          // = x^3 + ax^2 + x
+}
+
+// Returns the y-coordinate sqrt(x^3 + ax^2 + x) of a point with affine x-coordinate
+// x on y^2 = x^3 + ax^2 + x. The choice of root is deterministic.
+fn affine_y(a: &ExtensionFieldElement, x: &ExtensionFieldElement) -> ExtensionFieldElement {
+    montgomery_rhs(a, x).sqrt()
+}
+
+// Increment alpha until x = alpha*u is the x-coordinate of a point on
+// y^2 = x^3 + ax^2 + x, and return x. Since u is a nonsquare, and the points of
+// [2]E have square x-coordinates, the point is not in [2]E.
+fn get_point_notin_2E(a: &ExtensionFieldElement, alpha: &mut u64) -> ExtensionFieldElement {
+    loop {
+        *alpha += 1;
+        let x = &ExtensionFieldElement::from_u64(*alpha) * &U;
+        if montgomery_rhs(a, &x).is_square() {
+            return x;
+        }
+    }
+}
+
+// Elligator 2: returns x = -a/(1 + ur^2) if it is the x-coordinate of a point on
+// y^2 = x^3 + ax^2 + x, and x = ur^2 * (-a/(1 + ur^2)) otherwise.
+//
+// For a = 0 the map degenerates, so we use -1/(1 + ur^2) instead, and return None
+// if neither candidate is on the curve.
+fn get_x_on_curve(a: &ExtensionFieldElement, r: u64) -> Option<ExtensionFieldElement> {
+    let ur2 = &U * &ExtensionFieldElement::from_u64(r * r);
+    let mut x = (&ur2 + &ExtensionFieldElement::one()).inv();
+    x = if a.vartime_eq(&ExtensionFieldElement::zero()) { -&x } else { -&(a * &x) };
+    if montgomery_rhs(a, &x).is_square() {
+        return Some(x);
+    }
+    x = &ur2 * &x;
+    if montgomery_rhs(a, &x).is_square() {
+        return Some(x);
+    }
+    None
+}
+
+// Returns the affine point (x, y) on y^2 = x^3 + ax^2 + x obtained by Elligator 2.
+fn get_pt_on_curve(a: &ExtensionFieldElement, r: u64) -> Option<(ExtensionFieldElement, ExtensionFieldElement)> {
+    get_x_on_curve(a, r).map(|x| (x, affine_y(a, &x)))
+}
+
+// Returns true if the points of y^2 = x^3 + ax^2 + x, or of its quadratic twist if
+// twist is set, form a group (Z/(p+1)Z)^2.
+//
+// Sample points R with [p+1]R = O until two of them have order 2^372 and distinct
+// points of order 2, and two have order 3^239 and distinct subgroups of order 3.
+// These generate E[2^372] and E[3^239], so (p+1)^2 divides the group order, which
+// is at most (p+1)^2 by the Hasse bound. Otherwise the points with [p+1]R = O form
+// a subgroup of index at least 2, so each sample fails with probability at least
+// 1/2, and the expected number of samples is constant.
+fn has_full_torsion<R: RngCore + CryptoRng>(a: &ExtensionFieldElement, twist: bool, rng: &mut R) -> bool {
+    let curve = ProjectiveCurveParameters::from_affine(a);
+    let zero = ExtensionFieldElement::zero();
+    let mut xP2: Option<ProjectivePoint> = None;
+    let mut xP3: Option<ProjectivePoint> = None;
+    let mut found_2 = false;
+    let mut found_3 = false;
+    while !(found_2 && found_3) {
+        let x = ExtensionFieldElement::random(rng);
+        if montgomery_rhs(a, &x).is_square() == twist {
+            continue;
+        }
+        let xR = ProjectivePoint::from_affine(&x);
+        let xR2 = xR.pow3k(&curve, E_B).pow2k(&curve, E_A - 1); // = x([3^239 * 2^371]R)
+        let xR3 = xR.pow2k(&curve, E_A).pow3k(&curve, E_B - 1); // = x([2^372 * 3^238]R)
+        if !xR2.pow2k(&curve, 1).Z.vartime_eq(&zero) || !xR3.pow3k(&curve, 1).Z.vartime_eq(&zero) {
+            return false;
+        }
+        if !xR2.Z.vartime_eq(&zero) {
+            match xP2 {
+                None => xP2 = Some(xR2),
+                Some(xS2) => found_2 |= !xS2.vartime_eq(&xR2),
+            }
+        }
+        if !xR3.Z.vartime_eq(&zero) {
+            match xP3 {
+                None => xP3 = Some(xR3),
+                Some(xS3) => found_3 |= !xS3.vartime_eq(&xR3),
+            }
+        }
+    }
+    true
+}
+
+// Given x(R) for a point R of order 3^k, k > 0, returns x(R3) for the point R3
+// of order 3 in <R>, together with the number of triplings k needed to reach O.
+fn get_3_torsion_elt(curve: &ProjectiveCurveParameters, xR: &ProjectivePoint) -> (ProjectivePoint, u32) {
+    let mut xT = *xR;
+    let mut triples = 0;
+    loop {
+        let xT3 = xT.pow3k(curve, 1);
+        triples += 1;
+        if xT3.Z.vartime_eq(&ExtensionFieldElement::zero()) {
+            return (xT, triples);
+        }
+        xT = xT3;
+    }
+}
+
 /// A point on the projective line `P^1(F_{p^2})`.
 ///
 /// This represents a point on the (Kummer line) of a Montgomery curve.  The
@@ -175,6 +420,12 @@ impl Debug for ProjectivePoint {
     }
 }
 
+impl Default for ProjectivePoint {
+    fn default() -> ProjectivePoint {
+        ProjectivePoint::new()
+    }
+}
+
 #[cfg(test)]
 impl Arbitrary for ProjectivePoint {
     fn arbitrary(g: &mut Gen) -> ProjectivePoint {
@@ -205,9 +456,8 @@ impl ProjectivePoint {
         }
     }
 
-    pub fn to_affine(&self) -> ExtensionFieldElement {
-        let affine_x = &self.Z.inv() * &self.X;
-        affine_x
+    pub fn to_affine(self) -> ExtensionFieldElement {
+        &self.Z.inv() * &self.X
     }
     /// Returns true if both sides are equal. Takes variable time.
     pub fn vartime_eq(&self, _rhs: &ProjectivePoint) -> bool {
@@ -215,8 +465,83 @@ impl ProjectivePoint {
         let t1 = &self.Z * &_rhs.X;
         t0.vartime_eq(&t1)
     }
+    /// Returns `x(P)` in a random projective representation `(lX : lZ)`, for a
+    /// uniformly random nonzero `l` in `F_{p^2}`.
+    ///
+    /// Points from `from_affine` have `Z = 1`, so that the intermediate values of
+    /// the ladders and isogenies computed from them are predictable from the
+    /// inputs and the secret. Randomizing the representation is a countermeasure
+    /// against differential power analysis and template attacks.
+    pub fn randomize<R: RngCore + CryptoRng>(&self, rng: &mut R) -> ProjectivePoint {
+        let l = loop {
+            let l = ExtensionFieldElement::random(rng);
+            if !l.vartime_eq(&ExtensionFieldElement::zero()) {
+                break l;
+            }
+        };
+        ProjectivePoint{ X: &self.X * &l, Z: &self.Z * &l }
+    }
+    /// Sample `x(P)` for a uniformly random point `P != O` on the curve
+    /// `E_(A:C)(F_{p^2})`.
+    ///
+    /// Takes variable time, since it uses rejection sampling.
+    pub fn random<R: RngCore + CryptoRng>(curve: &ProjectiveCurveParameters, rng: &mut R) -> ProjectivePoint {
+        loop {
+            let x = ExtensionFieldElement::random(rng);
+            // x is the x-coordinate of a point on E_(A:C) if and only if
+            // x^3 + (A/C)x^2 + x is a square, or equivalently, if
+            // C(Cx^3 + Ax^2 + Cx) = C^2(x^3 + (A/C)x^2 + x) is a square.
+            let mut t0 = &curve.C * &x; // = Cx
+            t0 = &t0 + &curve.A;        // = Cx + A
+            t0 = &t0 * &x;              // = Cx^2 + Ax
+            t0 = &t0 + &curve.C;        // = Cx^2 + Ax + C
+            t0 = &t0 * &x;              // = Cx^3 + Ax^2 + Cx
+            t0 = &t0 * &curve.C;        // = C(Cx^3 + Ax^2 + Cx)
+            if !t0.is_square() {
+                continue;
+            }
+            // Other x-coordinates belong to two points, +-P, so keep those of
+            // the points with y = 0 only half of the time.
+            if t0.vartime_eq(&ExtensionFieldElement::zero()) && rng.next_u32() & 1 == 0 {
+                continue;
+            }
+            return ProjectivePoint::from_affine(&x);
+        }
+    }
+    /// Sample `x(P)` for a uniformly random point `P` of exact order `2^e` on the curve
+    /// `E_(A:C)`, where `1 <= e <= e_A`.
+    ///
+    /// The curve must be supersingular with `E(F_{p^2}) = (Z/(p+1)Z)^2`, as are all
+    /// curves in the SIDH isogeny graph of `E_0`. Takes variable time.
+    pub fn random_order_2e<R: RngCore + CryptoRng>(curve: &ProjectiveCurveParameters, e: u32, rng: &mut R) -> ProjectivePoint {
+        assert!((1..=E_A).contains(&e), "Exponent passed to random_order_2e must be in [1, e_A]");
+        loop {
+            let xP = ProjectivePoint::random(curve, rng);
+            let xQ = xP.pow3k(curve, E_B).pow2k(curve, E_A - e); // = x([3^e_B * 2^(e_A - e)]P)
+            // Now the order of Q divides 2^e; it is exactly 2^e if [2^(e-1)]Q != O.
+            if !xQ.pow2k(curve, e - 1).Z.vartime_eq(&ExtensionFieldElement::zero()) {
+                return xQ;
+            }
+        }
+    }
+    /// Sample `x(P)` for a uniformly random point `P` of exact order `3^e` on the curve
+    /// `E_(A:C)`, where `1 <= e <= e_B`.
+    ///
+    /// The curve must be supersingular with `E(F_{p^2}) = (Z/(p+1)Z)^2`, as are all
+    /// curves in the SIDH isogeny graph of `E_0`. Takes variable time.
+    pub fn random_order_3e<R: RngCore + CryptoRng>(curve: &ProjectiveCurveParameters, e: u32, rng: &mut R) -> ProjectivePoint {
+        assert!((1..=E_B).contains(&e), "Exponent passed to random_order_3e must be in [1, e_B]");
+        loop {
+            let xP = ProjectivePoint::random(curve, rng);
+            let xQ = xP.pow2k(curve, E_A).pow3k(curve, E_B - e); // = x([2^e_A * 3^(e_B - e)]P)
+            // Now the order of Q divides 3^e; it is exactly 3^e if [3^(e-1)]Q != O.
+            if !xQ.pow3k(curve, e - 1).Z.vartime_eq(&ExtensionFieldElement::zero()) {
+                return xQ;
+            }
+        }
+    }
     /// Given `xP = x(P), xQ = x(Q)`, and `xPmQ = x(P-Q)`, compute `xR = x(P+Q)`.
-    fn add(&self, xQ: &ProjectivePoint, xPmQ: &ProjectivePoint) -> ProjectivePoint {
+    pub(crate) fn add(&self, xQ: &ProjectivePoint, xPmQ: &ProjectivePoint) -> ProjectivePoint {
         let xP = *self;
         // Algorithm 1 of Costello-Smith.
         let mut v0 = &xP.X + &xP.Z;         // X_P + Z_P
@@ -231,6 +556,24 @@ impl ProjectivePoint {
 
         ProjectivePoint{ X: x, Z: z }
     }
+    /// Given `xP = x(P)`, the affine `affine_xQ = x(Q)` in `F_p`, and `xPmQ = x(P-Q)`,
+    /// compute `xR = x(P+Q)`.
+    fn add_affine_prime_field(&self, affine_xQ: &PrimeFieldElement, xPmQ: &ProjectivePoint) -> ProjectivePoint {
+        let xP = *self;
+        let one = PrimeFieldElement::one();
+        // Algorithm 1 of Costello-Smith, with Z_Q = 1.
+        let mut v0 = &xP.X + &xP.Z;         // X_P + Z_P
+        let v1 = &v0 * &(affine_xQ - &one); // (x_Q - 1)(X_P + Z_P)
+        v0 = &xP.X - &xP.Z;                 // X_P - Z_P
+        let v2 = &v0 * &(affine_xQ + &one); // (x_Q + 1)(X_P - Z_P)
+        let v3 = (&v1 + &v2).square();      // 4(x_Q X_P - Z_P)^2
+        let v4 = (&v1 - &v2).square();      // 4(x_Q Z_P - X_P)^2
+        v0 = &xPmQ.Z * &v3;                 // 4X_{P-Q}(x_Q X_P - Z_P)^2
+        let z = &xPmQ.X * &v4;              // 4Z_{P-Q}(x_Q Z_P - X_P)^2
+        let x = v0;
+
+        ProjectivePoint{ X: x, Z: z }
+    }
     /// Given `xP = x(P)` and cached curve parameters `Aplus2C = A + 2*C, C4 = 4*C`, compute `xQ = x([2]P)`.
     fn double(&self, curve: &CachedCurveParameters) -> ProjectivePoint {
         let xP = *self;
@@ -339,6 +682,15 @@ impl ProjectivePoint {
     /// input scalar. All scalars of the same input length execute in uniform time.
     /// The scalar can be padded with zero bytes to ensure a uniform length.
     fn scalar_mul(&self, curve: &ProjectiveCurveParameters, scalar: &[u8]) -> ProjectivePoint {
+        let (xQ, _) = self.ladder(curve, scalar);
+        xQ
+    }
+    /// Given `x(P)` and a scalar `m` in little-endian bytes, compute both `x([m]P)`
+    /// and `x([m+1]P)` using the Montgomery ladder.
+    ///
+    /// This function's execution time is dependent only on the byte-length of the
+    /// input scalar.
+    fn ladder(&self, curve: &ProjectiveCurveParameters, scalar: &[u8]) -> (ProjectivePoint, ProjectivePoint) {
         let xP = *self;
         let cached_params = curve.cached_params();
         let mut x0 = ProjectivePoint{ X: ExtensionFieldElement::one(), Z: ExtensionFieldElement::zero() };
@@ -360,8 +712,7 @@ impl ProjectivePoint {
         }
         // Now prev_bit is the lowest bit of the scalar.
         ProjectivePoint::conditional_swap(&mut x0, &mut x1, prev_bit.into());
-        let xQ = x0;
-        xQ
+        (x0, x1)
     }
     /// Given `P = (x_P, y_P)` in affine coordinates, as well as projective points
     /// `x(Q), x(R) = x(P+Q)`, all in the prime-field subgroup of the starting curve
@@ -460,8 +811,7 @@ impl ProjectivePoint {
             }
         }
 
-        let xR = x2;
-        xR
+        x2
     }
     /// Right-to-left point multiplication, which given the x-coordinate
     /// of `P, Q` and `P-Q` calculates the x-coordinate of `R=P+[k]Q`.
@@ -475,8 +825,7 @@ impl ProjectivePoint {
 
         // Iterate over the bits of the scalar, bottom to top.
         let mut prev_bit: u8 = 0;
-        for i in 0..scalar.len() {
-            let scalar_byte = scalar[i];
+        for &scalar_byte in scalar.iter() {
             for j in 0..8 {
                 let bit = (scalar_byte >> (j as u32)) & 0x1;
                 ProjectivePoint::conditional_swap(&mut R1, &mut R2, (bit ^ prev_bit).into());
@@ -485,8 +834,120 @@ impl ProjectivePoint {
             }
         }
         ProjectivePoint::conditional_swap(&mut R1, &mut R2, prev_bit.into());
-        let xR = R1;
-        xR
+        R1
+    }
+    /// Fixed-basis three-point ladder, which given the affine x-coordinate
+    /// `affine_xP` of `P` in `E_0(F_p)` calculates the x-coordinate of
+    /// `R=P+[k]Q` on the starting curve, where `Q = \tau(P)` is the image of `P`
+    /// under the distortion map.
+    ///
+    /// For the SIDH bases `P_A` and `P_B` this uses precomputed tables of
+    /// `x([2^i]Q)`, which are in `F_p`, so that each bit of the scalar costs one
+    /// differential addition instead of a doubling and an addition, as proposed
+    /// by Faz-Hernández, López, Ochoa-Jiménez and Rodríguez-Henríquez. For other
+    /// points, it falls back to `right_to_left_ladder`. The execution time
+    /// depends only on the byte-length of the scalar.
+    //
+    // The ladder is right_to_left_ladder with the doublings R0 = [2^i]Q replaced
+    // by table lookups at the public index i. For P_A, the bits from 372 on are
+    // skipped, as [2^i]Q_A = O for them.
+    //
+    // Keygen uses secret_point instead, whose fixed-base comb is faster still.
+    pub fn fixed_basis_ladder(affine_xP: &PrimeFieldElement, scalar: &[u8]) -> ProjectivePoint {
+        let xP = ProjectivePoint::from_affine_prime_field(affine_xP); // = ( x_P : 1) = x(P)
+        let mut xQ = xP;                                              //
+        xQ.X = -(&xQ.X);                                              // = (-x_P : 1) = x(Q)
+        let xPmQ = ProjectivePoint::distort_and_difference(affine_xP); // = x(Q - P)
+
+        match fixed_basis_table(affine_xP, scalar.len()) {
+            Some(table) => ProjectivePoint::fixed_basis_ladder_with_table(table, &xP, &xPmQ, scalar),
+            None => {
+                // Starting curve has a = 0, so (A:C) = (0,1).
+                let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+                ProjectivePoint::right_to_left_ladder(&xP, &xQ, &xPmQ, &curve, scalar)
+            }
+        }
+    }
+    // The right-to-left ladder with the table x([2^i]Q) in place of the doublings.
+    fn fixed_basis_ladder_with_table(table: &[PrimeFieldElement], xP: &ProjectivePoint, xPmQ: &ProjectivePoint,
+                                     scalar: &[u8]) -> ProjectivePoint
+    {
+        let mut R1 = *xP;
+        let mut R2 = *xPmQ;
+
+        // Iterate over the bits of the scalar, bottom to top.
+        let mut prev_bit: u8 = 0;
+        for (i, affine_xQ) in table.iter().enumerate().take(8 * scalar.len()) {
+            let bit = (scalar[i / 8] >> ((i % 8) as u32)) & 0x1;
+            ProjectivePoint::conditional_swap(&mut R1, &mut R2, (bit ^ prev_bit).into());
+            R2 = R2.add_affine_prime_field(affine_xQ, &R1); // = xADD(R2, x([2^i]Q), R1)
+            prev_bit = bit;
+        }
+        ProjectivePoint::conditional_swap(&mut R1, &mut R2, prev_bit.into());
+        R1
+    }
+    /// Two-dimensional differential ladder, which given the x-coordinates of
+    /// `R, S` and `R-S` calculates the x-coordinate of `[a]R + [b]S`.
+    ///
+    /// This replaces `mont_twodim_scalarmult` of `sage/sidh_curve_and_isogeny_arithmetic.sage`,
+    /// which needs the y-coordinates, by an x-only ladder. The scalars are in
+    /// little-endian bytes and must have the same length; the execution time
+    /// depends only on that length. `R-S` and `R+S` must not be the identity or
+    /// `(0,0)`, as the differential additions are then undefined.
+    //
+    // Algorithm:
+    //
+    // Write U = [a']R + [b']S for the bits of (a,b) seen so far, and keep the
+    // square (x00, x10, x01, x11) = (x(U), x(U+R), x(U+S), x(U+R+S)). For the
+    // next bits (a_i, b_i), swap the rows if a_i = 1 and the columns if b_i = 1,
+    // so that x00 = x(U + [a_i]R + [b_i]S). Then
+    //
+    //      (x00, x10, x01, x11) <--- ( xDBL(x00), xADD(x00, x10, x(R)),
+    //                                  xADD(x00, x01, x(S)), xADD(x00, x11, x(R-S) or x(R+S)) )
+    //
+    // is the swapped square for 2U + [a_i]R + [b_i]S, and the difference of x00 and
+    // x11 is R-S if a_i != b_i, and R+S otherwise. As with the one-dimensional
+    // ladders, the swap back is merged into the next swap.
+    //
+    pub fn two_dimensional_ladder(xR: &ProjectivePoint, xS: &ProjectivePoint, xRmS: &ProjectivePoint,
+                                  curve: &ProjectiveCurveParameters, a: &[u8], b: &[u8]) -> ProjectivePoint
+    {
+        assert_eq!(a.len(), b.len(), "scalars must have the same length");
+        let cached_params = curve.cached_params();
+        let xRpS = xR.add(xS, xRmS);
+
+        // (x00, x10, x01, x11) <--- (x(O), x(R), x(S), x(R+S))
+        let mut x00 = ProjectivePoint{ X: ExtensionFieldElement::one(), Z: ExtensionFieldElement::zero() };
+        let mut x10 = *xR;
+        let mut x01 = *xS;
+        let mut x11 = xRpS;
+
+        // Iterate over the bits of the scalars, top to bottom.
+        let mut prev_a: u8 = 0;
+        let mut prev_b: u8 = 0;
+        for i in (0..a.len()).rev() {
+            for j in (0..8).rev() {
+                let a_bit = (a[i] >> (j as u32)) & 0x1;
+                let b_bit = (b[i] >> (j as u32)) & 0x1;
+                let swap_rows: Choice = (a_bit ^ prev_a).into();
+                let swap_cols: Choice = (b_bit ^ prev_b).into();
+                ProjectivePoint::conditional_swap(&mut x00, &mut x10, swap_rows);
+                ProjectivePoint::conditional_swap(&mut x01, &mut x11, swap_rows);
+                ProjectivePoint::conditional_swap(&mut x00, &mut x01, swap_cols);
+                ProjectivePoint::conditional_swap(&mut x10, &mut x11, swap_cols);
+
+                let diagonal = ProjectivePoint::conditional_select(&xRpS, xRmS, (a_bit ^ b_bit).into());
+                x11 = x00.add(&x11, &diagonal); // = xADD(x00, x11, x(R+S) or x(R-S))
+                x01 = x00.add(&x01, xS);        // = xADD(x00, x01, x(S))
+                assign!{(x00, x10) = x00.dbl_add(&x10, xR, &cached_params)};
+                prev_a = a_bit;
+                prev_b = b_bit;
+            }
+        }
+        ProjectivePoint::conditional_swap(&mut x00, &mut x10, prev_a.into());
+        ProjectivePoint::conditional_swap(&mut x01, &mut x11, prev_a.into());
+        ProjectivePoint::conditional_swap(&mut x00, &mut x01, prev_b.into());
+        x00
     }
     /// Given the affine x-coordinate `affine_xP` of `P`, compute the x-coordinate
     /// `x(\tau(P)-P) of \tau(P)-P`.
@@ -499,8 +960,7 @@ impl ProjectivePoint {
 
         let x = ExtensionFieldElement{ A: Fp751Element::zero(), B: b };
         let z = ExtensionFieldElement{ A: a, B: Fp751Element::zero() };
-        let xR = ProjectivePoint{ X: x, Z: z };
-        xR
+        ProjectivePoint{ X: x, Z: z }
     }
     /// Given an affine point `P = (x_P, y_P)` in the prime-field subgroup of the
     /// starting curve `E_0(F_p)`, together with a secret scalar `m`, compute `x(P+[m]Q)`,
@@ -595,12 +1055,90 @@ impl ProjectivePoint {
     // These formulas could probably be combined with the formulas for y-recover
     // and computed more efficiently, but efficiency isn't the biggest concern
     // here, since the bulk of the cost is already in the ladder.
+    //
+    // For the SIDH bases P_A and P_B and scalars of at most 48 bytes, steps 1 and 2
+    // are replaced by a fixed-base comb, which computes [m]P on E_0(F_p) together
+    // with its y-coordinate, and [m]Q = \tau([m]P); see the fixed_base module.
+    // This makes secret_point about 2.5 to 3 times faster (compare the benches
+    // secret_point_fixed_base_379bit_scalar and secret_point_ladder_379bit_scalar).
     pub fn secret_point(affine_xP: &PrimeFieldElement, affine_yP: &PrimeFieldElement, scalar: &[u8]) -> ProjectivePoint {
+        ProjectivePoint::blinded_secret_point(affine_xP, affine_yP, scalar, &PrimeFieldElement::one())
+    }
+    /// Like `secret_point`, but with the projective representations of the
+    /// starting points of the scalar multiplication by `m` randomized with a
+    /// random nonzero `l` in `F_p`. The result is the same `x(P+[m]Q)`, in a
+    /// random representation.
+    ///
+    /// See `randomize` for the rationale. Unlike `randomize`, `l` is taken in
+    /// `F_p` rather than `F_{p^2}`: the comb and the ladder work on coordinates
+    /// in `F_p`, which a blinding in `F_{p^2}` would move into `F_{p^2}`, making
+    /// every step several times more expensive.
+    pub fn secret_point_randomized<R: RngCore + CryptoRng>(affine_xP: &PrimeFieldElement, affine_yP: &PrimeFieldElement,
+                                                           scalar: &[u8], rng: &mut R) -> ProjectivePoint
+    {
+        let l = loop {
+            let l = PrimeFieldElement::random(rng);
+            if !l.vartime_eq(&PrimeFieldElement::zero()) {
+                break l;
+            }
+        };
+        ProjectivePoint::blinded_secret_point(affine_xP, affine_yP, scalar, &l)
+    }
+    // The secret_point computation, where blinding scales the projective
+    // coordinates of the starting points of the comb or the ladder.
+    fn blinded_secret_point(affine_xP: &PrimeFieldElement, affine_yP: &PrimeFieldElement, scalar: &[u8],
+                            blinding: &PrimeFieldElement) -> ProjectivePoint
+    {
+        let (XmQ, YmQ, ZmQ) = match fixed_base_table(affine_xP, affine_yP) {
+            Some(table) if scalar.len() <= FIXED_BASE_SCALAR_BYTES => {
+                // [m]Q = \tau([m]P) = (-X_{mP} : Y_{mP}*i : Z_{mP}).
+                let (XmP, YmP, ZmP) = fixed_base_scalar_mul(table, scalar, blinding);
+                (-(&XmP), YmP, ZmP)
+            }
+            _ => ProjectivePoint::distorted_scalar_mul(affine_xP, affine_yP, scalar, blinding),
+        };
+
+        // Now compute x(P + [m]Q) = (X_Ra + i*X_Rb : Z_R)
+        let mut XRb = &ZmQ.square() * &YmQ; // = Y_{mQ} * Z_{mQ}^2
+        XRb = &XRb * affine_yP;             // = Y_{mQ} * y_P * Z_{mQ}^2
+        XRb = &XRb + &XRb;                  // = 2 * Y_{mQ} * y_P * Z_{mQ}^2
+        XRb = -(&XRb);                      // = -2 * Y_{mQ} * y_P * Z_{mQ}^2
+
+        let mut t0 = (affine_yP * &ZmQ).square(); // = (y_P * Z_{mQ})^2
+        let mut t1 = YmQ.square();                // = Y_{mQ}^2
+        let mut XRa = &t0 - &t1;            // = (y_P * Z_{mQ})^2 - Y_{mQ}^2
+        XRa = &XRa * &ZmQ;                  // = Z_{mQ}*((y_P * Z_{mQ})^2 - Y_{mQ}^2)
+        t0 = affine_xP * &ZmQ;              // = x_P * Z_{mQ}
+        t1 = &XmQ + &t0;                    // = X_{mQ} + x_P*Z_{mQ}
+        t0 = &XmQ - &t0;                    // = X_{mQ} - x_P*Z_{mQ}
+        t0 = t0.square();                   // = (X_{mQ} - x_P*Z_{mQ})^2
+        t1 = &t1 * &t0;                     // = (X_{mQ} + x_P*Z_{mQ})*(X_{mQ} - x_P*Z_{mQ})^2
+        XRa = &XRa - &t1;                   // = Z_{mQ}*((y_P*Z_{mQ})^2 - Y_{mQ}^2) - (X_{mQ} + x_P*Z_{mQ})*(X_{mQ} - x_P*Z_{mQ})^2
+
+        let ZR = &ZmQ * &t0;                // = Z_{mQ}*(X_{mQ} - x_P*Z_{mQ})^2
+
+        let mut xR = ProjectivePoint{ X: ExtensionFieldElement::zero(), Z: ExtensionFieldElement::zero() };
+        xR.X.A = XRa.A;
+        xR.X.B = XRb.A;
+        xR.Z.A = ZR.A;
+
+        xR
+    }
+    /// Given an affine point `P = (x_P, y_P)` in the prime-field subgroup of the
+    /// starting curve `E_0(F_p)`, together with a scalar `m`, compute
+    /// `[m]Q = (X : Y*i : Z)` for `Q = \tau(P)`, with `X, Y, Z` in `F_p`, using the
+    /// Montgomery ladder and Okeya-Sakurai coordinate recovery. The ladder starts
+    /// from representations scaled by `blinding`.
+    ///
+    /// See secret_point for more details.
+    fn distorted_scalar_mul(affine_xP: &PrimeFieldElement, affine_yP: &PrimeFieldElement, scalar: &[u8],
+                            blinding: &PrimeFieldElement) -> (PrimeFieldElement, PrimeFieldElement, PrimeFieldElement)
+    {
         let mut xQ = ProjectivePrimeFieldPoint::from_affine(affine_xP);
         xQ.X = -(&xQ.X);
 
         // Compute x([m]Q) = (X_{mQ} : Z_{mQ}), x([m+1]Q) = (X_{m1Q} : Z_{m1Q}).
-        let (xmQ, xm1Q) = xQ.scalar_mul_prime_field(&E0_A_PLUS2_OVER4, scalar);
+        let (xmQ, xm1Q) = xQ.blinded_scalar_mul_prime_field(&E0_A_PLUS2_OVER4, scalar, blinding);
 
         // Now perform coordinate recovery:
 	    // [m]Q = (X_{mQ} : Y_{mQ}*i : Z_{mQ})
@@ -627,31 +1165,247 @@ impl ProjectivePoint {
 	    // X_{mQ} = -2*X_{mQ}*Z_{mQ}*Z_{m1Q}*y_P
         let XmQ = &xmQ.X * &t0;
 
-        // Now compute x(P + [m]Q) = (X_Ra + i*X_Rb : Z_R)
-        let mut XRb = &ZmQ.square() * &YmQ; // = Y_{mQ} * Z_{mQ}^2
-        XRb = &XRb * affine_yP;             // = Y_{mQ} * y_P * Z_{mQ}^2
-        XRb = &XRb + &XRb;                  // = 2 * Y_{mQ} * y_P * Z_{mQ}^2
-        XRb = -(&XRb);                      // = -2 * Y_{mQ} * y_P * Z_{mQ}^2
+        (XmQ, YmQ, ZmQ)
+    }
+}
 
-        t0 = (affine_yP * &ZmQ).square();   // = (y_P * Z_{mQ})^2
-        t1 = YmQ.square();                  // = Y_{mQ}^2
-        let mut XRa = &t0 - &t1;            // = (y_P * Z_{mQ})^2 - Y_{mQ}^2
-        XRa = &XRa * &ZmQ;                  // = Z_{mQ}*((y_P * Z_{mQ})^2 - Y_{mQ}^2)
-        t0 = affine_xP * &ZmQ;              // = x_P * Z_{mQ}
-        t1 = &XmQ + &t0;                    // = X_{mQ} + x_P*Z_{mQ}
-        t0 = &XmQ - &t0;                    // = X_{mQ} - x_P*Z_{mQ}
-        t0 = t0.square();                   // = (X_{mQ} - x_P*Z_{mQ})^2
-        t1 = &t1 * &t0;                     // = (X_{mQ} + x_P*Z_{mQ})*(X_{mQ} - x_P*Z_{mQ})^2
-        XRa = &XRa - &t1;                   // = Z_{mQ}*((y_P*Z_{mQ})^2 - Y_{mQ}^2) - (X_{mQ} + x_P*Z_{mQ})*(X_{mQ} - x_P*Z_{mQ})^2
+/// A point `(X:Y:Z)` on a Montgomery curve `E_(A:C)`, including its y-coordinate.
+///
+/// Unlike `ProjectivePoint`, this distinguishes `P` from `-P`, at the cost of
+/// slower arithmetic. The identity is `(0:1:0)`.
+#[derive(Copy, Clone, PartialEq)]
+pub struct MontgomeryPoint {
+    pub X: ExtensionFieldElement,
+    pub Y: ExtensionFieldElement,
+    pub Z: ExtensionFieldElement,
+}
 
-        let ZR = &ZmQ * &t0;                // = Z_{mQ}*(X_{mQ} - x_P*Z_{mQ})^2
+impl ConditionallySelectable for MontgomeryPoint {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        MontgomeryPoint{
+            X: ExtensionFieldElement::conditional_select(&a.X, &b.X, choice),
+            Y: ExtensionFieldElement::conditional_select(&a.Y, &b.Y, choice),
+            Z: ExtensionFieldElement::conditional_select(&a.Z, &b.Z, choice)
+        }
+    }
 
-        let mut xR = ProjectivePoint{ X: ExtensionFieldElement::zero(), Z: ExtensionFieldElement::zero() };
-        xR.X.A = XRa.A;
-        xR.X.B = XRb.A;
-        xR.Z.A = ZR.A;
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        ExtensionFieldElement::conditional_swap(&mut a.X, &mut b.X, choice);
+        ExtensionFieldElement::conditional_swap(&mut a.Y, &mut b.Y, choice);
+        ExtensionFieldElement::conditional_swap(&mut a.Z, &mut b.Z, choice);
+    }
+}
 
-        xR
+impl Debug for MontgomeryPoint {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "MontgomeryPoint(X: {:?}\nY: {:?}\nZ: {:?})", &self.X, &self.Y, &self.Z)
+    }
+}
+
+impl Neg for &MontgomeryPoint {
+    type Output = MontgomeryPoint;
+    fn neg(self) -> MontgomeryPoint {
+        MontgomeryPoint{ X: self.X, Y: -&self.Y, Z: self.Z }
+    }
+}
+
+impl MontgomeryPoint {
+    /// Construct the identity `(0:1:0)`.
+    pub fn identity() -> MontgomeryPoint {
+        MontgomeryPoint{ X: ExtensionFieldElement::zero(), Y: ExtensionFieldElement::one(), Z: ExtensionFieldElement::zero() }
+    }
+
+    /// Construct the point `(x:y:1)`. The caller is responsible for it lying on the curve.
+    pub fn from_affine(x: &ExtensionFieldElement, y: &ExtensionFieldElement) -> MontgomeryPoint {
+        MontgomeryPoint{ X: *x, Y: *y, Z: ExtensionFieldElement::one() }
+    }
+    /// Returns the affine coordinates `(x, y)`. The identity has none, so the output
+    /// is undefined for it.
+    pub fn to_affine(self) -> (ExtensionFieldElement, ExtensionFieldElement) {
+        let z_inv = self.Z.inv();
+        (&self.X * &z_inv, &self.Y * &z_inv)
+    }
+    /// Forget the y-coordinate, returning `x(P)` on the Kummer line.
+    pub fn to_projective_point(self) -> ProjectivePoint {
+        // The identity (0:1:0) maps to (1:0).
+        let mut X = self.X;
+        X.conditional_assign(&ExtensionFieldElement::one(), self.Z.ct_eq(&ExtensionFieldElement::zero()));
+        ProjectivePoint{ X, Z: self.Z }
+    }
+    /// Returns true if this is the identity. Takes variable time.
+    pub fn is_identity(&self) -> bool {
+        self.Z.vartime_eq(&ExtensionFieldElement::zero())
+    }
+    /// Returns true if both sides are equal. Takes variable time.
+    pub fn vartime_eq(&self, rhs: &MontgomeryPoint) -> bool {
+        (&self.X * &rhs.Z).vartime_eq(&(&self.Z * &rhs.X)) &&
+        (&self.Y * &rhs.Z).vartime_eq(&(&self.Z * &rhs.Y))
+    }
+    /// Returns true if the point satisfies `CY^2Z = CX^3 + AX^2Z + CXZ^2`. Takes
+    /// variable time.
+    pub fn is_on_curve(&self, curve: &ProjectiveCurveParameters) -> bool {
+        let (X, Y, Z) = (&self.X, &self.Y, &self.Z);
+        let lhs = &(&curve.C * &Y.square()) * Z;    // = CY^2Z
+        let mut rhs = &(&curve.C * X) + &(&curve.A * Z);
+        rhs = &(&rhs * X) + &(&curve.C * &Z.square());
+        rhs = &rhs * X;                             // = CX^3 + AX^2Z + CXZ^2
+        lhs.vartime_eq(&rhs)
+    }
+    /// Compute `[2]P`. Takes variable time.
+    pub fn double(&self, curve: &ProjectiveCurveParameters) -> MontgomeryPoint {
+        if self.is_identity() {
+            return *self;
+        }
+        // The tangent at P has slope lambda = N/D, with N = C(3X^2 + Z^2) + 2AXZ
+        // and D = 2CYZ, for a = A/C. Writing x([2]P) = lambda^2 - a - 2x = T/D^2
+        // gives T = N^2 - 4CY^2Z(AZ + 2CX), and y([2]P) = lambda*(x - x([2]P)) - y.
+        // If Y = 0, then D = 0 and the result is (0 : -N^3Z : 0), the identity.
+        let (A, C) = (&curve.A, &curve.C);
+        let (X, Y, Z) = (&self.X, &self.Y, &self.Z);
+        let X2 = X.square();
+        let AXZ = &(A * X) * Z;
+        let mut N = &(&X2 + &X2) + &X2;             // = 3X^2
+        N = &(&N + &Z.square()) * C;                // = C(3X^2 + Z^2)
+        N = &(&N + &AXZ) + &AXZ;                    // = C(3X^2 + Z^2) + 2AXZ
+        let D = &(&(Y + Y) * Z) * C;                // = 2CYZ
+        let D2 = D.square();                        // = D^2
+        let mut T = &(A * Z) + &(&(X + X) * C);     // = AZ + 2CX
+        T = &(&(&T * Z) * &Y.square()) * C;         // = CY^2Z(AZ + 2CX)
+        T = &T + &T;
+        T = &N.square() - &(&T + &T);               // = N^2 - 4CY^2Z(AZ + 2CX)
+
+        let X3 = &(&T * &D) * Z;                    // = TDZ
+        let mut Y3 = &(X * &D2) - &(&T * Z);        // = XD^2 - TZ
+        Y3 = &(&N * &Y3) - &(&(Y * &D2) * &D);      // = N(XD^2 - TZ) - YD^3
+        let Z3 = &(&D2 * &D) * Z;                   // = D^3Z
+        MontgomeryPoint{ X: X3, Y: Y3, Z: Z3 }
+    }
+    /// Compute `P + Q`, handling all special cases. Takes variable time.
+    pub fn add(&self, other: &MontgomeryPoint, curve: &ProjectiveCurveParameters) -> MontgomeryPoint {
+        if self.is_identity() {
+            return *other;
+        }
+        if other.is_identity() {
+            return *self;
+        }
+        let (X1, Y1, Z1) = (&self.X, &self.Y, &self.Z);
+        let (x2, y2, z2) = (&other.X, &other.Y, &other.Z);
+        if (X1 * z2).vartime_eq(&(x2 * Z1)) {
+            if (Y1 * z2).vartime_eq(&(y2 * Z1)) {
+                return self.double(curve);
+            }
+            return MontgomeryPoint::identity();
+        }
+
+        // Addition of distinct points, as ADD in sage/sidh_curve_and_isogeny_arithmetic.sage,
+        // with all coordinates scaled by C to avoid inverting it for a = A/C.
+        // Cost: 25M+5S+9a+4s.
+        let (A, C) = (&curve.A, &curve.C);
+        let mut t0 = x2 * Z1;
+        let mut t1 = X1 * z2;
+        let t2 = &t0 + &t1;         // = x2*Z1 + X1*z2
+        let t3 = &t1 - &t0;         // = X1*z2 - x2*Z1
+        t0 = x2 * X1;
+        t1 = Z1 * z2;
+        let mut t4 = &t0 + &t1;     // = x2*X1 + Z1*z2
+        t0 = &t0 * A;
+        let mut t5 = &(y2 * Y1) * C;
+        t0 = &t0 - &t5;
+        t0 = &t0 * &t1;
+        t0 = &t0 + &t0;             // = 2*Z1*z2*(A*x2*X1 - C*y2*Y1)
+        t5 = &(&t2 * &t4) * C;
+        t5 = &t5 + &t0;             // = C*(x2*Z1 + X1*z2)*(x2*X1 + Z1*z2) + 2*Z1*z2*(A*x2*X1 - C*y2*Y1)
+        t0 = X1.square();
+        let mut t6 = Z1.square();
+        t0 = &t0 + &t6;             // = X1^2 + Z1^2
+        t1 = &t1 + &t1;             // = 2*Z1*z2
+        let mut t7 = y2 * X1;
+        t6 = x2 * Y1;
+        t7 = &t7 - &t6;             // = y2*X1 - x2*Y1
+        t1 = &t1 * &t7;
+        t7 = A * &t2;
+        t4 = &(&t4 * C) + &t7;      // = A*(X1*z2 + x2*Z1) + C*(x2*X1 + Z1*z2)
+        t4 = &t1 * &t4;
+        t1 = y2 * z2;
+        t0 = &t0 * &t1;             // = y2*z2*(X1^2 + Z1^2)
+        t1 = z2.square();
+        t6 = x2.square();
+        t1 = &t1 + &t6;
+        t6 = Z1 * Y1;
+        t1 = &t1 * &t6;             // = Z1*Y1*(z2^2 + x2^2)
+        t0 = &t0 - &t1;
+        t0 = &(&t2 * &t0) * C;
+        let X3 = &t5 * &t3;
+        let Y3 = &t4 + &t0;
+        let Z3 = &(&t3.square() * &t3) * C; // = C*(X1*z2 - x2*Z1)^3
+        MontgomeryPoint{ X: X3, Y: Y3, Z: Z3 }
+    }
+    /// Given `P = (x_P, y_P)` in affine coordinates, as well as `x(Q)` and `x(R)`
+    /// for `R = P + Q`, use the Okeya-Sakurai coordinate recovery strategy to
+    /// recover `Q = (X_Q : Y_Q : Z_Q)`.
+    ///
+    /// This is `recover_os` of sage/sidh_curve_and_isogeny_arithmetic.sage, with
+    /// a projective curve constant. It requires `y_P != 0`, and `Q, R` not the
+    /// identity.
+    pub fn okeya_sakurai_recovery(curve: &ProjectiveCurveParameters,
+                                  affine_xP: &ExtensionFieldElement, affine_yP: &ExtensionFieldElement,
+                                  xQ: &ProjectivePoint, xR: &ProjectivePoint) -> MontgomeryPoint
+    {
+        let (X1, Z1) = (&xQ.X, &xQ.Z);
+        let (X2, Z2) = (&xR.X, &xR.Z);
+        let v1 = affine_xP * Z1;                    // = x_P*Z_Q
+        let mut v2 = &curve.C * &(X1 + &v1);        // = C(X_Q + x_P*Z_Q)
+        let v3 = &curve.A * Z1;                     // = A*Z_Q
+        v2 = &(&v2 + &v3) + &v3;                    // = C(X_Q + x_P*Z_Q) + 2A*Z_Q
+        v2 = &v2 * &(&(affine_xP * X1) + Z1);       // = (C(X_Q + x_P*Z_Q) + 2A*Z_Q)*(x_P*X_Q + Z_Q)
+        let v4 = &v3 * Z1;                          // = A*Z_Q^2
+        v2 = &(&v2 - &v4) - &v4;
+        v2 = &v2 * Z2;                              // = Z_R*((C(X_Q + x_P*Z_Q) + 2A*Z_Q)*(x_P*X_Q + Z_Q) - 2A*Z_Q^2)
+        let v5 = &(&curve.C * &(X1 - &v1).square()) * X2; // = C*X_R*(X_Q - x_P*Z_Q)^2
+        let Y = &v2 - &v5;
+
+        let mut v6 = &(&curve.C * affine_yP) * &(Z1 * Z2);
+        v6 = &v6 + &v6;                             // = 2C*y_P*Z_Q*Z_R
+        MontgomeryPoint{ X: &v6 * X1, Y, Z: &v6 * Z1 }
+    }
+    /// Given the affine x-coordinates of `P, Q` and `Q-P` on the curve, recover
+    /// `P` and `Q` with their y-coordinates, as `recover_y` of
+    /// sage/sidh_curve_and_isogeny_arithmetic.sage.
+    ///
+    /// The points are recovered up to a common sign, i.e. the output may also be
+    /// `(-P, -Q)`, and are otherwise consistent: `Q - P` has the given x-coordinate.
+    pub fn recover_points(curve: &ProjectiveCurveParameters, affine_xP: &ExtensionFieldElement,
+                          affine_xQ: &ExtensionFieldElement, affine_xQmP: &ExtensionFieldElement) ->
+                         (MontgomeryPoint, MontgomeryPoint)
+    {
+        let a = &curve.A * &curve.C.inv();
+        let y = affine_y(&a, affine_xQmP);
+        let xP = ProjectivePoint::from_affine(affine_xP);
+        let xQ = ProjectivePoint::from_affine(affine_xQ);
+        // P + (Q-P) = Q, where Q-P = (x, -y), and Q + (P-Q) = P, where P-Q = (x, y).
+        let P = MontgomeryPoint::okeya_sakurai_recovery(curve, affine_xQmP, &-&y, &xP, &xQ);
+        let Q = MontgomeryPoint::okeya_sakurai_recovery(curve, affine_xQmP, &y, &xQ, &xP);
+        (P, Q)
+    }
+    /// Given a point `P` and a scalar `m` in little-endian bytes, compute `[m]P`, using
+    /// the Montgomery ladder on `x(P)` followed by Okeya-Sakurai coordinate recovery.
+    ///
+    /// This function's execution time is dependent only on the byte-length of the
+    /// input scalar.
+    pub fn scalar_mul(&self, curve: &ProjectiveCurveParameters, scalar: &[u8]) -> MontgomeryPoint {
+        let (x, y) = self.to_affine();
+        let (xQ, xR) = self.to_projective_point().ladder(curve, scalar);
+        let mut Q = MontgomeryPoint::okeya_sakurai_recovery(curve, &x, &y, &xQ, &xR);
+
+        let zero = ExtensionFieldElement::zero();
+        // If y_P = 0, then P has order 2, and [m]P = (X_Q : 0 : Z_Q) or the identity.
+        let order_two = MontgomeryPoint{ X: xQ.X, Y: zero, Z: xQ.Z };
+        Q.conditional_assign(&order_two, y.ct_eq(&zero));
+        // If [m+1]P is the identity, then [m]P = -P.
+        Q.conditional_assign(&-self, xR.Z.ct_eq(&zero));
+        // If [m]P is the identity, the recovery is not defined.
+        Q.conditional_assign(&MontgomeryPoint::identity(), xQ.Z.ct_eq(&zero));
+        Q
     }
 }
 
@@ -707,9 +1461,8 @@ impl ProjectivePrimeFieldPoint {
         }
     }
 
-    pub fn to_affine(&self) -> PrimeFieldElement {
-        let affine_x = &self.Z.inv() * &self.X;
-        affine_x
+    pub fn to_affine(self) -> PrimeFieldElement {
+        &self.Z.inv() * &self.X
     }
     /// Returns true if both sides are equal. Takes variable time.
     pub fn vartime_eq(&self, _rhs: &ProjectivePrimeFieldPoint) -> bool {
@@ -801,10 +1554,17 @@ impl ProjectivePrimeFieldPoint {
     /// scalar. All scalars of the same input length execute in uniform time.
     /// The scalar can be padded with zero bytes to ensure a uniform length.
     fn scalar_mul_prime_field(&self, aPlus2Over4: &PrimeFieldElement, scalar: &[u8]) -> (ProjectivePrimeFieldPoint, ProjectivePrimeFieldPoint)
+    {
+        self.blinded_scalar_mul_prime_field(aPlus2Over4, scalar, &PrimeFieldElement::one())
+    }
+    // The Montgomery ladder, started from x(O) = (l : 0) and x(P) = (lX_P : lZ_P)
+    // for l = blinding. The difference x(P) used by dbl_add keeps Z_P = 1.
+    fn blinded_scalar_mul_prime_field(&self, aPlus2Over4: &PrimeFieldElement, scalar: &[u8], blinding: &PrimeFieldElement) ->
+                                     (ProjectivePrimeFieldPoint, ProjectivePrimeFieldPoint)
     {
         let xP = *self;
-        let mut x0 = ProjectivePrimeFieldPoint{ X: PrimeFieldElement::one(), Z: PrimeFieldElement::zero() };
-        let mut x1 = xP;
+        let mut x0 = ProjectivePrimeFieldPoint{ X: *blinding, Z: PrimeFieldElement::zero() };
+        let mut x1 = ProjectivePrimeFieldPoint{ X: &xP.X * blinding, Z: &xP.Z * blinding };
 
         // Iterate over the bits of the scalar, top to bottom.
         let mut prev_bit: u8 = 0;
@@ -1023,6 +1783,63 @@ mod test {
         assert!(AFFINE_XAP.vartime_eq(&affine_xQ), "\nExpected\n{:?}\nfound\n{:?}", AFFINE_XAP, affine_xQ);
     }
 
+    // The point P with x(P) = AFFINE_XP on CURVE.
+    fn montgomery_point_p() -> MontgomeryPoint {
+        let a = &CURVE.A * &CURVE.C.inv();
+        MontgomeryPoint::from_affine(&AFFINE_XP, &affine_y(&a, &AFFINE_XP))
+    }
+
+    #[test]
+    fn montgomery_point_arithmetic_versus_kummer_line() {
+        let P = montgomery_point_p();
+        assert!(P.is_on_curve(&CURVE));
+
+        let P2 = P.double(&CURVE);
+        let P3 = P2.add(&P, &CURVE);
+        assert!(P2.is_on_curve(&CURVE) && P3.is_on_curve(&CURVE));
+        assert!(P2.to_projective_point().vartime_eq(&ProjectivePoint::from_affine(&AFFINE_XP2)));
+        assert!(P3.to_projective_point().vartime_eq(&ProjectivePoint::from_affine(&AFFINE_XP3)));
+        assert!(P.add(&P2, &CURVE).vartime_eq(&P3));
+        assert!(P.add(&P, &CURVE).vartime_eq(&P2));
+        assert!(P3.add(&-&P, &CURVE).vartime_eq(&P2));
+
+        let O = MontgomeryPoint::identity();
+        assert!(P.add(&-&P, &CURVE).is_identity());
+        assert!(P.add(&O, &CURVE).vartime_eq(&P) && O.add(&P, &CURVE).vartime_eq(&P));
+        assert!(O.double(&CURVE).is_identity());
+        assert!(O.to_projective_point().Z.vartime_eq(&ExtensionFieldElement::zero()));
+        assert!(!O.to_projective_point().X.vartime_eq(&ExtensionFieldElement::zero()));
+    }
+
+    #[test]
+    fn montgomery_point_scalar_mul_versus_sage() {
+        let P = montgomery_point_p();
+        let Q = P.scalar_mul(&CURVE, &M_SCALAR_BYTES[..]); // = [m]P
+        assert!(Q.is_on_curve(&CURVE));
+        assert!(Q.to_projective_point().vartime_eq(&ProjectivePoint::from_affine(&AFFINE_XAP)));
+
+        // The recovered y-coordinate has the right sign: [m]P + P = [m+1]P.
+        let mut m1 = M_SCALAR_BYTES;
+        m1[0] += 1;
+        assert!(Q.add(&P, &CURVE).vartime_eq(&P.scalar_mul(&CURVE, &m1[..])));
+
+        assert!(P.scalar_mul(&CURVE, &[0u8]).is_identity());
+        assert!(P.scalar_mul(&CURVE, &[1u8]).vartime_eq(&P));
+        assert!(P.scalar_mul(&CURVE, &[2u8]).vartime_eq(&P.double(&CURVE)));
+    }
+
+    #[test]
+    fn montgomery_point_recovery() {
+        let P = montgomery_point_p();
+        let Q = P.scalar_mul(&CURVE, &M_SCALAR_BYTES[..]);
+        let affine_xQ = Q.to_affine().0;
+        let affine_xQmP = Q.add(&-&P, &CURVE).to_affine().0;
+
+        let (P1, Q1) = MontgomeryPoint::recover_points(&CURVE, &AFFINE_XP, &affine_xQ, &affine_xQmP);
+        assert!(P1.is_on_curve(&CURVE) && Q1.is_on_curve(&CURVE));
+        assert!((P1.vartime_eq(&P) && Q1.vartime_eq(&Q)) || (P1.vartime_eq(&-&P) && Q1.vartime_eq(&-&Q)));
+    }
+
     #[test]
     fn recover_curve_params() {
         // Created using old public key generation code that output the a value:
@@ -1085,6 +1902,77 @@ mod test {
         assert!(affine_xR.vartime_eq(&sage_affine_xR), "\nExpected\n{:?}\nfound\n{:?}", sage_affine_xR, affine_xR);
     }
 
+    #[test]
+    fn randomized_secret_point_versus_secret_point() {
+        let mut rng = rand::thread_rng();
+        let mut scalar = [0u8; 48];
+        rng.fill_bytes(&mut scalar[..]);
+        // The comb for P_B, and the ladder for -P_B, which has no comb tables.
+        let bases = [(AFFINE_X_PB, AFFINE_Y_PB), (AFFINE_X_PB, -(&AFFINE_Y_PB))];
+        for (affine_x, affine_y) in bases.iter() {
+            let affine_xR = ProjectivePoint::secret_point_randomized(affine_x, affine_y, &scalar[..], &mut rng).to_affine();
+            let xT = ProjectivePoint::secret_point(affine_x, affine_y, &scalar[..]);
+            let affine_xT = xT.to_affine();
+            assert!(affine_xR.vartime_eq(&affine_xT), "\nExpected\n{:?}\nfound\n{:?}", affine_xT, affine_xR);
+
+            let affine_xU = xT.randomize(&mut rng).to_affine();
+            assert!(affine_xU.vartime_eq(&affine_xT), "\nExpected\n{:?}\nfound\n{:?}", affine_xT, affine_xU);
+        }
+    }
+
+    #[test]
+    fn fixed_basis_ladder_versus_secret_point() {
+        let mut rng = rand::thread_rng();
+        let bases = [(AFFINE_X_PA, AFFINE_Y_PA), (AFFINE_X_PB, AFFINE_Y_PB)];
+        for (affine_x, affine_y) in bases.iter() {
+            for len in [1, 47, 48].iter() {
+                let mut scalar = [0u8; 48];
+                rng.fill_bytes(&mut scalar[..*len]);
+                let xR = ProjectivePoint::fixed_basis_ladder(affine_x, &scalar[..*len]);
+                let xT = ProjectivePoint::secret_point(affine_x, affine_y, &scalar[..*len]);
+                assert!(xR.vartime_eq(&xT), "\nExpected\n{:?}\nfound\n{:?}", xT, xR);
+            }
+            // The table of P_A stops at [2^371]Q_A, while P_B falls back to the
+            // generic ladder for scalars longer than its table.
+            let all_ones = [0xff; 49];
+            let xR = ProjectivePoint::fixed_basis_ladder(affine_x, &all_ones[..]);
+            let xT = ProjectivePoint::secret_point(affine_x, affine_y, &all_ones[..]);
+            assert!(xR.vartime_eq(&xT), "\nExpected\n{:?}\nfound\n{:?}", xT, xR);
+        }
+    }
+
+    #[test]
+    fn two_dimensional_ladder_versus_three_point_ladder() {
+        // x(P + [m]Q) = x([1]P + [m]Q).
+        let mut one = [0u8; 32];
+        one[0] = 1;
+        let xR = ProjectivePoint::two_dimensional_ladder(&THREE_POINT_LADDER_INPUTS[0], &THREE_POINT_LADDER_INPUTS[1], &THREE_POINT_LADDER_INPUTS[2], &CURVE, &one[..], &M_SCALAR_BYTES[..]);
+        let xT = ProjectivePoint::three_point_ladder(&THREE_POINT_LADDER_INPUTS[0], &THREE_POINT_LADDER_INPUTS[1], &THREE_POINT_LADDER_INPUTS[2], &CURVE, &M_SCALAR_BYTES[..]);
+        assert!(xR.vartime_eq(&xT), "\nExpected\n{:?}\nfound\n{:?}", xT, xR);
+    }
+
+    #[test]
+    fn two_dimensional_ladder_versus_montgomery_points() {
+        let e0 = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let (affine_xR, affine_yR, affine_xS, affine_yS) = e0.generate_3_torsion_basis();
+        let R = MontgomeryPoint::from_affine(&affine_xR, &affine_yR);
+        let S = MontgomeryPoint::from_affine(&affine_xS, &affine_yS);
+        let xR = R.to_projective_point();
+        let xS = S.to_projective_point();
+        let xRmS = R.add(&-&S, &e0).to_projective_point();
+
+        let mut a = [0u8; 48];
+        let mut b = [0u8; 48];
+        a[..32].copy_from_slice(&M_SCALAR_BYTES);
+        b[16..].copy_from_slice(&M_SCALAR_BYTES);
+        let scalars = [(a, b), (b, a), (a, a), (a, [0u8; 48]), ([0u8; 48], b), ([0u8; 48], [0u8; 48])];
+        for &(a, b) in scalars.iter() {
+            let T = R.scalar_mul(&e0, &a[..]).add(&S.scalar_mul(&e0, &b[..]), &e0);
+            let xT = ProjectivePoint::two_dimensional_ladder(&xR, &xS, &xRmS, &e0, &a[..], &b[..]);
+            assert!(xT.vartime_eq(&T.to_projective_point()), "\nExpected\n{:?}\nfound\n{:?}", T.to_projective_point(), xT);
+        }
+    }
+
     #[test]
     fn point_triple_versus_add_double() {
         fn triple_equals_add_double(curve: ProjectiveCurveParameters, P: ProjectivePoint) -> bool {
@@ -1099,6 +1987,141 @@ mod test {
         QuickCheck::new().quickcheck(triple_equals_add_double as fn(ProjectiveCurveParameters, ProjectivePoint) -> bool);
     }
 
+    #[test]
+    fn random_point_has_order_dividing_p_plus_1() {
+        let mut rng = rand::thread_rng();
+        let e0 = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        for _ in 0..4 {
+            let xP = ProjectivePoint::random(&e0, &mut rng);
+            let xQ = xP.pow2k(&e0, E_A).pow3k(&e0, E_B); // = x([p+1]P)
+            assert!(xQ.Z.vartime_eq(&ExtensionFieldElement::zero()), "Expected [p+1]P = O");
+        }
+    }
+
+    #[test]
+    fn random_points_of_exact_order() {
+        let mut rng = rand::thread_rng();
+        // E_0 and the (supersingular) codomain of the first 4-isogeny from E_0.
+        let e0 = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let mut e1 = e0;
+        e1.A = ExtensionFieldElement::from_u64(12);
+        e1.C = -(&ExtensionFieldElement::from_u64(2));
+        let zero = ExtensionFieldElement::zero();
+
+        for curve in [e0, e1].iter() {
+            for &e in [1, 5, E_A].iter() {
+                let xP = ProjectivePoint::random_order_2e(curve, e, &mut rng);
+                assert!(!xP.pow2k(curve, e - 1).Z.vartime_eq(&zero));
+                assert!(xP.pow2k(curve, e).Z.vartime_eq(&zero));
+            }
+            for &e in [1, 5, E_B].iter() {
+                let xP = ProjectivePoint::random_order_3e(curve, e, &mut rng);
+                assert!(!xP.pow3k(curve, e - 1).Z.vartime_eq(&zero));
+                assert!(xP.pow3k(curve, e).Z.vartime_eq(&zero));
+            }
+        }
+    }
+
+    #[test]
+    fn singular_curves() {
+        let one = ExtensionFieldElement::one();
+        let two = ExtensionFieldElement::from_u64(2);
+        for &(A, C) in [(two, one), (-(&two), one), (one, ExtensionFieldElement::zero())].iter() {
+            assert!(ProjectiveCurveParameters{ A, C }.is_singular());
+        }
+        let e0 = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: one };
+        assert!(!e0.is_singular());
+    }
+
+    #[test]
+    fn supersingular_curves() {
+        let mut rng = rand::thread_rng();
+        // E_0 and the (supersingular) codomain of the first 4-isogeny from E_0.
+        let e0 = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let mut e1 = e0;
+        e1.A = ExtensionFieldElement::from_u64(12);
+        e1.C = -(&ExtensionFieldElement::from_u64(2));
+        for curve in [e0, e1].iter() {
+            assert!(curve.is_supersingular(&mut rng));
+            assert!(curve.has_p_plus_1_squared_points(&mut rng));
+        }
+
+        // Curves through random x-coordinates are ordinary, except with negligible
+        // probability, as is y^2 = x^3 + 3x^2 + x.
+        let xP = ExtensionFieldElement::random(&mut rng);
+        let xQ = ExtensionFieldElement::random(&mut rng);
+        let xQmP = ExtensionFieldElement::random(&mut rng);
+        let ordinary = [ProjectiveCurveParameters::recover_curve_parameters(&xP, &xQ, &xQmP),
+                        ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::from_u64(3))];
+        for curve in ordinary.iter() {
+            assert!(!curve.is_supersingular(&mut rng));
+            assert!(!curve.has_p_plus_1_squared_points(&mut rng));
+        }
+        let singular = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::from_u64(2));
+        assert!(!singular.is_supersingular(&mut rng));
+    }
+
+    #[test]
+    fn generate_torsion_bases_regression() {
+        // Regression values for E_0, recorded from this implementation so that
+        // the deterministic choice of basis does not change unnoticed. They are
+        // not independent test vectors; the orders are checked below on E_1.
+        const E0_XP2: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+            "5587584953942720346394521850854996811085072645008289713528188263678797854010082261245989783581819541743212793459170751575221290569284769998489047747367475490709173366624847982277678051102917788971327746737637705280293823799565",
+            "7806030991972409844554197795295672553857669245579931115377669529965592031303657659584988303777753168445082946574720922147696871057854848691011717289867270517863765802550726985707451956732715283420571524960302873422763325867893");
+        const E0_YP2: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+            "6989341257039586087530037018069200807630270954572611906164747027021916728460418523452811180257037865965624283317696480393761576388794123253227846132014377001896891332430169280938672524808473488590396611581467677292687143133960",
+            "5900983247694412778386774600440294490572936602202555097308597740005445024096375677793755271848479986822474895767636256877558913486283586551338068879219782940682628866816733969905665935167851720984343241789354013259649085047193");
+        const E0_XQ2: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+            "3184096342187361672986889951331652953313418860988777004766431095599509848443373102224008076552404099031972367577137380742393146406531833287320568636218976654950510572486979916163979954023752947593339325048251253255080409886665",
+            "1810016771452366852722252436612780775775936576978993041106631996362229318148375428489828593081974938558679451990371602221382170450842889861801405830582257270379527916376998926122024112810612245109810870826539795211893140785184");
+        const E0_YQ2: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+            "8339514262084893770615987077641008971311353781475807854727665484088756599305633022577713761736682437707280223634333999436336290278420553498432272562711932497396615809177930328480811672457151058329855954481583006594513264345691",
+            "5780266268597403495939754269642858315024485987845645435836307706011303091027267720832380333845251067273640211665521281200333393824033802318349979777058215923203364912150070956033626976726498645685863607685580489544734404303970");
+        const E0_XP3: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+            "3863663225285916288287063349981778702786527406448692209516743258539690739127228105764625447971649352799907619756444386669138766223807490996560988657613142548201790693564472469719544621646549401511011000603499624661405360856433",
+            "383986078329705896129401054431880331921207360907723758675013195250816832030065757655958026623596017816467982122284767897947601413828426987673987528007098949024631583787093577014956355065483783978170882233961020366486434730876");
+        const E0_YP3: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+            "4167087076214390155755483813688886011481784245159643649490452011118284128677483305896766063249420802098329994517118104613850849873116669207748050705526985349788069558102951935108277005459735317148835681678947388755391020409527",
+            "4608346623248915950927888571758267454376109326628494894732827924107863170425097518071194541496280547577544461901686542759308731996709489968288967967782252240829804874778460792980970435000635867795595468454698350715213412301864");
+        const E0_XQ3: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+            "8220223464727975785000721199326197372902025427349067027522346205737284289184258147451450596182171387429579205577814762002951854850750270182813046583506271222855579022250123867586587436764902574810087891208695085624638781634703",
+            "10296966056143800587540087931038631121192952441066203822629420726243418797891391703103078492935474067772386260221301810996885742477387508763831644791835703465063374686910254235369050063694846127530206470181044084137964260742822");
+        const E0_YQ3: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+            "9839536309982090549303783804385761485247590234328287522441356570678990161499647483799651649817073323261110573546392732365324298844528874603346846239962850110719489723772931291316788290589074773552441373335744887880289808953619",
+            "5585980826201823996042283272535539834410504494983495336394664585823785893273543697903798072127087956911451846864995128535638854487795624182879535746767493763666154107322667532342150851692895438850926582918083770236244860893601");
+
+        let e0 = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let (xP, yP, xQ, yQ) = e0.generate_2_torsion_basis();
+        assert!(xP.vartime_eq(&E0_XP2) && yP.vartime_eq(&E0_YP2));
+        assert!(xQ.vartime_eq(&E0_XQ2) && yQ.vartime_eq(&E0_YQ2));
+        let (xP, yP, xQ, yQ) = e0.generate_3_torsion_basis();
+        assert!(xP.vartime_eq(&E0_XP3) && yP.vartime_eq(&E0_YP3));
+        assert!(xQ.vartime_eq(&E0_XQ3) && yQ.vartime_eq(&E0_YQ3));
+
+        // The codomain of the first 4-isogeny from E_0, which has a = -6.
+        let e1 = ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) };
+        let a = -(&ExtensionFieldElement::from_u64(6));
+        let zero = ExtensionFieldElement::zero();
+        let (xP, yP, xQ, yQ) = e1.generate_2_torsion_basis();
+        for &(x, y) in [(xP, yP), (xQ, yQ)].iter() {
+            assert!(y.square().vartime_eq(&montgomery_rhs(&a, &x)));
+            let xR = ProjectivePoint::from_affine(&x);
+            assert!(!xR.pow2k(&e1, E_A - 1).Z.vartime_eq(&zero));
+            assert!(xR.pow2k(&e1, E_A).Z.vartime_eq(&zero));
+        }
+        let (xP, yP, xQ, yQ) = e1.generate_3_torsion_basis();
+        for &(x, y) in [(xP, yP), (xQ, yQ)].iter() {
+            assert!(y.square().vartime_eq(&montgomery_rhs(&a, &x)));
+            let xR = ProjectivePoint::from_affine(&x);
+            assert!(!xR.pow3k(&e1, E_B - 1).Z.vartime_eq(&zero));
+            assert!(xR.pow3k(&e1, E_B).Z.vartime_eq(&zero));
+        }
+        let xP3 = ProjectivePoint::from_affine(&xP).pow3k(&e1, E_B - 1);
+        let xQ3 = ProjectivePoint::from_affine(&xQ).pow3k(&e1, E_B - 1);
+        assert!(!xP3.vartime_eq(&xQ3));
+    }
+
     #[test]
     fn scalar_mul_prime_field_and_coordinate_recovery_versus_sage_generated_torsion_points() {
         // x((11,...)) = 11
@@ -1276,6 +2299,22 @@ mod bench {
         b.iter(|| xP.triple(&cached_params));
     }
 
+    #[bench]
+    fn point_dbl_add(b: &mut Bencher) {
+        let xP = ProjectivePoint{ X: CURVE_A, Z: CURVE_C };
+        let cached_params = CURVE.cached_params();
+        let xP2 = xP.double(&cached_params);
+
+        b.iter(|| xP2.dbl_add(&xP, &xP, &cached_params));
+    }
+
+    #[bench]
+    fn point_pow3k_239(b: &mut Bencher) {
+        let xP = ProjectivePoint{ X: CURVE_A, Z: CURVE_C };
+
+        b.iter(|| xP.pow3k(&CURVE, 239));
+    }
+
     #[bench]
     fn scalar_mul_379bit_scalar(b: &mut Bencher) {
         let m_scalar_bytes: [u8; 48] = [84, 222, 146, 63, 85, 18, 173, 162, 167, 38, 10, 8, 143, 176, 93, 228, 247, 128, 50, 128, 205, 42, 15, 137, 119, 67, 43, 3, 61, 91, 237, 24, 235, 12, 53, 96, 186, 164, 232, 223, 197, 224, 64, 109, 137, 63, 246, 4];
@@ -1299,10 +2338,42 @@ mod bench {
         b.iter(|| ProjectivePoint::three_point_ladder(&THREE_POINT_LADDER_INPUTS[0], &THREE_POINT_LADDER_INPUTS[1], &THREE_POINT_LADDER_INPUTS[2], &CURVE, &m_scalar_bytes[..]));
     }
 
+    #[bench]
+    fn two_dimensional_ladder_379bit_scalars(b: &mut Bencher) {
+        let m_scalar_bytes: [u8; 48] = [84, 222, 146, 63, 85, 18, 173, 162, 167, 38, 10, 8, 143, 176, 93, 228, 247, 128, 50, 128, 205, 42, 15, 137, 119, 67, 43, 3, 61, 91, 237, 24, 235, 12, 53, 96, 186, 164, 232, 223, 197, 224, 64, 109, 137, 63, 246, 4];
+        let mut n_scalar_bytes = m_scalar_bytes;
+        n_scalar_bytes.reverse();
+
+        b.iter(|| ProjectivePoint::two_dimensional_ladder(&THREE_POINT_LADDER_INPUTS[0], &THREE_POINT_LADDER_INPUTS[1], &THREE_POINT_LADDER_INPUTS[2], &CURVE, &m_scalar_bytes[..], &n_scalar_bytes[..]));
+    }
+
     #[bench]
     fn right_to_left_ladder_379bit_scalar(b: &mut Bencher) {
         let m_scalar_bytes: [u8; 48] = [84, 222, 146, 63, 85, 18, 173, 162, 167, 38, 10, 8, 143, 176, 93, 228, 247, 128, 50, 128, 205, 42, 15, 137, 119, 67, 43, 3, 61, 91, 237, 24, 235, 12, 53, 96, 186, 164, 232, 223, 197, 224, 64, 109, 137, 63, 246, 4];
         
         b.iter(|| ProjectivePoint::right_to_left_ladder(&THREE_POINT_LADDER_INPUTS[0], &THREE_POINT_LADDER_INPUTS[1], &THREE_POINT_LADDER_INPUTS[2], &CURVE, &m_scalar_bytes[..]));
     }
+
+    #[bench]
+    fn fixed_basis_ladder_379bit_scalar(b: &mut Bencher) {
+        let m_scalar_bytes: [u8; 48] = [84, 222, 146, 63, 85, 18, 173, 162, 167, 38, 10, 8, 143, 176, 93, 228, 247, 128, 50, 128, 205, 42, 15, 137, 119, 67, 43, 3, 61, 91, 237, 24, 235, 12, 53, 96, 186, 164, 232, 223, 197, 224, 64, 109, 137, 63, 246, 4];
+
+        b.iter(|| ProjectivePoint::fixed_basis_ladder(&AFFINE_X_PB, &m_scalar_bytes[..]));
+    }
+
+    #[bench]
+    fn secret_point_fixed_base_379bit_scalar(b: &mut Bencher) {
+        let m_scalar_bytes: [u8; 48] = [84, 222, 146, 63, 85, 18, 173, 162, 167, 38, 10, 8, 143, 176, 93, 228, 247, 128, 50, 128, 205, 42, 15, 137, 119, 67, 43, 3, 61, 91, 237, 24, 235, 12, 53, 96, 186, 164, 232, 223, 197, 224, 64, 109, 137, 63, 246, 4];
+
+        b.iter(|| ProjectivePoint::secret_point(&AFFINE_X_PB, &AFFINE_Y_PB, &m_scalar_bytes[..]));
+    }
+
+    #[bench]
+    fn secret_point_ladder_379bit_scalar(b: &mut Bencher) {
+        let m_scalar_bytes: [u8; 48] = [84, 222, 146, 63, 85, 18, 173, 162, 167, 38, 10, 8, 143, 176, 93, 228, 247, 128, 50, 128, 205, 42, 15, 137, 119, 67, 43, 3, 61, 91, 237, 24, 235, 12, 53, 96, 186, 164, 232, 223, 197, 224, 64, 109, 137, 63, 246, 4];
+        // A base without comb tables.
+        let (affine_x, affine_y) = (&AFFINE_X_PB, -(&AFFINE_Y_PB));
+
+        b.iter(|| ProjectivePoint::secret_point(affine_x, &affine_y, &m_scalar_bytes[..]));
+    }
 }
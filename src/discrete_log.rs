@@ -0,0 +1,525 @@
+// This file is part of sidh-rs.
+// Copyright (c) 2017 Erkan Tairi
+// See LICENSE for licensing information.
+//
+// Author:
+// - Erkan Tairi <erkan.tairi@gmail.com>
+//
+
+//! This module contains discrete logarithms in the groups `mu_{2^372}` and
+//! `mu_{3^239}` of roots of unity in `F_{p^2}`, where the pairings of the
+//! `pairing` module take their values.
+//!
+//! Logarithms are computed with the windowed Pohlig-Hellman algorithm of
+//! `sage/sidh_pohlig_hellman.sage`. The exponent is split into nested windows
+//! (`372 = 4*84 + 6*6` with `84 = 4*21` and `21 = 4*5 + 1`, respectively
+//! `239 = 3*61 + 56`), and the powers of the inverted generator needed to strip
+//! each window are precomputed once per generator. All arithmetic takes place
+//! on `CyclotomicElement`s, using cyclotomic squarings and cubings.
+//!
+//! Each table offers a constant-time `discrete_log`, for logarithms which are
+//! secret, and a faster `vartime_discrete_log` which branches on the digits.
+//! Logarithms are returned as little-endian integers, in the same format as
+//! the SIDH secret scalars.
+
+use field::{CyclotomicElement, ExtensionFieldElement, mulby3};
+use constants::*;
+use sidh::SECRET_KEY_SIZE;
+
+use subtle::ConditionallySelectable;
+use subtle::ConstantTimeEq;
+
+/// How the digits of a logarithm are found and used: either by branching on
+/// their values, or in constant time.
+trait Mode {
+    /// Returns the index `d` with `v = roots[d]`.
+    fn digit(v: &CyclotomicElement, roots: &[CyclotomicElement]) -> u8;
+    /// Returns `u * t^d` for a digit `d` in `{0, 1}`.
+    fn mul_pow2(u: &CyclotomicElement, t: &CyclotomicElement, d: u8) -> CyclotomicElement;
+    /// Returns `u * t^d` for a digit `d` in `{0, 1, 2}`.
+    fn mul_pow3(u: &CyclotomicElement, t: &CyclotomicElement, d: u8) -> CyclotomicElement;
+}
+
+struct VarTime;
+
+impl Mode for VarTime {
+    fn digit(v: &CyclotomicElement, roots: &[CyclotomicElement]) -> u8 {
+        roots.iter().position(|r| v.vartime_eq(r)).unwrap_or(0) as u8
+    }
+    fn mul_pow2(u: &CyclotomicElement, t: &CyclotomicElement, d: u8) -> CyclotomicElement {
+        if d == 0 { *u } else { u * t }
+    }
+    fn mul_pow3(u: &CyclotomicElement, t: &CyclotomicElement, d: u8) -> CyclotomicElement {
+        match d {
+            0 => *u,
+            1 => u * t,
+            _ => u * &t.square(),
+        }
+    }
+}
+
+struct ConstantTime;
+
+impl Mode for ConstantTime {
+    fn digit(v: &CyclotomicElement, roots: &[CyclotomicElement]) -> u8 {
+        let mut d = 0u8;
+        for (i, r) in roots.iter().enumerate() {
+            d.conditional_assign(&(i as u8), v.ct_eq(r));
+        }
+        d
+    }
+    fn mul_pow2(u: &CyclotomicElement, t: &CyclotomicElement, d: u8) -> CyclotomicElement {
+        let mut s = CyclotomicElement::one();
+        s.conditional_assign(t, d.ct_eq(&1));
+        u * &s
+    }
+    fn mul_pow3(u: &CyclotomicElement, t: &CyclotomicElement, d: u8) -> CyclotomicElement {
+        let mut s = CyclotomicElement::one();
+        s.conditional_assign(t, d.ct_eq(&1));
+        s.conditional_assign(&t.square(), d.ct_eq(&2));
+        u * &s
+    }
+}
+
+// Compute x^alpha, where alpha is given by its base-2 digits, least significant first.
+fn exp2<M: Mode>(x: &CyclotomicElement, digits: &[u8]) -> CyclotomicElement {
+    let mut r = CyclotomicElement::one();
+    for d in digits.iter().rev() {
+        r = M::mul_pow2(&r.square(), x, *d);
+    }
+    r
+}
+
+// Compute x^alpha, where alpha is given by its base-3 digits, least significant first.
+fn exp3<M: Mode>(x: &CyclotomicElement, digits: &[u8]) -> CyclotomicElement {
+    let mut r = CyclotomicElement::one();
+    for d in digits.iter().rev() {
+        r = M::mul_pow3(&r.cube(), x, *d);
+    }
+    r
+}
+
+/// Precomputed tables for discrete logarithms to the base `g` in `mu_{2^372}`.
+///
+/// Every table entry is a power `g^(-2^(372-k))`, of order `2^k`.
+#[derive(Copy, Clone)]
+pub struct DiscreteLogTable2e {
+    // Of orders 2^372, 2^288, 2^204 and 2^120.
+    t_ori: [CyclotomicElement; 4],
+    // Of orders 2^84, 2^63 and 2^42.
+    lut_0: [CyclotomicElement; 3],
+    // Of orders 2^21, 2^16, 2^11 and 2^6.
+    lut_1: [CyclotomicElement; 4],
+    // Of orders 2^36, 2^30, 2^24, 2^18 and 2^12.
+    lut_3: [CyclotomicElement; 5],
+    // Of orders 2^6, 2^5, 2^4, 2^3 and 2^2.
+    lut: [CyclotomicElement; 5],
+    // The square roots of unity 1 and -1.
+    roots: [CyclotomicElement; 2],
+}
+
+impl DiscreteLogTable2e {
+    /// Build the tables for a generator `g` of `mu_{2^372}`, such as a reduced
+    /// pairing of a basis of the `2^372`-torsion.
+    pub fn new(g: &CyclotomicElement) -> DiscreteLogTable2e {
+        let mut tmp = g.inv();
+        let mut k = E_A;
+        // Returns the entry of order 2^order, which must be called in decreasing order.
+        let mut entry = |order: u32| {
+            tmp = tmp.pow2k(k - order);
+            k = order;
+            tmp
+        };
+
+        let t_ori = [entry(372), entry(288), entry(204), entry(120)];
+        let l84 = entry(84);
+        let l63 = entry(63);
+        let l42 = entry(42);
+        let l36 = entry(36);
+        let l30 = entry(30);
+        let l24 = entry(24);
+        let l21 = entry(21);
+        let l18 = entry(18);
+        let l16 = entry(16);
+        let l12 = entry(12);
+        let l11 = entry(11);
+        let l6 = entry(6);
+        let lut = [l6, entry(5), entry(4), entry(3), entry(2)];
+
+        DiscreteLogTable2e{
+            t_ori,
+            lut_0: [l84, l63, l42],
+            lut_1: [l21, l16, l11, l6],
+            lut_3: [l36, l30, l24, l18, l12],
+            lut,
+            roots: [CyclotomicElement::one(), CyclotomicElement::new(&-&ExtensionFieldElement::one())],
+        }
+    }
+    /// Returns `alpha` in `[0, 2^372)` with `h = g^alpha`. The output is
+    /// undefined if `h` is not a power of `g`.
+    ///
+    /// This function executes in constant time, so it may be used for secret
+    /// logarithms.
+    pub fn discrete_log(&self, h: &CyclotomicElement) -> [u8; SECRET_KEY_SIZE] {
+        self.log::<ConstantTime>(h)
+    }
+    /// Returns `alpha` in `[0, 2^372)` with `h = g^alpha`. The output is
+    /// undefined if `h` is not a power of `g`.
+    ///
+    /// This function does not execute in constant time.
+    pub fn vartime_discrete_log(&self, h: &CyclotomicElement) -> [u8; SECRET_KEY_SIZE] {
+        self.log::<VarTime>(h)
+    }
+
+    fn log<M: Mode>(&self, h: &CyclotomicElement) -> [u8; SECRET_KEY_SIZE] {
+        let mut digits = [0u8; E_A as usize];
+        self.phn84::<M>(h, &mut digits);
+
+        let mut alpha = [0u8; SECRET_KEY_SIZE];
+        for (i, d) in digits.iter().enumerate() {
+            alpha[i / 8] |= d << (i % 8);
+        }
+        alpha
+    }
+
+    // Find the a <= 6 digits of the logarithm of q, of order dividing 2^a.
+    fn phn1<M: Mode>(&self, q: &CyclotomicElement, a: usize, digits: &mut [u8]) {
+        let mut u = *q;
+        for (l, digit) in digits.iter_mut().enumerate().take(a - 1) {
+            let v = u.pow2k((a - 1 - l) as u32);
+            *digit = M::digit(&v, &self.roots);
+            u = M::mul_pow2(&u, &self.lut[6 - a + l], *digit);
+        }
+        digits[a - 1] = M::digit(&u, &self.roots);
+    }
+
+    // Find the 21 digits of the logarithm of q, of order dividing 2^21.
+    fn phn5<M: Mode>(&self, q: &CyclotomicElement, digits: &mut [u8]) {
+        let mut u = *q;
+        for i in 0..4 {
+            let v = u.pow2k((1 + 5 * (3 - i)) as u32);
+            let d = &mut digits[5 * i..5 * (i + 1)];
+            self.phn1::<M>(&v, 5, d);
+            u = &u * &exp2::<M>(&self.lut_1[i], d);
+        }
+        digits[20] = M::digit(&u, &self.roots);
+    }
+
+    // Find the 84 digits of the logarithm of q, of order dividing 2^84.
+    fn phn21<M: Mode>(&self, q: &CyclotomicElement, digits: &mut [u8]) {
+        let mut u = *q;
+        for i in 0..3 {
+            let v = u.pow2k((21 * (3 - i)) as u32);
+            let d = &mut digits[21 * i..21 * (i + 1)];
+            self.phn5::<M>(&v, d);
+            u = &u * &exp2::<M>(&self.lut_0[i], d);
+        }
+        self.phn5::<M>(&u, &mut digits[63..84]);
+    }
+
+    // Find the 372 digits of the logarithm of r.
+    fn phn84<M: Mode>(&self, r: &CyclotomicElement, digits: &mut [u8]) {
+        let mut t = *r;
+        for k in 0..4 {
+            let u = t.pow2k((36 + 84 * (3 - k)) as u32);
+            let d = &mut digits[84 * k..84 * (k + 1)];
+            self.phn21::<M>(&u, d);
+            t = &t * &exp2::<M>(&self.t_ori[k], d);
+        }
+        // Do the last part.
+        for i in 0..5 {
+            let u = t.pow2k((6 * (5 - i)) as u32);
+            let d = &mut digits[336 + 6 * i..336 + 6 * (i + 1)];
+            self.phn1::<M>(&u, 6, d);
+            t = &t * &exp2::<M>(&self.lut_3[i], d);
+        }
+        self.phn1::<M>(&t, 6, &mut digits[366..372]);
+    }
+}
+
+/// Precomputed tables for discrete logarithms to the base `g` in `mu_{3^239}`.
+///
+/// Every table entry is a power `g^(-3^(239-k))`, of order `3^k`.
+#[derive(Copy, Clone)]
+pub struct DiscreteLogTable3e {
+    // Of orders 3^239, 3^178 and 3^117.
+    t_ori: [CyclotomicElement; 3],
+    // Of orders 3^61, 3^46, 3^31 and 3^16.
+    lut_0: [CyclotomicElement; 4],
+    // Of orders 3^56, 3^41 and 3^26.
+    lut_0_l: [CyclotomicElement; 3],
+    // Of orders 3^15, 3^12, 3^9 and 3^6.
+    lut_1: [CyclotomicElement; 4],
+    // Of orders 3^11, 3^8 and 3^5.
+    lut_1_l: [CyclotomicElement; 3],
+    // Of orders 3^3, 3^2 and 3.
+    lut: [CyclotomicElement; 3],
+    // The cube roots of unity 1, w, w^2, where w = g^(3^238).
+    roots: [CyclotomicElement; 3],
+}
+
+impl DiscreteLogTable3e {
+    /// Build the tables for a generator `g` of `mu_{3^239}`, such as a reduced
+    /// pairing of a basis of the `3^239`-torsion.
+    pub fn new(g: &CyclotomicElement) -> DiscreteLogTable3e {
+        let mut tmp = g.inv();
+        let mut k = E_B;
+        // Returns the entry of order 3^order, which must be called in decreasing order.
+        let mut entry = |order: u32| {
+            tmp = tmp.pow3k(k - order);
+            k = order;
+            tmp
+        };
+
+        let t_ori = [entry(239), entry(178), entry(117)];
+        let l61 = entry(61);
+        let l56 = entry(56);
+        let l46 = entry(46);
+        let l41 = entry(41);
+        let l31 = entry(31);
+        let l26 = entry(26);
+        let l16 = entry(16);
+        let l15 = entry(15);
+        let l12 = entry(12);
+        let l11 = entry(11);
+        let l9 = entry(9);
+        let l8 = entry(8);
+        let l6 = entry(6);
+        let l5 = entry(5);
+        let lut = [entry(3), entry(2), entry(1)];
+        let w = lut[2].inv();
+
+        DiscreteLogTable3e{
+            t_ori,
+            lut_0: [l61, l46, l31, l16],
+            lut_0_l: [l56, l41, l26],
+            lut_1: [l15, l12, l9, l6],
+            lut_1_l: [l11, l8, l5],
+            lut,
+            roots: [CyclotomicElement::one(), w, lut[2]],
+        }
+    }
+    /// Returns `alpha` in `[0, 3^239)` with `h = g^alpha`. The output is
+    /// undefined if `h` is not a power of `g`.
+    ///
+    /// This function executes in constant time, so it may be used for secret
+    /// logarithms.
+    pub fn discrete_log(&self, h: &CyclotomicElement) -> [u8; SECRET_KEY_SIZE] {
+        self.log::<ConstantTime>(h)
+    }
+    /// Returns `alpha` in `[0, 3^239)` with `h = g^alpha`. The output is
+    /// undefined if `h` is not a power of `g`.
+    ///
+    /// This function does not execute in constant time.
+    pub fn vartime_discrete_log(&self, h: &CyclotomicElement) -> [u8; SECRET_KEY_SIZE] {
+        self.log::<VarTime>(h)
+    }
+
+    fn log<M: Mode>(&self, h: &CyclotomicElement) -> [u8; SECRET_KEY_SIZE] {
+        let mut digits = [0u8; E_B as usize];
+        self.phn61::<M>(h, &mut digits);
+
+        // Horner's rule, from the most significant digit: alpha = 3*alpha + d.
+        let mut alpha = [0u8; SECRET_KEY_SIZE];
+        for d in digits.iter().rev() {
+            mulby3(&mut alpha);
+            let mut carry = *d as u16;
+            for byte in alpha.iter_mut() {
+                let sum = (*byte as u16) + carry;
+                *byte = sum as u8;
+                carry = sum >> 8;
+            }
+        }
+        alpha
+    }
+
+    // Find the a <= 3 digits of the logarithm of q, of order dividing 3^a.
+    fn phn1<M: Mode>(&self, q: &CyclotomicElement, a: usize, digits: &mut [u8]) {
+        let mut u = *q;
+        for (l, digit) in digits.iter_mut().enumerate().take(a - 1) {
+            let v = u.pow3k((a - 1 - l) as u32);
+            *digit = M::digit(&v, &self.roots);
+            u = M::mul_pow3(&u, &self.lut[3 - a + l], *digit);
+        }
+        digits[a - 1] = M::digit(&u, &self.roots);
+    }
+
+    // Find the 15 digits of the logarithm of q, of order dividing 3^15.
+    fn phn3<M: Mode>(&self, q: &CyclotomicElement, digits: &mut [u8]) {
+        let mut u = *q;
+        for i in 0..4 {
+            let v = u.pow3k((3 * (4 - i)) as u32);
+            let d = &mut digits[3 * i..3 * (i + 1)];
+            self.phn1::<M>(&v, 3, d);
+            u = &u * &exp3::<M>(&self.lut_1[i], d);
+        }
+        self.phn1::<M>(&u, 3, &mut digits[12..15]);
+    }
+
+    // Find the 61 digits of the logarithm of q, of order dividing 3^61.
+    fn phn15<M: Mode>(&self, q: &CyclotomicElement, digits: &mut [u8]) {
+        let mut u = *q;
+        for i in 0..4 {
+            let v = u.pow3k((1 + 15 * (3 - i)) as u32);
+            let d = &mut digits[15 * i..15 * (i + 1)];
+            self.phn3::<M>(&v, d);
+            u = &u * &exp3::<M>(&self.lut_0[i], d);
+        }
+        // Do the last part.
+        digits[60] = M::digit(&u, &self.roots);
+    }
+
+    // Find the 56 digits of the logarithm of q, of order dividing 3^56.
+    fn phn15_l<M: Mode>(&self, q: &CyclotomicElement, digits: &mut [u8]) {
+        let mut u = *q;
+        for i in 0..3 {
+            let v = u.pow3k((11 + 15 * (2 - i)) as u32);
+            let d = &mut digits[15 * i..15 * (i + 1)];
+            self.phn3::<M>(&v, d);
+            u = &u * &exp3::<M>(&self.lut_0_l[i], d);
+        }
+        // Do the last part.
+        for i in 0..3 {
+            let v = u.pow3k((2 + 3 * (2 - i)) as u32);
+            let d = &mut digits[45 + 3 * i..45 + 3 * (i + 1)];
+            self.phn1::<M>(&v, 3, d);
+            u = &u * &exp3::<M>(&self.lut_1_l[i], d);
+        }
+        // And the final part.
+        self.phn1::<M>(&u, 2, &mut digits[54..56]);
+    }
+
+    // Find the 239 digits of the logarithm of r.
+    fn phn61<M: Mode>(&self, r: &CyclotomicElement, digits: &mut [u8]) {
+        let mut u = *r;
+        for k in 0..3 {
+            let v = u.pow3k((56 + 61 * (2 - k)) as u32);
+            let d = &mut digits[61 * k..61 * (k + 1)];
+            self.phn15::<M>(&v, d);
+            u = &u * &exp3::<M>(&self.t_ori[k], d);
+        }
+        self.phn15_l::<M>(&u, &mut digits[183..239]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use curve::ProjectiveCurveParameters;
+    use pairing::{weil_pairing_2e, weil_pairing_3e};
+    use rand::{RngCore, thread_rng};
+
+    // The Weil pairing of a basis of E_0[2^372], resp. E_0[3^239].
+    fn generator_2e() -> CyclotomicElement {
+        let curve = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let (xP, yP, xQ, yQ) = curve.generate_2_torsion_basis();
+        CyclotomicElement::new(&weil_pairing_2e(&curve, &xP, &yP, &xQ, &yQ))
+    }
+
+    fn generator_3e() -> CyclotomicElement {
+        let curve = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let (xP, yP, xQ, yQ) = curve.generate_3_torsion_basis();
+        CyclotomicElement::new(&weil_pairing_3e(&curve, &xP, &yP, &xQ, &yQ))
+    }
+
+    fn check(g: &CyclotomicElement, alpha: &[u8; SECRET_KEY_SIZE],
+             log: &dyn Fn(&CyclotomicElement) -> [u8; SECRET_KEY_SIZE]) {
+        let h = g.exp(&alpha[..]);
+        assert_eq!(&log(&h)[..], &alpha[..]);
+    }
+
+    #[test]
+    fn discrete_log_2e() {
+        let g = generator_2e();
+        let table = DiscreteLogTable2e::new(&g);
+        let mut rng = thread_rng();
+
+        let mut max = [0xffu8; SECRET_KEY_SIZE];
+        max[47] = 0;
+        max[46] = 15; // = 2^372 - 1
+        let mut small = [0u8; SECRET_KEY_SIZE];
+        small[0] = 1;
+        let mut exponents = vec![[0u8; SECRET_KEY_SIZE], small, max];
+        for _ in 0..4 {
+            let mut alpha = [0u8; SECRET_KEY_SIZE];
+            rng.fill_bytes(&mut alpha[..]);
+            alpha[47] = 0;
+            alpha[46] &= 15;
+            exponents.push(alpha);
+        }
+
+        for alpha in exponents.iter() {
+            check(&g, alpha, &|h| table.discrete_log(h));
+            check(&g, alpha, &|h| table.vartime_discrete_log(h));
+        }
+    }
+
+    #[test]
+    fn discrete_log_3e() {
+        let g = generator_3e();
+        let table = DiscreteLogTable3e::new(&g);
+        let mut rng = thread_rng();
+
+        let mut max = [0u8; SECRET_KEY_SIZE];
+        max[0] = 1;
+        for _ in 0..E_B {
+            mulby3(&mut max);
+        }
+        max[0] -= 1; // = 3^239 - 1, as 3^239 is odd
+        let mut small = [0u8; SECRET_KEY_SIZE];
+        small[0] = 2;
+        let mut exponents = vec![[0u8; SECRET_KEY_SIZE], small, max];
+        for _ in 0..4 {
+            let mut alpha = [0u8; SECRET_KEY_SIZE];
+            rng.fill_bytes(&mut alpha[..]);
+            alpha[47] &= 3; // < 2^378 < 3^239
+            exponents.push(alpha);
+        }
+
+        for alpha in exponents.iter() {
+            check(&g, alpha, &|h| table.discrete_log(h));
+            check(&g, alpha, &|h| table.vartime_discrete_log(h));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "bench"))]
+mod bench {
+    use super::*;
+    use test::Bencher;
+    use curve::ProjectiveCurveParameters;
+    use pairing::{weil_pairing_2e, weil_pairing_3e};
+
+    fn tables() -> (CyclotomicElement, DiscreteLogTable2e, CyclotomicElement, DiscreteLogTable3e) {
+        let curve = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let (xP, yP, xQ, yQ) = curve.generate_2_torsion_basis();
+        let g2 = CyclotomicElement::new(&weil_pairing_2e(&curve, &xP, &yP, &xQ, &yQ));
+        let (xP, yP, xQ, yQ) = curve.generate_3_torsion_basis();
+        let g3 = CyclotomicElement::new(&weil_pairing_3e(&curve, &xP, &yP, &xQ, &yQ));
+        (g2, DiscreteLogTable2e::new(&g2), g3, DiscreteLogTable3e::new(&g3))
+    }
+
+    #[bench]
+    fn discrete_log_2e(b: &mut Bencher) {
+        let (g2, table, _, _) = tables();
+        b.iter(|| table.discrete_log(&g2));
+    }
+
+    #[bench]
+    fn vartime_discrete_log_2e(b: &mut Bencher) {
+        let (g2, table, _, _) = tables();
+        b.iter(|| table.vartime_discrete_log(&g2));
+    }
+
+    #[bench]
+    fn discrete_log_3e(b: &mut Bencher) {
+        let (_, _, g3, table) = tables();
+        b.iter(|| table.discrete_log(&g3));
+    }
+
+    #[bench]
+    fn vartime_discrete_log_3e(b: &mut Bencher) {
+        let (_, _, g3, table) = tables();
+        b.iter(|| table.vartime_discrete_log(&g3));
+    }
+}
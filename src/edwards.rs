@@ -0,0 +1,377 @@
+// This file is part of sidh-rs.
+// Copyright (c) 2017 Erkan Tairi
+// See LICENSE for licensing information.
+//
+// Author:
+// - Erkan Tairi <erkan.tairi@gmail.com>
+//
+
+//! This module contains twisted Edwards curves `ax^2 + y^2 = 1 + dx^2y^2` over
+//! `F_{p^2}`, and the birational maps between them and the Montgomery curves
+//! `E_(A:C) : Cy^2 = Cx^3 + Ax^2 + Cx` of the `curve` module.
+//!
+//! The Montgomery curve `E_(A:C)` is birational to the twisted Edwards curve with
+//! `a = (A + 2C)/C` and `d = (A - 2C)/C`, via `(u, v) -> (u/v, (u - 1)/(u + 1))`.
+//! Points are in extended coordinates `(X:Y:Z:T)` with `x = X/Z`, `y = Y/Z` and
+//! `T = XY/Z`, and use the formulas of Hisil, Wong, Carter and Dawson, "Twisted
+//! Edwards Curves Revisited", and of Chuengsatiansup, "Tripling on twisted
+//! Edwards curves".
+//!
+//! The Montgomery x-coordinate `(X:Z)` of a point corresponds to its Edwards
+//! y-coordinate `(X - Z : X + Z)`, which is what the Edwards isogeny formulas
+//! work with.
+
+use field::ExtensionFieldElement;
+use curve::{MontgomeryPoint, ProjectiveCurveParameters, ProjectivePoint};
+
+use core::fmt::Debug;
+use core::ops::Neg;
+use subtle::ConditionallySelectable;
+use subtle::ConstantTimeEq;
+use subtle::Choice;
+
+/// A twisted Edwards curve `ax^2 + y^2 = 1 + dx^2y^2`.
+#[derive(Copy, Clone, PartialEq)]
+pub struct EdwardsCurveParameters {
+    pub a: ExtensionFieldElement,
+    pub d: ExtensionFieldElement,
+}
+
+impl Debug for EdwardsCurveParameters {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "EdwardsCurveParameters(a: {:?}\nd: {:?})", &self.a, &self.d)
+    }
+}
+
+impl EdwardsCurveParameters {
+    /// Construct the twisted Edwards curve birational to `E_(A:C)`, with
+    /// `a = (A + 2C)/C` and `d = (A - 2C)/C`.
+    pub fn from_montgomery(curve: &ProjectiveCurveParameters) -> EdwardsCurveParameters {
+        let c_inv = curve.C.inv();
+        let C2 = &curve.C + &curve.C;
+        let a = &(&curve.A + &C2) * &c_inv;    // = (A + 2C)/C
+        let d = &(&curve.A - &C2) * &c_inv;    // = (A - 2C)/C
+        EdwardsCurveParameters{ a, d }
+    }
+    /// Returns the Montgomery curve `E_(A:C)` with `(A:C) = (2(a + d) : a - d)`.
+    ///
+    /// The curve is birational to `Bv^2 = u^3 + (A/C)u^2 + u`, with `B = 4/(a - d)`.
+    /// If `B` is not a square, `E_(A:C)` is its quadratic twist, which has the
+    /// same Kummer line, so that `ProjectivePoint` arithmetic is unaffected.
+    pub fn to_montgomery(&self) -> ProjectiveCurveParameters {
+        let A = &self.a + &self.d;
+        ProjectiveCurveParameters{ A: &A + &A, C: &self.a - &self.d }
+    }
+}
+
+/// A point `(X:Y:Z:T)` on a twisted Edwards curve, in extended coordinates.
+/// The identity is `(0:1:1:0)`.
+#[derive(Copy, Clone, PartialEq)]
+pub struct EdwardsPoint {
+    pub X: ExtensionFieldElement,
+    pub Y: ExtensionFieldElement,
+    pub Z: ExtensionFieldElement,
+    pub T: ExtensionFieldElement,
+}
+
+impl ConditionallySelectable for EdwardsPoint {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        EdwardsPoint{
+            X: ExtensionFieldElement::conditional_select(&a.X, &b.X, choice),
+            Y: ExtensionFieldElement::conditional_select(&a.Y, &b.Y, choice),
+            Z: ExtensionFieldElement::conditional_select(&a.Z, &b.Z, choice),
+            T: ExtensionFieldElement::conditional_select(&a.T, &b.T, choice)
+        }
+    }
+
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        ExtensionFieldElement::conditional_swap(&mut a.X, &mut b.X, choice);
+        ExtensionFieldElement::conditional_swap(&mut a.Y, &mut b.Y, choice);
+        ExtensionFieldElement::conditional_swap(&mut a.Z, &mut b.Z, choice);
+        ExtensionFieldElement::conditional_swap(&mut a.T, &mut b.T, choice);
+    }
+}
+
+impl Debug for EdwardsPoint {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "EdwardsPoint(X: {:?}\nY: {:?}\nZ: {:?}\nT: {:?})", &self.X, &self.Y, &self.Z, &self.T)
+    }
+}
+
+impl Neg for &EdwardsPoint {
+    type Output = EdwardsPoint;
+    fn neg(self) -> EdwardsPoint {
+        EdwardsPoint{ X: -&self.X, Y: self.Y, Z: self.Z, T: -&self.T }
+    }
+}
+
+impl EdwardsPoint {
+    /// Construct the identity `(0:1:1:0)`.
+    pub fn identity() -> EdwardsPoint {
+        let zero = ExtensionFieldElement::zero();
+        let one = ExtensionFieldElement::one();
+        EdwardsPoint{ X: zero, Y: one, Z: one, T: zero }
+    }
+    /// Construct the point `(x:y:1:xy)`. The caller is responsible for it lying on the curve.
+    pub fn from_affine(x: &ExtensionFieldElement, y: &ExtensionFieldElement) -> EdwardsPoint {
+        EdwardsPoint{ X: *x, Y: *y, Z: ExtensionFieldElement::one(), T: x * y }
+    }
+    /// Returns the affine coordinates `(x, y)`.
+    pub fn to_affine(&self) -> (ExtensionFieldElement, ExtensionFieldElement) {
+        let z_inv = self.Z.inv();
+        (&self.X * &z_inv, &self.Y * &z_inv)
+    }
+    /// Map a point on `E_(A:C)` to the curve `EdwardsCurveParameters::from_montgomery`.
+    ///
+    /// The map `(u, v) -> (u/v, (u - 1)/(u + 1))` is undefined at the points with
+    /// `u = -1`, which correspond to points at infinity of the Edwards curve. The
+    /// identity and `(0, 0)` are sent to `(0, 1)` and `(0, -1)`, respectively.
+    pub fn from_montgomery(P: &MontgomeryPoint) -> EdwardsPoint {
+        // (x, y) = (U/V, (U - W)/(U + W)) = (U(U + W) : V(U - W) : V(U + W) : U(U - W)).
+        let (U, V, W) = (&P.X, &P.Y, &P.Z);
+        let UpW = U + W;
+        let UmW = U - W;
+        let mut Q = EdwardsPoint{ X: U * &UpW, Y: V * &UmW, Z: V * &UpW, T: U * &UmW };
+
+        let zero = ExtensionFieldElement::zero();
+        let one = ExtensionFieldElement::one();
+        let order_two = EdwardsPoint{ X: zero, Y: -&one, Z: one, T: zero };
+        Q.conditional_assign(&order_two, U.ct_eq(&zero));
+        Q.conditional_assign(&EdwardsPoint::identity(), W.ct_eq(&zero));
+        Q
+    }
+    /// Map a point to the Montgomery curve `E_(A:C)` it was constructed from, by
+    /// `(x, y) -> ((1 + y)/(1 - y), (1 + y)/((1 - y)x))`.
+    ///
+    /// This is undefined at the points with `x = 0` other than `(0, 1)` and `(0, -1)`,
+    /// which are sent to the identity and `(0, 0)`, respectively.
+    pub fn to_montgomery(&self) -> MontgomeryPoint {
+        // (U : V : W) = ((Z + Y)X : (Z + Y)Z : (Z - Y)X).
+        let ZpY = &self.Z + &self.Y;
+        let ZmY = &self.Z - &self.Y;
+        let mut P = MontgomeryPoint{ X: &ZpY * &self.X, Y: &ZpY * &self.Z, Z: &ZmY * &self.X };
+
+        // The identity gives (0 : 2Z^2 : 0) as it should, but (0, -1) gives (0 : 0 : 0).
+        let zero = ExtensionFieldElement::zero();
+        let order_two = MontgomeryPoint{ X: zero, Y: zero, Z: ExtensionFieldElement::one() };
+        P.conditional_assign(&order_two, ZpY.ct_eq(&zero));
+        P
+    }
+    /// Lift `x(P)` on `E_(A:C)` to one of the points `±P` on the Edwards curve
+    /// `edwards = EdwardsCurveParameters::from_montgomery(E_(A:C))`.
+    ///
+    /// Computes a square root, so takes variable time. If `P` is not defined over
+    /// `F_{p^2}`, i.e. it lies on the quadratic twist, the output is undefined.
+    pub fn from_projective_point(edwards: &EdwardsCurveParameters, xP: &ProjectivePoint) -> EdwardsPoint {
+        // y = (X - Z)/(X + Z), and x^2 = (1 - y^2)/(a - dy^2) = ((X + Z)^2 - (X - Z)^2)/(a(X + Z)^2 - d(X - Z)^2).
+        let Y = &xP.X - &xP.Z;
+        let Z = &xP.X + &xP.Z;
+        let Y2 = Y.square();
+        let Z2 = Z.square();
+        let x2 = &(&Z2 - &Y2) * &(&(&edwards.a * &Z2) - &(&edwards.d * &Y2)).inv();
+        let x = x2.sqrt();
+        let y = &Y * &Z.inv();
+        EdwardsPoint::from_affine(&x, &y)
+    }
+    /// Returns the Montgomery x-coordinate `(Z + Y : Z - Y)` of the point.
+    pub fn to_projective_point(&self) -> ProjectivePoint {
+        ProjectivePoint{ X: &self.Z + &self.Y, Z: &self.Z - &self.Y }
+    }
+    /// Returns true if both sides are equal. Takes variable time.
+    pub fn vartime_eq(&self, _rhs: &EdwardsPoint) -> bool {
+        (&self.X * &_rhs.Z).vartime_eq(&(&self.Z * &_rhs.X)) &&
+        (&self.Y * &_rhs.Z).vartime_eq(&(&self.Z * &_rhs.Y))
+    }
+    /// Returns true if the point satisfies `(aX^2 + Y^2)Z^2 = Z^4 + dX^2Y^2` and
+    /// `XY = ZT`. Takes variable time.
+    pub fn is_on_curve(&self, curve: &EdwardsCurveParameters) -> bool {
+        let X2 = self.X.square();
+        let Y2 = self.Y.square();
+        let Z2 = self.Z.square();
+        let lhs = &(&(&curve.a * &X2) + &Y2) * &Z2;
+        let rhs = &Z2.square() + &(&curve.d * &(&X2 * &Y2));
+        lhs.vartime_eq(&rhs) && (&self.X * &self.Y).vartime_eq(&(&self.Z * &self.T))
+    }
+    /// Compute `P + Q` with the unified addition formula, which may also be used
+    /// for doubling.
+    ///
+    /// The formula is complete if `a` is a square and `d` is not. Otherwise it
+    /// fails for some pairs of points, which on the curves birational to
+    /// supersingular Montgomery curves include `P` and `Q = P + (0, -1)`.
+    // Cost: 9M+1*a+1*d+7a+1s, following add-2008-hwcd.
+    pub fn add(&self, other: &EdwardsPoint, curve: &EdwardsCurveParameters) -> EdwardsPoint {
+        let A = &self.X * &other.X;                            // = X1*X2
+        let B = &self.Y * &other.Y;                            // = Y1*Y2
+        let C = &(&curve.d * &self.T) * &other.T;              // = d*T1*T2
+        let D = &self.Z * &other.Z;                            // = Z1*Z2
+        let mut E = &(&self.X + &self.Y) * &(&other.X + &other.Y);
+        E = &(&E - &A) - &B;                                   // = X1*Y2 + Y1*X2
+        let F = &D - &C;
+        let G = &D + &C;
+        let H = &B - &(&curve.a * &A);                         // = Y1*Y2 - a*X1*X2
+        EdwardsPoint{ X: &E * &F, Y: &G * &H, Z: &F * &G, T: &E * &H }
+    }
+    /// Compute `[2]P`.
+    // Cost: 4M+4S+1*a+5a+1s, following dbl-2008-hwcd.
+    pub fn double(&self, curve: &EdwardsCurveParameters) -> EdwardsPoint {
+        let A = self.X.square();                               // = X^2
+        let B = self.Y.square();                               // = Y^2
+        let mut C = self.Z.square();
+        C = &C + &C;                                           // = 2Z^2
+        let D = &curve.a * &A;                                 // = aX^2
+        let mut E = (&self.X + &self.Y).square();
+        E = &(&E - &A) - &B;                                   // = 2XY
+        let G = &D + &B;                                       // = aX^2 + Y^2
+        let F = &G - &C;                                       // = aX^2 + Y^2 - 2Z^2
+        let H = &D - &B;                                       // = aX^2 - Y^2
+        EdwardsPoint{ X: &E * &F, Y: &G * &H, Z: &F * &G, T: &E * &H }
+    }
+    /// Compute `[3]P`.
+    // Cost: 12M+4S+1*a+9a, following tpl-2015-c and converting back to extended
+    // coordinates.
+    pub fn triple(&self, curve: &EdwardsCurveParameters) -> EdwardsPoint {
+        let YY = self.Y.square();                              // = Y^2
+        let aXX = &curve.a * &self.X.square();                 // = aX^2
+        let Ap = &YY + &aXX;                                   // = aX^2 + Y^2
+        let mut B = self.Z.square();
+        B = &(&B + &B) - &Ap;
+        B = &B + &B;                                           // = 2(2Z^2 - aX^2 - Y^2)
+        let xB = &aXX * &B;
+        let yB = &YY * &B;
+        let AA = &Ap * &(&YY - &aXX);                          // = (Y^2 + aX^2)(Y^2 - aX^2)
+        let F = &AA - &yB;
+        let G = &AA + &xB;
+        let X3 = &(&self.X * &(&yB + &AA)) * &F;
+        let Y3 = &(&self.Y * &(&xB - &AA)) * &G;
+        let Z3 = &(&self.Z * &F) * &G;
+        EdwardsPoint{ X: &X3 * &Z3, Y: &Y3 * &Z3, Z: Z3.square(), T: &X3 * &Y3 }
+    }
+    /// Compute `[2^k]P` by repeated doubling.
+    pub fn pow2k(&self, curve: &EdwardsCurveParameters, k: u32) -> EdwardsPoint {
+        let mut Q = *self;
+        for _ in 0..k {
+            Q = Q.double(curve);
+        }
+        Q
+    }
+    /// Compute `[3^k]P` by repeated tripling.
+    pub fn pow3k(&self, curve: &EdwardsCurveParameters, k: u32) -> EdwardsPoint {
+        let mut Q = *self;
+        for _ in 0..k {
+            Q = Q.triple(curve);
+        }
+        Q
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::thread_rng;
+
+    // A basis of E_0[2^372] and one of E_0[3^239], as Montgomery points.
+    fn e0_bases() -> (ProjectiveCurveParameters, [MontgomeryPoint; 4]) {
+        let e0 = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let (xP2, yP2, xQ2, yQ2) = e0.generate_2_torsion_basis();
+        let (xP3, yP3, xQ3, yQ3) = e0.generate_3_torsion_basis();
+        (e0, [MontgomeryPoint::from_affine(&xP2, &yP2), MontgomeryPoint::from_affine(&xQ2, &yQ2),
+              MontgomeryPoint::from_affine(&xP3, &yP3), MontgomeryPoint::from_affine(&xQ3, &yQ3)])
+    }
+
+    #[test]
+    fn birational_maps_round_trip() {
+        let (e0, points) = e0_bases();
+        let ed = EdwardsCurveParameters::from_montgomery(&e0);
+        let e0_again = ed.to_montgomery();
+        assert!(e0_again.j_invariant().vartime_eq(&e0.j_invariant()));
+
+        for P in points.iter() {
+            let Q = EdwardsPoint::from_montgomery(P);
+            assert!(Q.is_on_curve(&ed));
+            assert!(Q.to_montgomery().vartime_eq(P));
+            assert!(Q.to_projective_point().vartime_eq(&P.to_projective_point()));
+
+            let R = EdwardsPoint::from_projective_point(&ed, &P.to_projective_point());
+            assert!(R.is_on_curve(&ed));
+            assert!(R.vartime_eq(&Q) || R.vartime_eq(&-&Q));
+        }
+
+        let O = EdwardsPoint::from_montgomery(&MontgomeryPoint::identity());
+        assert!(O.vartime_eq(&EdwardsPoint::identity()));
+        assert!(O.to_montgomery().is_identity());
+        let T = MontgomeryPoint::from_affine(&ExtensionFieldElement::zero(), &ExtensionFieldElement::zero());
+        assert!(EdwardsPoint::from_montgomery(&T).to_montgomery().vartime_eq(&T));
+    }
+
+    #[test]
+    fn addition_versus_montgomery() {
+        let (e0, points) = e0_bases();
+        let ed = EdwardsCurveParameters::from_montgomery(&e0);
+        for P in points.iter() {
+            for Q in points.iter() {
+                let R = EdwardsPoint::from_montgomery(P).add(&EdwardsPoint::from_montgomery(Q), &ed);
+                assert!(R.is_on_curve(&ed));
+                assert!(R.to_montgomery().vartime_eq(&P.add(Q, &e0)));
+            }
+            let P = EdwardsPoint::from_montgomery(P);
+            assert!(P.add(&EdwardsPoint::identity(), &ed).vartime_eq(&P));
+            assert!(P.add(&-&P, &ed).vartime_eq(&EdwardsPoint::identity()));
+            assert!(P.add(&P, &ed).vartime_eq(&P.double(&ed)));
+            assert!(P.double(&ed).add(&P, &ed).vartime_eq(&P.triple(&ed)));
+        }
+    }
+
+    #[test]
+    fn pow2k_and_pow3k_commute_with_montgomery() {
+        let mut rng = thread_rng();
+        let (e0, _) = e0_bases();
+        // E_(12:-2) is 4-isogenous to E_0.
+        let e1 = ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) };
+        for curve in [e0, e1].iter() {
+            let ed = EdwardsCurveParameters::from_montgomery(curve);
+            for _ in 0..4 {
+                let xP = ProjectivePoint::random(curve, &mut rng);
+                let P = EdwardsPoint::from_projective_point(&ed, &xP);
+                for &k in [1, 2, 5, 16].iter() {
+                    let Q = P.pow2k(&ed, k);
+                    assert!(Q.is_on_curve(&ed));
+                    assert!(Q.to_projective_point().vartime_eq(&xP.pow2k(curve, k)));
+                    let Q = P.pow3k(&ed, k);
+                    assert!(Q.is_on_curve(&ed));
+                    assert!(Q.to_projective_point().vartime_eq(&xP.pow3k(curve, k)));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "bench"))]
+mod bench {
+    use super::*;
+    use test::Bencher;
+
+    fn e0_point() -> (EdwardsCurveParameters, EdwardsPoint) {
+        let e0 = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let (xP, yP, _, _) = e0.generate_2_torsion_basis();
+        (EdwardsCurveParameters::from_montgomery(&e0), EdwardsPoint::from_montgomery(&MontgomeryPoint::from_affine(&xP, &yP)))
+    }
+
+    #[bench]
+    fn edwards_addition(b: &mut Bencher) {
+        let (ed, P) = e0_point();
+        let Q = P.double(&ed);
+        b.iter(|| P.add(&Q, &ed));
+    }
+
+    #[bench]
+    fn edwards_double(b: &mut Bencher) {
+        let (ed, P) = e0_point();
+        b.iter(|| P.double(&ed));
+    }
+
+    #[bench]
+    fn edwards_triple(b: &mut Bencher) {
+        let (ed, P) = e0_point();
+        b.iter(|| P.triple(&ed));
+    }
+}
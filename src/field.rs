@@ -6,10 +6,15 @@
 // - Erkan Tairi <erkan.tairi@gmail.com>
 //
 
-//! This module contains finite field arithmetic functionality for SIDH, 
-//! which is not part of the public API.
+//! This module contains finite field arithmetic functionality for SIDH.
+//!
+//! Field elements are stored in Montgomery form. Conversions to and from
+//! canonical integers in `[0, p)` are provided by `to_le_bytes`, `from_le_bytes`,
+//! `to_u64_limbs`, `from_u64_limbs`, and the `Display`, `LowerHex` and `FromStr`
+//! implementations.
 
-use core::fmt::Debug;
+use core::fmt::{Debug, Display, Formatter, LowerHex};
+use core::str::FromStr;
 
 use core::cmp::{Eq, PartialEq};
 
@@ -22,6 +27,8 @@ use subtle::ConditionallySelectable;
 use subtle::ConstantTimeEq;
 use subtle::Choice;
 
+use rand_core::{CryptoRng, RngCore};
+
 #[cfg(test)]
 use quickcheck::{Arbitrary, Gen, QuickCheck};
 
@@ -64,7 +71,7 @@ impl<'b> AddAssign<&'b ExtensionFieldElement> for ExtensionFieldElement {
     }
 }
 
-impl<'a, 'b> Add<&'b ExtensionFieldElement> for &'a ExtensionFieldElement {
+impl<'b> Add<&'b ExtensionFieldElement> for &ExtensionFieldElement {
     type Output = ExtensionFieldElement;
     fn add(self, _rhs: &'b ExtensionFieldElement) -> ExtensionFieldElement {
         let a = &self.A + &_rhs.A;
@@ -85,7 +92,7 @@ impl <'b> SubAssign<&'b ExtensionFieldElement> for ExtensionFieldElement {
     }
 }
 
-impl<'a, 'b> Sub<&'b ExtensionFieldElement> for &'a ExtensionFieldElement {
+impl<'b> Sub<&'b ExtensionFieldElement> for &ExtensionFieldElement {
     type Output = ExtensionFieldElement;
     fn sub(self, _rhs: &'b ExtensionFieldElement) -> ExtensionFieldElement {
         let a = &self.A - &_rhs.A;
@@ -106,51 +113,32 @@ impl<'b> MulAssign<&'b ExtensionFieldElement> for ExtensionFieldElement {
     }
 }
 
-impl<'a, 'b> Mul<&'b ExtensionFieldElement> for &'a ExtensionFieldElement {
+impl<'b> Mul<&'b ExtensionFieldElement> for &ExtensionFieldElement {
     type Output = ExtensionFieldElement;
     fn mul(self, _rhs: &'b ExtensionFieldElement) -> ExtensionFieldElement {
-        // Alias self, _rhs for more readable formulas.
-        let a = &self.A;
-        let b = &self.B;
-        let c = &_rhs.A;
-        let d = &_rhs.B;
-
-        // We want to compute
-        //
-        // (a + bi)*(c + di) = (a*c - b*d) + (a*d + b*c)i
-        //
-        // Use Karatsuba's trick: note that
-        //
-        // (b - a)*(c - d) = (b*c + a*d) - a*c - b*d
-        //
-        // so (a*d + b*c) = (b-a)*(c-d) + a*c + b*d.
-        //
-        let ac = a * c;                               // = a*c*R*R
-        let bd = b * d;                               // = b*d*R*R
-        let b_minus_a = b - a;                        // = (b-a)*R
-        let c_minus_d = c - d;                        // = (c-d)*R
-        
-        let mut ad_plus_bc = &b_minus_a * &c_minus_d; // = (b-a)*(c-d)*R*R
-        ad_plus_bc += &ac;                            // = ((b-a)*(c-d) + a*c)*R*R
-        ad_plus_bc += &bd;                            // = ((b-a)*(c-d) + a*c + b*d)*R*R
-        let _b = ad_plus_bc.reduce();                 // = (a*d + b*c)*R mod p
+        self.mul_unreduced(_rhs).reduce()
+    }
+}
 
-        let ac_minus_bd = &ac - &bd;                  // = (a*c - b*d)*R*R
-        let _a = ac_minus_bd.reduce();                // = (a*c - b*d)*R mod p  
+impl<'b> Mul<&'b PrimeFieldElement> for &ExtensionFieldElement {
+    type Output = ExtensionFieldElement;
+    fn mul(self, _rhs: &'b PrimeFieldElement) -> ExtensionFieldElement {
+        // (a + b*i)*c = a*c + (b*c)*i, for c in F_p.
+        let a = (&self.A * &_rhs.A).reduce();
+        let b = (&self.B * &_rhs.A).reduce();
 
         ExtensionFieldElement{
-            A: _a,
-            B: _b
+            A: a,
+            B: b
         }
     }
 }
 
-impl <'a> Neg for &'a ExtensionFieldElement {
+impl Neg for &ExtensionFieldElement {
     type Output = ExtensionFieldElement;
     fn neg(self) -> ExtensionFieldElement {
         let zero = ExtensionFieldElement::zero();
-        let result = &zero - (self as &ExtensionFieldElement);
-        result
+        &zero - (self as &ExtensionFieldElement)
     }
 }
 
@@ -168,6 +156,12 @@ impl ConditionallySelectable for ExtensionFieldElement {
     }
 }
 
+impl ConstantTimeEq for ExtensionFieldElement {
+    fn ct_eq(&self, other: &ExtensionFieldElement) -> Choice {
+        self.A.ct_eq(&other.A) & self.B.ct_eq(&other.B)
+    }
+}
+
 impl Debug for ExtensionFieldElement {
     fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
         write!(f, "ExtensionFieldElement(A: {:?}\nB: {:?})", &self.A.0[..], &self.B.0[..])
@@ -250,8 +244,8 @@ impl ExtensionFieldElement {
         let _a = ac.reduce();
 
         let mut minus_b = Fp751Element::zero();
-        minus_b = &minus_b - &b;
-        let minus_bc = &minus_b * &c;
+        minus_b = &minus_b - b;
+        let minus_bc = &minus_b * c;
         let _b = minus_bc.reduce();
 
         ExtensionFieldElement{
@@ -278,34 +272,69 @@ impl ExtensionFieldElement {
     }
     /// Set the output to `x^2`.
     pub fn square(&self) -> ExtensionFieldElement {
+        self.square_unreduced().reduce()
+    }
+    /// Compute `x*y`, without the final Montgomery reduction of the coordinates.
+    pub fn mul_unreduced(&self, _rhs: &ExtensionFieldElement) -> ExtensionFieldElementX2 {
+        // Alias self, _rhs for more readable formulas.
+        let a = &self.A;
+        let b = &self.B;
+        let c = &_rhs.A;
+        let d = &_rhs.B;
+
+        // We want to compute
+        //
+        // (a + bi)*(c + di) = (a*c - b*d) + (a*d + b*c)i
+        //
+        // Use Karatsuba's trick: note that
+        //
+        // (b - a)*(c - d) = (b*c + a*d) - a*c - b*d
+        //
+        // so (a*d + b*c) = (b-a)*(c-d) + a*c + b*d.
+        //
+        let ac = a * c;                               // = a*c*R*R
+        let bd = b * d;                               // = b*d*R*R
+        let b_minus_a = b - a;                        // = (b-a)*R
+        let c_minus_d = c - d;                        // = (c-d)*R
+        
+        let mut ad_plus_bc = &b_minus_a * &c_minus_d; // = (b-a)*(c-d)*R*R
+        ad_plus_bc += &ac;                            // = ((b-a)*(c-d) + a*c)*R*R
+        ad_plus_bc += &bd;                            // = ((b-a)*(c-d) + a*c + b*d)*R*R
+
+        let ac_minus_bd = &ac - &bd;                  // = (a*c - b*d)*R*R
+
+        ExtensionFieldElementX2{
+            A: ac_minus_bd,
+            B: ad_plus_bc
+        }
+    }
+    /// Compute `x^2`, without the final Montgomery reduction of the coordinates.
+    pub fn square_unreduced(&self) -> ExtensionFieldElementX2 {
         let a = &self.A;
         let b = &self.B;
 
         // We want to compute
-	    //
-	    // (a + bi)*(a + bi) = (a^2 - b^2) + 2abi
+        //
+        // (a + bi)*(a + bi) = (a^2 - b^2) + 2abi
         //
         let a2 = a + a;        // = a*R + a*R = 2*a*R
         let a_plus_b = a + b;  // = a*R + b*R = (a+b)*R
         let a_minus_b = a - b; // = a*R - b*R = (a-b)*R
 
         let asq_minus_bsq = &a_plus_b * &a_minus_b; // = (a+b)*(a-b)*R*R = (a^2 - b^2)*R*R
-        let ab2 = &a2 * b;                          // = 2*a*b*R*R                       
-
-        let _a = asq_minus_bsq.reduce(); // = (a^2 - b^2)*R mod p
-        let _b = ab2.reduce();           // = 2*a*b*R mod p
+        let ab2 = &a2 * b;                          // = 2*a*b*R*R
 
-        ExtensionFieldElement{
-            A: _a,
-            B: _b
+        ExtensionFieldElementX2{
+            A: asq_minus_bsq,
+            B: ab2
         }
     }
     /// Returns true if both sides are equal. Takes variable time.
     pub fn vartime_eq(&self, _rhs: &ExtensionFieldElement) -> bool {
-        (&self.A == &_rhs.A) && (&self.B == &_rhs.B)
+        (self.A == _rhs.A) && (self.B == _rhs.B)
     }
     /// Convert the input to wire format.
-    pub fn to_bytes(&self) -> [u8; 188] {
+    pub fn to_bytes(self) -> [u8; 188] {
         let mut bytes = [0u8; 188];
         bytes[0..94].clone_from_slice(&self.A.to_bytes());
         bytes[94..188].clone_from_slice(&self.B.to_bytes());
@@ -318,6 +347,288 @@ impl ExtensionFieldElement {
         let b = Fp751Element::from_bytes(&bytes[94..188]);
         ExtensionFieldElement{ A: a, B: b }
     }
+    /// Returns true if `x` is a square in `F_{p^2}`.
+    ///
+    /// The element `x = a + bi` is a square in `F_{p^2}` if and only if its norm
+    /// `a^2 + b^2` is a square in `F_p`.
+    pub fn is_square(&self) -> bool {
+        let a = &self.A;
+        let b = &self.B;
+        let asq_plus_bsq = &(a * a) + &(b * b);        // = (a^2 + b^2)*R*R
+        let norm = PrimeFieldElement{ A: asq_plus_bsq.reduce() }; // = (a^2 + b^2)*R mod p
+        norm.is_square()
+    }
+    /// Set output to a square root of `x`, if `x` is a square. If `x` is nonsquare
+    /// output is undefined.
+    ///
+    /// The root is a deterministic function of `x`, computed using only `F_p`
+    /// arithmetic. This function is not constant time.
+    pub fn sqrt(&self) -> ExtensionFieldElement {
+        // = 1/2
+        const ONE_HALF: PrimeFieldElement = PrimeFieldElement::from_str_const(
+            "5177358870884652626488884118933402660713694822774535585058094839527339470341239423251441448280533356812276605809420101192601955988261277196522080234385575908488353420039456667179199865476387463490117543425495750936332825788416");
+        let a = self.real();
+        let b = self.imag();
+
+        // If y = y0 + y1*i is a root of a + b*i, then y0^2 = (a +- |x|)/2 and
+        // y1 = b/(2*y0), where |x| = sqrt(a^2 + b^2) is the square root of the norm.
+        let n = (&a.square() + &b.square()).sqrt();
+        let mut t0 = &(&a + &n) * &ONE_HALF;
+        if t0.vartime_eq(&PrimeFieldElement::zero()) {
+            t0 = &(&a - &n) * &ONE_HALF;
+        }
+        let mut t2 = t0.p34();              // = t0^((p-3)/4)
+        let t1 = &t0 * &t2;                 // = t0^((p+1)/4)
+        t2 = &(&t2 * &b) * &ONE_HALF;       // = b/(2*t1) if t0 is square
+
+        if t1.square().vartime_eq(&t0) {
+            ExtensionFieldElement::new(&t1, &t2)
+        } else {
+            // Now t1^2 = -t0, and the root is t2 - t1*i.
+            ExtensionFieldElement::new(&t2, &-&t1)
+        }
+    }
+    /// Returns true if `x` is a nonzero cube in `F_{p^2}`.
+    pub fn is_cube(&self) -> bool {
+        // x is a cube if and only if x^((p^2-1)/3) = 1. Write this as
+        // (x^(p-1))^((p+1)/3), where x^(p-1) lies in the cyclotomic subgroup.
+        CyclotomicElement::project(self).is_cube()
+    }
+    /// Sample a uniformly random `ExtensionFieldElement`.
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> ExtensionFieldElement {
+        let a = PrimeFieldElement::random(rng);
+        let b = PrimeFieldElement::random(rng);
+        ExtensionFieldElement{ A: a.A, B: b.A }
+    }
+}
+
+/// Represents an element of `F_{p^2}` whose coordinates have not been
+/// Montgomery-reduced yet, i.e. the output of a multiplication before the
+/// reduction step.
+///
+/// This allows sums and differences of products, such as `x*y - z*w`, to be
+/// computed with one reduction per coordinate instead of one per product.
+/// Each coordinate is kept in `[0, p*2^768)`, which is the input range of the
+/// Montgomery reduction. Subtraction keeps this range, while addition does not
+/// reduce, so it should only be used when the sum is known to stay in range.
+#[derive(Copy, Clone)]
+pub struct ExtensionFieldElementX2 {
+    /// The real part, as a double-width value `a*R*R`.
+    pub A: Fp751X2,
+    /// The imaginary part, as a double-width value `b*R*R`.
+    pub B: Fp751X2,
+}
+
+impl<'b> AddAssign<&'b ExtensionFieldElementX2> for ExtensionFieldElementX2 {
+    fn add_assign(&mut self, _rhs: &'b ExtensionFieldElementX2) {
+        let result = (self as &ExtensionFieldElementX2) + _rhs;
+        self.A = result.A;
+        self.B = result.B;
+    }
+}
+
+impl<'b> Add<&'b ExtensionFieldElementX2> for &ExtensionFieldElementX2 {
+    type Output = ExtensionFieldElementX2;
+    fn add(self, _rhs: &'b ExtensionFieldElementX2) -> ExtensionFieldElementX2 {
+        let a = &self.A + &_rhs.A;
+        let b = &self.B + &_rhs.B;
+
+        ExtensionFieldElementX2{
+            A: a,
+            B: b
+        }
+    }
+}
+
+impl <'b> SubAssign<&'b ExtensionFieldElementX2> for ExtensionFieldElementX2 {
+    fn sub_assign(&mut self, _rhs: &'b ExtensionFieldElementX2) {
+        let result = (self as &ExtensionFieldElementX2) - _rhs;
+        self.A = result.A;
+        self.B = result.B;
+    }
+}
+
+impl<'b> Sub<&'b ExtensionFieldElementX2> for &ExtensionFieldElementX2 {
+    type Output = ExtensionFieldElementX2;
+    fn sub(self, _rhs: &'b ExtensionFieldElementX2) -> ExtensionFieldElementX2 {
+        let a = &self.A - &_rhs.A;
+        let b = &self.B - &_rhs.B;
+
+        ExtensionFieldElementX2{
+            A: a,
+            B: b
+        }
+    }
+}
+
+impl Debug for ExtensionFieldElementX2 {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "ExtensionFieldElementX2(A: {:?}\nB: {:?})", &self.A.0[..], &self.B.0[..])
+    }
+}
+
+impl ExtensionFieldElementX2 {
+    /// Construct a zero `ExtensionFieldElementX2`.
+    pub fn zero() -> ExtensionFieldElementX2 {
+        ExtensionFieldElementX2{ A: Fp751X2::zero(), B: Fp751X2::zero() }
+    }
+    /// Perform Montgomery reduction on both coordinates.
+    pub fn reduce(&self) -> ExtensionFieldElement {
+        ExtensionFieldElement{
+            A: self.A.reduce(), // = a*R mod p
+            B: self.B.reduce()  // = b*R mod p
+        }
+    }
+}
+
+//-----------------------------------------------------------------------------//
+//                           Cyclotomic Subgroup                               //
+//-----------------------------------------------------------------------------//
+
+/// Represents an element of the cyclotomic subgroup of `F_{p^2}^*`, i.e. an
+/// element `a + bi` of norm `a^2 + b^2 = 1`, or equivalently `x^(p+1) = 1`.
+///
+/// The reduced pairings take their values in this subgroup of order `p+1`,
+/// where inversion is conjugation, and squaring and cubing are cheaper than in
+/// `F_{p^2}`.
+#[derive(Copy, Clone, PartialEq)]
+pub struct CyclotomicElement {
+    /// The underlying element of `F_{p^2}`, of norm 1.
+    pub A: ExtensionFieldElement,
+}
+
+impl<'b> MulAssign<&'b CyclotomicElement> for CyclotomicElement {
+    fn mul_assign(&mut self, _rhs: &'b CyclotomicElement) {
+        self.A = &self.A * &_rhs.A;
+    }
+}
+
+impl<'b> Mul<&'b CyclotomicElement> for &CyclotomicElement {
+    type Output = CyclotomicElement;
+    fn mul(self, _rhs: &'b CyclotomicElement) -> CyclotomicElement {
+        CyclotomicElement{ A: &self.A * &_rhs.A }
+    }
+}
+
+impl ConditionallySelectable for CyclotomicElement {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        CyclotomicElement{ A: ExtensionFieldElement::conditional_select(&a.A, &b.A, choice) }
+    }
+}
+
+impl ConstantTimeEq for CyclotomicElement {
+    fn ct_eq(&self, other: &CyclotomicElement) -> Choice {
+        self.A.ct_eq(&other.A)
+    }
+}
+
+impl Debug for CyclotomicElement {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "CyclotomicElement(A: {:?})", &self.A)
+    }
+}
+
+#[cfg(test)]
+impl Arbitrary for CyclotomicElement {
+    fn arbitrary(g: &mut Gen) -> CyclotomicElement {
+        CyclotomicElement::project(&ExtensionFieldElement::arbitrary(g))
+    }
+}
+
+impl CyclotomicElement {
+    /// Construct the neutral element `1`.
+    pub fn one() -> CyclotomicElement {
+        CyclotomicElement{ A: ExtensionFieldElement::one() }
+    }
+    /// Wrap an element of `F_{p^2}` of norm 1. The norm is not checked.
+    pub fn new(x: &ExtensionFieldElement) -> CyclotomicElement {
+        CyclotomicElement{ A: *x }
+    }
+    /// Map a nonzero `x` to the cyclotomic subgroup, by computing
+    /// `x^(p-1) = conj(x)/x = conj(x)^2/(a^2 + b^2)`.
+    ///
+    /// Cost: 1I+3M+2S in `F_p`.
+    pub fn project(x: &ExtensionFieldElement) -> CyclotomicElement {
+        let u0 = x.real();
+        let u1 = x.imag();
+        let v0 = u0.square();               // = u0^2
+        let v1 = u1.square();               // = u1^2
+        let t0 = (&v0 + &v1).inv();         // = 1/(u0^2 + u1^2)
+        let mut w0 = &v0 - &v1;             // = u0^2 - u1^2
+        let mut w1 = &u0 * &u1;
+        w1 = -&(&w1 + &w1);                 // = -2*u0*u1
+        w0 = &w0 * &t0;
+        w1 = &w1 * &t0;
+        CyclotomicElement{ A: ExtensionFieldElement::new(&w0, &w1) }
+    }
+    /// Compute the inverse `x^(-1) = x^p = a - bi`.
+    pub fn inv(&self) -> CyclotomicElement {
+        CyclotomicElement{ A: ExtensionFieldElement{ A: self.A.A, B: -&self.A.B } }
+    }
+    /// Compute `x^2 = (2a^2 - 1) + ((a + b)^2 - 1)i`, using `a^2 + b^2 = 1`.
+    ///
+    /// Cost: 2S+3a in `F_p`.
+    pub fn square(&self) -> CyclotomicElement {
+        let one = PrimeFieldElement::one();
+        let a0 = self.A.real();
+        let a1 = self.A.imag();
+        let t0 = (&a0 + &a1).square();      // = (a0 + a1)^2
+        let t1 = a0.square();               // = a0^2
+        CyclotomicElement{ A: ExtensionFieldElement::new(&(&(&t1 + &t1) - &one), &(&t0 - &one)) }
+    }
+    /// Compute `x^3 = a(4a^2 - 3) + b(4a^2 - 1)i`, using `a^2 + b^2 = 1`.
+    ///
+    /// Cost: 1S+2M+4a in `F_p`.
+    pub fn cube(&self) -> CyclotomicElement {
+        let one = PrimeFieldElement::one();
+        let a0 = self.A.real();
+        let a1 = self.A.imag();
+        let mut t0 = &a0 + &a0;             // = 2a0
+        t0 = &t0.square() - &one;           // = 4a0^2 - 1
+        let b1 = &t0 * &a1;                 // = a1(4a0^2 - 1)
+        t0 = &(&t0 - &one) - &one;          // = 4a0^2 - 3
+        let b0 = &t0 * &a0;                 // = a0(4a0^2 - 3)
+        CyclotomicElement{ A: ExtensionFieldElement::new(&b0, &b1) }
+    }
+    /// Compute `x^(2^k)` by `k` cyclotomic squarings.
+    pub fn pow2k(&self, k: u32) -> CyclotomicElement {
+        let mut y = *self;
+        for _ in 0..k {
+            y = y.square();
+        }
+        y
+    }
+    /// Compute `x^(3^k)` by `k` cyclotomic cubings.
+    pub fn pow3k(&self, k: u32) -> CyclotomicElement {
+        let mut y = *self;
+        for _ in 0..k {
+            y = y.cube();
+        }
+        y
+    }
+    /// Compute `x^e` for a little-endian integer `e`, by square-and-multiply.
+    ///
+    /// This function executes in constant time for a fixed length of `e`.
+    pub fn exp(&self, e: &[u8]) -> CyclotomicElement {
+        let mut y = CyclotomicElement::one();
+        for byte in e.iter().rev() {
+            for i in (0..8).rev() {
+                y = y.square();
+                let t = &y * self;
+                y.conditional_assign(&t, Choice::from((byte >> i) & 1));
+            }
+        }
+        y
+    }
+    /// Returns true if `x` is a cube in the cyclotomic subgroup, i.e. if
+    /// `x^((p+1)/3) = x^(2^372 * 3^238) = 1`.
+    pub fn is_cube(&self) -> bool {
+        self.pow2k(372).pow3k(238).vartime_eq(&CyclotomicElement::one())
+    }
+    /// Returns true if both sides are equal. Takes variable time.
+    pub fn vartime_eq(&self, _rhs: &CyclotomicElement) -> bool {
+        self.A.vartime_eq(&_rhs.A)
+    }
 }
 
 //-----------------------------------------------------------------------------//
@@ -339,7 +650,7 @@ impl<'b> AddAssign<&'b PrimeFieldElement> for PrimeFieldElement {
     }
 }
 
-impl<'a, 'b> Add<&'b PrimeFieldElement> for &'a PrimeFieldElement {
+impl<'b> Add<&'b PrimeFieldElement> for &PrimeFieldElement {
     type Output = PrimeFieldElement;
     fn add(self, _rhs: &'b PrimeFieldElement) -> PrimeFieldElement {
         let a = &self.A + &_rhs.A;
@@ -354,7 +665,7 @@ impl <'b> SubAssign<&'b PrimeFieldElement> for PrimeFieldElement {
     }
 }
 
-impl<'a, 'b> Sub<&'b PrimeFieldElement> for &'a PrimeFieldElement {
+impl<'b> Sub<&'b PrimeFieldElement> for &PrimeFieldElement {
     type Output = PrimeFieldElement;
     fn sub(self, _rhs: &'b PrimeFieldElement) -> PrimeFieldElement {
         let a = &self.A - &_rhs.A;
@@ -369,7 +680,7 @@ impl<'b> MulAssign<&'b PrimeFieldElement> for PrimeFieldElement {
     }
 }
 
-impl<'a, 'b> Mul<&'b PrimeFieldElement> for &'a PrimeFieldElement {
+impl<'b> Mul<&'b PrimeFieldElement> for &PrimeFieldElement {
     type Output = PrimeFieldElement;
     fn mul(self, _rhs: &'b PrimeFieldElement) -> PrimeFieldElement {
         // Alias self, _rhs for more readable formulas.
@@ -382,12 +693,11 @@ impl<'a, 'b> Mul<&'b PrimeFieldElement> for &'a PrimeFieldElement {
     }
 }
 
-impl <'a> Neg for &'a PrimeFieldElement {
+impl Neg for &PrimeFieldElement {
     type Output = PrimeFieldElement;
     fn neg(self) -> PrimeFieldElement {
         let zero = PrimeFieldElement::zero();
-        let result = &zero - (self as &PrimeFieldElement);
-        result
+        &zero - (self as &PrimeFieldElement)
     }
 }
 
@@ -451,9 +761,8 @@ impl PrimeFieldElement {
     /// Set the output to `x^2`.
     pub fn square(&self) -> PrimeFieldElement {
         let a = &self.A;      // = a*R
-        let b = &self.A;      // = b*R
-        let ab = a * b;       // = a*b*R*R
-        let _a = ab.reduce(); // = a*b*R mod p
+        let aa = a.square();  // = a*a*R*R
+        let _a = aa.reduce(); // = a*a*R mod p
 
         PrimeFieldElement{ A: _a }
     }
@@ -503,6 +812,27 @@ impl PrimeFieldElement {
         // Now result^2 = y^(p+1) = y^2 = x, so result = sqrt(x).
         result
     }
+    /// Returns true if `x` is a square in `F_p`.
+    pub fn is_square(&self) -> bool {
+        let mut result = self.p34();        // result = x^((p-3)/4)
+        result = &result.square() * self;   // result = x^((p-1)/2)
+        // Now result is 1 if x is a nonzero square, 0 if x is zero, and -1 otherwise.
+        !result.vartime_eq(&-(&PrimeFieldElement::one()))
+    }
+    /// Sample a uniformly random `PrimeFieldElement`.
+    //
+    // Use rejection sampling: draw 751-bit integers until one is less than p.
+    // Since p > 0.87*2^751, this takes fewer than 1.15 trials on average.
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> PrimeFieldElement {
+        let mut bytes = [0u8; FP751_ENCODED_BYTES];
+        loop {
+            rng.fill_bytes(&mut bytes[..]);
+            bytes[FP751_ENCODED_BYTES - 1] &= 0x7f; // Clear high bit, so the integer is < 2^751.
+            if let Some(x) = PrimeFieldElement::from_le_bytes(&bytes) {
+                return x;
+            }
+        }
+    }
     /// Set output to `1/x`.
     pub fn inv(&self) -> PrimeFieldElement {
         let mut result = self.square(); // result = x^2
@@ -513,7 +843,373 @@ impl PrimeFieldElement {
     }
     /// Returns true if both sides are equal. Takes variable time.
     pub fn vartime_eq(&self, _rhs: &PrimeFieldElement) -> bool {
-        &self.A == &_rhs.A
+        self.A == _rhs.A
+    }
+}
+
+//-----------------------------------------------------------------------------//
+//                          Canonical Conversions                              //
+//-----------------------------------------------------------------------------//
+
+/// The length, in bytes, of the canonical encoding of an element of `F_p`.
+pub const FP751_ENCODED_BYTES: usize = 94;
+
+/// The number of 64-bit limbs of the canonical integer representation of an
+/// element of `F_p`.
+pub const FP751_NUM_U64_LIMBS: usize = 12;
+
+/// `p = 2^372 * 3^239 - 1`, as little-endian 64-bit limbs.
+const P751_U64: [u64; FP751_NUM_U64_LIMBS] = [0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0xeeafffffffffffff, 0xe3ec968549f878a8, 0xda959b1a13f7cc76, 0x084e9867d6ebe876, 0x8562b5045cb25748, 0x0e12909f97badc66, 0x6fe5d541f71c];
+
+/// `(2^768)^2 mod p`, as little-endian 64-bit limbs.
+const MONTGOMERY_RSQ_U64: [u64; FP751_NUM_U64_LIMBS] = [0x233046449dad4058, 0xdb010161a696452a, 0x5e36941472e3fd8e, 0xf40bfe2082a2e706, 0x4932cca8904f8751, 0x1f735f1f1ee7fc81, 0xa24f4d80c1048e18, 0xb56c383ccdb607c5, 0x441dd47b735f9c90, 0x5673ed2c6a6ac82a, 0x06c905261132294b, 0x41ad830f1f35];
+
+/// An error which can be returned when parsing a field element from a string.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParseFieldElementError {
+    /// The input string (or one of its coordinates) was empty.
+    Empty,
+    /// The input string contained a character which is not a valid digit.
+    InvalidDigit,
+    /// The parsed integer is not in the range `[0, p)`.
+    OutOfRange,
+}
+
+impl Display for ParseFieldElementError {
+    fn fmt(&self, f: &mut Formatter) -> ::core::fmt::Result {
+        let description = match *self {
+            ParseFieldElementError::Empty => "cannot parse field element from empty string",
+            ParseFieldElementError::InvalidDigit => "invalid digit found in string",
+            ParseFieldElementError::OutOfRange => "integer is not less than the field characteristic p",
+        };
+        f.write_str(description)
+    }
+}
+
+// Compute `a - b` over little-endian 64-bit limbs, returning the difference
+// and the final borrow.
+const fn sub_u64_limbs(a: &[u64; FP751_NUM_U64_LIMBS], b: &[u64; FP751_NUM_U64_LIMBS]) -> ([u64; FP751_NUM_U64_LIMBS], u64) {
+    let mut result = [0u64; FP751_NUM_U64_LIMBS];
+    let mut borrow = 0u64;
+    let mut i = 0;
+    while i < FP751_NUM_U64_LIMBS {
+        let (t0, b0) = a[i].overflowing_sub(b[i]);
+        let (t1, b1) = t0.overflowing_sub(borrow);
+        result[i] = t1;
+        borrow = (b0 | b1) as u64;
+        i += 1;
+    }
+    (result, borrow)
+}
+
+// Returns true if the integer given by little-endian 64-bit limbs is less than `p`.
+const fn is_less_than_p(a: &[u64; FP751_NUM_U64_LIMBS]) -> bool {
+    let (_, borrow) = sub_u64_limbs(a, &P751_U64);
+    borrow == 1
+}
+
+// Compute `a*b*2^(-768) mod p` in `[0, p)` for `a, b` in `[0, p)`, given as
+// little-endian 64-bit limbs.
+//
+// This is the textbook CIOS Montgomery multiplication. Since `p = -1 mod 2^64`,
+// the Montgomery constant `-p^(-1) mod 2^64` is `1`. The assembly backends are
+// faster, but this version can be evaluated in `const` contexts.
+const fn mont_mul_u64_limbs(a: &[u64; FP751_NUM_U64_LIMBS], b: &[u64; FP751_NUM_U64_LIMBS]) -> [u64; FP751_NUM_U64_LIMBS] {
+    let mut t = [0u64; FP751_NUM_U64_LIMBS + 2];
+    let mut i = 0;
+    while i < FP751_NUM_U64_LIMBS {
+        // t = t + a*b[i]
+        let mut carry = 0u64;
+        let mut j = 0;
+        while j < FP751_NUM_U64_LIMBS {
+            let uv = (t[j] as u128) + (a[j] as u128) * (b[i] as u128) + (carry as u128);
+            t[j] = uv as u64;
+            carry = (uv >> 64) as u64;
+            j += 1;
+        }
+        let uv = (t[FP751_NUM_U64_LIMBS] as u128) + (carry as u128);
+        t[FP751_NUM_U64_LIMBS] = uv as u64;
+        t[FP751_NUM_U64_LIMBS + 1] = (uv >> 64) as u64;
+
+        // t = (t + m*p)/2^64, where m = t[0]*(-p^(-1)) = t[0] mod 2^64
+        let m = t[0] as u128;
+        let uv = (t[0] as u128) + m * (P751_U64[0] as u128);
+        carry = (uv >> 64) as u64;
+        j = 1;
+        while j < FP751_NUM_U64_LIMBS {
+            let uv = (t[j] as u128) + m * (P751_U64[j] as u128) + (carry as u128);
+            t[j - 1] = uv as u64;
+            carry = (uv >> 64) as u64;
+            j += 1;
+        }
+        let uv = (t[FP751_NUM_U64_LIMBS] as u128) + (carry as u128);
+        t[FP751_NUM_U64_LIMBS - 1] = uv as u64;
+        t[FP751_NUM_U64_LIMBS] = t[FP751_NUM_U64_LIMBS + 1] + ((uv >> 64) as u64);
+        i += 1;
+    }
+    // Now t < 2p < 2^768, so a single conditional subtraction reduces it to [0, p).
+    let mut result = [0u64; FP751_NUM_U64_LIMBS];
+    i = 0;
+    while i < FP751_NUM_U64_LIMBS {
+        result[i] = t[i];
+        i += 1;
+    }
+    if is_less_than_p(&result) {
+        result
+    } else {
+        let (reduced, _) = sub_u64_limbs(&result, &P751_U64);
+        reduced
+    }
+}
+
+// Pack little-endian 64-bit limbs into the limb layout used by the backend.
+#[cfg(target_arch = "x86_64")]
+const fn fp751_from_u64_limbs(limbs: [u64; FP751_NUM_U64_LIMBS]) -> Fp751Element {
+    Fp751Element(limbs)
+}
+
+// Pack little-endian 64-bit limbs into the limb layout used by the backend.
+#[cfg(target_arch = "x86")]
+const fn fp751_from_u64_limbs(limbs: [u64; FP751_NUM_U64_LIMBS]) -> Fp751Element {
+    let mut words = [0u32; 2*FP751_NUM_U64_LIMBS];
+    let mut i = 0;
+    while i < FP751_NUM_U64_LIMBS {
+        words[2*i] = limbs[i] as u32;
+        words[2*i + 1] = (limbs[i] >> 32) as u32;
+        i += 1;
+    }
+    Fp751Element(words)
+}
+
+// Parse a decimal, or `0x`-prefixed hexadecimal, integer in `[0, p)` into
+// little-endian 64-bit limbs.
+const fn parse_u64_limbs(s: &[u8]) -> Result<[u64; FP751_NUM_U64_LIMBS], ParseFieldElementError> {
+    let (radix, mut i) = if s.len() > 2 && s[0] == b'0' && (s[1] == b'x' || s[1] == b'X') {
+        (16u128, 2)
+    } else {
+        (10u128, 0)
+    };
+    if i >= s.len() {
+        return Err(ParseFieldElementError::Empty);
+    }
+
+    let mut limbs = [0u64; FP751_NUM_U64_LIMBS];
+    while i < s.len() {
+        let digit = match s[i] {
+            b'0'..=b'9' => (s[i] - b'0') as u128,
+            b'a'..=b'f' => (s[i] - b'a' + 10) as u128,
+            b'A'..=b'F' => (s[i] - b'A' + 10) as u128,
+            _ => return Err(ParseFieldElementError::InvalidDigit),
+        };
+        if digit >= radix {
+            return Err(ParseFieldElementError::InvalidDigit);
+        }
+        // limbs = radix*limbs + digit
+        let mut carry = digit;
+        let mut j = 0;
+        while j < FP751_NUM_U64_LIMBS {
+            let uv = (limbs[j] as u128) * radix + carry;
+            limbs[j] = uv as u64;
+            carry = uv >> 64;
+            j += 1;
+        }
+        if carry != 0 {
+            return Err(ParseFieldElementError::OutOfRange);
+        }
+        i += 1;
+    }
+
+    if is_less_than_p(&limbs) {
+        Ok(limbs)
+    } else {
+        Err(ParseFieldElementError::OutOfRange)
+    }
+}
+
+// Write the integer given by little-endian 64-bit limbs in the given radix
+// (10 or 16) into `buf`, returning the index of the first written digit.
+fn write_u64_limbs(limbs: &[u64; FP751_NUM_U64_LIMBS], radix: u64, buf: &mut [u8; 232]) -> usize {
+    let mut n = *limbs;
+    let mut start = buf.len();
+    loop {
+        // n, r = n / radix, n % radix
+        let mut r = 0u128;
+        for limb in n.iter_mut().rev() {
+            let cur = (r << 64) | (*limb as u128);
+            *limb = (cur / (radix as u128)) as u64;
+            r = cur % (radix as u128);
+        }
+        start -= 1;
+        buf[start] = b"0123456789abcdef"[r as usize];
+        if n.iter().all(|&limb| limb == 0) {
+            return start;
+        }
+    }
+}
+
+impl PrimeFieldElement {
+    /// Construct a `PrimeFieldElement` from the little-endian 64-bit limbs of an
+    /// integer in `[0, p)`, converting it to Montgomery form.
+    ///
+    /// This can be used in `const` contexts. Panics if the integer is not less than `p`.
+    pub const fn from_u64_limbs(limbs: [u64; FP751_NUM_U64_LIMBS]) -> PrimeFieldElement {
+        assert!(is_less_than_p(&limbs), "Integer passed to PrimeFieldElement from_u64_limbs is not less than p");
+        let montgomery = mont_mul_u64_limbs(&limbs, &MONTGOMERY_RSQ_U64); // = a*R mod p
+        PrimeFieldElement{ A: fp751_from_u64_limbs(montgomery) }
+    }
+    /// Construct a `PrimeFieldElement` from a small integer. Can be used in `const` contexts.
+    pub const fn from_u64(x: u64) -> PrimeFieldElement {
+        let mut limbs = [0u64; FP751_NUM_U64_LIMBS];
+        limbs[0] = x;
+        PrimeFieldElement::from_u64_limbs(limbs)
+    }
+    /// Construct a `PrimeFieldElement` from a decimal, or `0x`-prefixed hexadecimal,
+    /// integer in `[0, p)`.
+    ///
+    /// This is intended for writing constants in readable form, and can be used in
+    /// `const` contexts. Panics if the string is not a valid integer in `[0, p)`;
+    /// use `str::parse` to handle invalid input gracefully.
+    pub const fn from_str_const(s: &str) -> PrimeFieldElement {
+        match parse_u64_limbs(s.as_bytes()) {
+            Ok(limbs) => PrimeFieldElement::from_u64_limbs(limbs),
+            Err(_) => panic!("Invalid input to PrimeFieldElement from_str_const"),
+        }
+    }
+    /// Convert to the little-endian 64-bit limbs of the canonical integer in `[0, p)`.
+    pub fn to_u64_limbs(self) -> [u64; FP751_NUM_U64_LIMBS] {
+        let bytes = self.to_le_bytes();
+        let mut limbs = [0u64; FP751_NUM_U64_LIMBS];
+        for (i, byte) in bytes.iter().enumerate() {
+            limbs[i / 8] |= (*byte as u64) << (8 * (i % 8));
+        }
+        limbs
+    }
+    /// Convert to the canonical integer in `[0, p)`, as 94 little-endian bytes.
+    pub fn to_le_bytes(self) -> [u8; FP751_ENCODED_BYTES] {
+        self.A.to_bytes()
+    }
+    /// Convert to the canonical integer in `[0, p)`, as 94 big-endian bytes.
+    pub fn to_be_bytes(self) -> [u8; FP751_ENCODED_BYTES] {
+        let mut bytes = self.to_le_bytes();
+        bytes.reverse();
+        bytes
+    }
+    /// Read a canonical integer from 94 little-endian bytes. Returns `None` if the
+    /// integer is not less than `p`.
+    pub fn from_le_bytes(bytes: &[u8; FP751_ENCODED_BYTES]) -> Option<PrimeFieldElement> {
+        let mut limbs = [0u64; FP751_NUM_U64_LIMBS];
+        for (i, byte) in bytes.iter().enumerate() {
+            limbs[i / 8] |= (*byte as u64) << (8 * (i % 8));
+        }
+        if is_less_than_p(&limbs) {
+            Some(PrimeFieldElement::from_u64_limbs(limbs))
+        } else {
+            None
+        }
+    }
+    /// Read a canonical integer from 94 big-endian bytes. Returns `None` if the
+    /// integer is not less than `p`.
+    pub fn from_be_bytes(bytes: &[u8; FP751_ENCODED_BYTES]) -> Option<PrimeFieldElement> {
+        let mut le_bytes = *bytes;
+        le_bytes.reverse();
+        PrimeFieldElement::from_le_bytes(&le_bytes)
+    }
+}
+
+impl Display for PrimeFieldElement {
+    /// Format the canonical integer in `[0, p)` in decimal.
+    fn fmt(&self, f: &mut Formatter) -> ::core::fmt::Result {
+        let mut buf = [0u8; 232];
+        let start = write_u64_limbs(&self.to_u64_limbs(), 10, &mut buf);
+        f.pad_integral(true, "", ::core::str::from_utf8(&buf[start..]).unwrap())
+    }
+}
+
+impl LowerHex for PrimeFieldElement {
+    /// Format the canonical integer in `[0, p)` in hexadecimal.
+    fn fmt(&self, f: &mut Formatter) -> ::core::fmt::Result {
+        let mut buf = [0u8; 232];
+        let start = write_u64_limbs(&self.to_u64_limbs(), 16, &mut buf);
+        f.pad_integral(true, "0x", ::core::str::from_utf8(&buf[start..]).unwrap())
+    }
+}
+
+impl FromStr for PrimeFieldElement {
+    type Err = ParseFieldElementError;
+    /// Parse a decimal, or `0x`-prefixed hexadecimal, integer in `[0, p)`.
+    fn from_str(s: &str) -> Result<PrimeFieldElement, ParseFieldElementError> {
+        let limbs = parse_u64_limbs(s.trim().as_bytes())?;
+        Ok(PrimeFieldElement::from_u64_limbs(limbs))
+    }
+}
+
+impl ExtensionFieldElement {
+    /// Construct the `ExtensionFieldElement` `a + b*i`. Can be used in `const` contexts.
+    pub const fn new(a: &PrimeFieldElement, b: &PrimeFieldElement) -> ExtensionFieldElement {
+        ExtensionFieldElement{ A: a.A, B: b.A }
+    }
+    /// Construct the `ExtensionFieldElement` `x + 0*i` from a small integer. Can be
+    /// used in `const` contexts.
+    pub const fn from_u64(x: u64) -> ExtensionFieldElement {
+        ExtensionFieldElement::new(&PrimeFieldElement::from_u64(x), &PrimeFieldElement::from_u64(0))
+    }
+    /// Construct the `ExtensionFieldElement` `a + b*i`, where `a` and `b` are given
+    /// as decimal, or `0x`-prefixed hexadecimal, integers in `[0, p)`.
+    ///
+    /// This can be used in `const` contexts. Panics on invalid input.
+    pub const fn from_str_const(a: &str, b: &str) -> ExtensionFieldElement {
+        ExtensionFieldElement::new(&PrimeFieldElement::from_str_const(a), &PrimeFieldElement::from_str_const(b))
+    }
+    /// Returns the real part `a` of `a + b*i`.
+    pub fn real(&self) -> PrimeFieldElement {
+        PrimeFieldElement{ A: self.A }
+    }
+    /// Returns the imaginary part `b` of `a + b*i`.
+    pub fn imag(&self) -> PrimeFieldElement {
+        PrimeFieldElement{ A: self.B }
+    }
+}
+
+impl Display for ExtensionFieldElement {
+    /// Format as `a + b*i`, with `a` and `b` the canonical integers in `[0, p)` in decimal.
+    fn fmt(&self, f: &mut Formatter) -> ::core::fmt::Result {
+        write!(f, "{} + {}*i", self.real(), self.imag())
+    }
+}
+
+impl LowerHex for ExtensionFieldElement {
+    /// Format as `a + b*i`, with `a` and `b` the canonical integers in `[0, p)` in hexadecimal.
+    fn fmt(&self, f: &mut Formatter) -> ::core::fmt::Result {
+        if f.alternate() {
+            write!(f, "{:#x} + {:#x}*i", self.real(), self.imag())
+        } else {
+            write!(f, "{:x} + {:x}*i", self.real(), self.imag())
+        }
+    }
+}
+
+impl FromStr for ExtensionFieldElement {
+    type Err = ParseFieldElementError;
+    /// Parse an element written as `a + b*i`, `a`, or `b*i`, where `a` and `b`
+    /// are decimal, or `0x`-prefixed hexadecimal, integers in `[0, p)`.
+    fn from_str(s: &str) -> Result<ExtensionFieldElement, ParseFieldElementError> {
+        // Strip the `*i` (or bare `i`) suffix from the imaginary part.
+        fn parse_imag(s: &str) -> Result<PrimeFieldElement, ParseFieldElementError> {
+            let s = s.trim();
+            let s = match s.strip_suffix('i') {
+                Some(rest) => rest.trim_end(),
+                None => return Err(ParseFieldElementError::InvalidDigit),
+            };
+            s.strip_suffix('*').unwrap_or(s).parse()
+        }
+
+        let s = s.trim();
+        let (a, b) = match s.find('+') {
+            Some(k) => (s[..k].parse()?, parse_imag(&s[k+1..])?),
+            None if s.ends_with('i') => (PrimeFieldElement::zero(), parse_imag(s)?),
+            None => (s.parse()?, PrimeFieldElement::zero()),
+        };
+        Ok(ExtensionFieldElement::new(&a, &b))
     }
 }
 
@@ -528,11 +1224,11 @@ impl<'b> AddAssign<&'b Fp751Element> for Fp751Element {
     }
 }
 
-impl<'a, 'b> Add<&'b Fp751Element> for &'a Fp751Element {
+impl<'b> Add<&'b Fp751Element> for &Fp751Element {
     type Output = Fp751Element;
     fn add(self, _rhs: &'b Fp751Element) -> Fp751Element {
         let mut result = Fp751Element::zero();
-        fpadd751(&self, _rhs, &mut result);
+        fpadd751(self, _rhs, &mut result);
         result
     }
 }
@@ -544,30 +1240,29 @@ impl <'b> SubAssign<&'b Fp751Element> for Fp751Element {
     }
 }
 
-impl<'a, 'b> Sub<&'b Fp751Element> for &'a Fp751Element {
+impl<'b> Sub<&'b Fp751Element> for &Fp751Element {
     type Output = Fp751Element;
     fn sub(self, _rhs: &'b Fp751Element) -> Fp751Element {
         let mut result = Fp751Element::zero();
-        fpsub751(&self, _rhs, &mut result);
+        fpsub751(self, _rhs, &mut result);
         result
     }
 }
 
-impl<'a, 'b> Mul<&'b Fp751Element> for &'a Fp751Element {
+impl<'b> Mul<&'b Fp751Element> for &Fp751Element {
     type Output = Fp751X2;
     fn mul(self, _rhs: &'b Fp751Element) -> Fp751X2 {
         let mut result = Fp751X2::zero();
-        mul751(&self, _rhs, &mut result); // = a*c*R*R
+        mul751(self, _rhs, &mut result); // = a*c*R*R
         result
     }
 }
 
-impl <'a> Neg for &'a Fp751Element {
+impl Neg for &Fp751Element {
     type Output = Fp751Element;
     fn neg(self) -> Fp751Element {
         let zero = Fp751Element::zero();
-        let result = &zero - (self as &Fp751Element);
-        result
+        &zero - (self as &Fp751Element)
     }
 }
 
@@ -636,6 +1331,12 @@ impl Fp751Element {
         srdc751(&mut _self);
         _self
     }
+    /// Compute the (unreduced) square of a field element.
+    pub fn square(&self) -> Fp751X2 {
+        let mut result = Fp751X2::zero();
+        sqr751(self, &mut result); // = a*a*R*R
+        result
+    }
 }
 
 impl<'b> AddAssign<&'b Fp751X2> for Fp751X2 {
@@ -645,11 +1346,11 @@ impl<'b> AddAssign<&'b Fp751X2> for Fp751X2 {
     }
 }
 
-impl<'a, 'b> Add<&'b Fp751X2> for &'a Fp751X2 {
+impl<'b> Add<&'b Fp751X2> for &Fp751X2 {
     type Output = Fp751X2;
     fn add(self, _rhs: &'b Fp751X2) -> Fp751X2 {
         let mut result = Fp751X2::zero();
-        mp_add751x2(&self, _rhs, &mut result);
+        mp_add751x2(self, _rhs, &mut result);
         result
     }
 }
@@ -661,11 +1362,11 @@ impl <'b> SubAssign<&'b Fp751X2> for Fp751X2 {
     }
 }
 
-impl<'a, 'b> Sub<&'b Fp751X2> for &'a Fp751X2 {
+impl<'b> Sub<&'b Fp751X2> for &Fp751X2 {
     type Output = Fp751X2;
     fn sub(self, _rhs: &'b Fp751X2) -> Fp751X2 {
         let mut result = Fp751X2::zero();
-        mp_sub751x2(&self, _rhs, &mut result);
+        mp_sub751x2(self, _rhs, &mut result);
         result
     }
 }
@@ -696,6 +1397,7 @@ pub fn mulby3(scalar: &mut [u8; 48]) {
 #[cfg(test)]
 mod test {
     use super::*;
+    use std::string::ToString;
 
     const SCALE_FACTOR: u8 = 3;
     const MAX_TESTS: u64 = 1 << (10 + SCALE_FACTOR);
@@ -707,8 +1409,8 @@ mod test {
 
         assert_eq!(bytes[0], 1);
 
-        for i in 1..188 {
-            assert_eq!(bytes[i], 0);
+        for &byte in bytes[1..].iter() {
+            assert_eq!(byte, 0);
         }
     }
     
@@ -765,6 +1467,20 @@ mod test {
                          .quickcheck(square_matches_mul as fn(ExtensionFieldElement) -> bool);
     }
 
+    #[test]
+    fn extension_field_element_mul_by_prime_field_element() {
+        fn mul_by_prime_field(x: ExtensionFieldElement, y: PrimeFieldElement) -> bool {
+            // Compute t1 = x*(y + 0*i)
+            let t1 = &x * &ExtensionFieldElement{ A: y.A, B: Fp751Element::zero() };
+            // Compute t2 = x*y
+            let t2 = &x * &y;
+
+            t1.vartime_eq(&t2)
+        }
+        QuickCheck::new().max_tests(MAX_TESTS)
+                         .quickcheck(mul_by_prime_field as fn(ExtensionFieldElement, PrimeFieldElement) -> bool);
+    }
+
     #[test]
     fn extension_field_element_inv() {
         fn inverse(x: ExtensionFieldElement) -> bool {
@@ -821,6 +1537,247 @@ mod test {
                          .quickcheck(square_root as fn(PrimeFieldElement) -> bool);
     }
 
+    #[test]
+    fn prime_field_element_const_constructors() {
+        assert!(PrimeFieldElement::from_u64(0).vartime_eq(&PrimeFieldElement::zero()));
+        assert!(PrimeFieldElement::from_u64(1).vartime_eq(&PrimeFieldElement::one()));
+        assert!(PrimeFieldElement::from_str_const("1").vartime_eq(&PrimeFieldElement::one()));
+        assert!(PrimeFieldElement::from_str_const("0x1").vartime_eq(&PrimeFieldElement::one()));
+        assert!(ExtensionFieldElement::from_u64(1).vartime_eq(&ExtensionFieldElement::one()));
+
+        // sage: p - 1
+        let minus_one = PrimeFieldElement::from_str_const("10354717741769305252977768237866805321427389645549071170116189679054678940682478846502882896561066713624553211618840202385203911976522554393044160468771151816976706840078913334358399730952774926980235086850991501872665651576830");
+        assert!(minus_one.vartime_eq(&-(&PrimeFieldElement::one())));
+    }
+
+    #[test]
+    fn prime_field_element_to_string_versus_sage() {
+        // sage: p - 1
+        let minus_one = -(&PrimeFieldElement::one());
+        assert_eq!(minus_one.to_string(), "10354717741769305252977768237866805321427389645549071170116189679054678940682478846502882896561066713624553211618840202385203911976522554393044160468771151816976706840078913334358399730952774926980235086850991501872665651576830");
+        // sage: hex(p - 1)
+        assert_eq!(format!("{:#x}", minus_one), "0x6fe5d541f71c0e12909f97badc668562b5045cb25748084e9867d6ebe876da959b1a13f7cc76e3ec968549f878a8eeaffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe");
+        assert_eq!(PrimeFieldElement::zero().to_string(), "0");
+        assert_eq!(format!("{:x}", PrimeFieldElement::from_u64(255)), "ff");
+
+        let i = ExtensionFieldElement::new(&PrimeFieldElement::zero(), &PrimeFieldElement::one());
+        assert_eq!(i.to_string(), "0 + 1*i");
+    }
+
+    #[test]
+    fn prime_field_element_from_str_errors() {
+        // sage: p
+        let p = "10354717741769305252977768237866805321427389645549071170116189679054678940682478846502882896561066713624553211618840202385203911976522554393044160468771151816976706840078913334358399730952774926980235086850991501872665651576831";
+        assert_eq!(p.parse::<PrimeFieldElement>(), Err(ParseFieldElementError::OutOfRange));
+        assert_eq!("1".repeat(300).parse::<PrimeFieldElement>(), Err(ParseFieldElementError::OutOfRange));
+        assert_eq!("12a".parse::<PrimeFieldElement>(), Err(ParseFieldElementError::InvalidDigit));
+        assert_eq!("0xg".parse::<PrimeFieldElement>(), Err(ParseFieldElementError::InvalidDigit));
+        assert_eq!("".parse::<PrimeFieldElement>(), Err(ParseFieldElementError::Empty));
+        assert_eq!("1 + i".parse::<ExtensionFieldElement>(), Err(ParseFieldElementError::Empty));
+    }
+
+    #[test]
+    fn prime_field_element_bytes_round_trip() {
+        fn round_trips(x: PrimeFieldElement) -> bool {
+            let le = PrimeFieldElement::from_le_bytes(&x.to_le_bytes()).unwrap();
+            let be = PrimeFieldElement::from_be_bytes(&x.to_be_bytes()).unwrap();
+            let limbs = PrimeFieldElement::from_u64_limbs(x.to_u64_limbs());
+            x.vartime_eq(&le) && x.vartime_eq(&be) && x.vartime_eq(&limbs)
+        }
+        QuickCheck::new().max_tests(MAX_TESTS)
+                         .quickcheck(round_trips as fn(PrimeFieldElement) -> bool);
+    }
+
+    #[test]
+    #[cfg(target_arch = "x86_64")]
+    fn mulx_adx_kernels_match_generic() {
+        fn kernels_match(x: Fp751Element, y: Fp751Element) -> bool {
+            let mut xy = Fp751X2::zero();
+            let mut xy_generic = Fp751X2::zero();
+            mul751(&x, &y, &mut xy);
+            mul751_generic(&x, &y, &mut xy_generic);
+
+            let mut xx = Fp751X2::zero();
+            let mut xx_generic = Fp751X2::zero();
+            sqr751(&x, &mut xx);
+            mul751_generic(&x, &x, &mut xx_generic);
+
+            let mut z = Fp751Element::zero();
+            let mut z_generic = Fp751Element::zero();
+            rdc751(&xy, &mut z);
+            rdc751_generic(&xy, &mut z_generic);
+
+            xy == xy_generic && xx == xx_generic && z.0 == z_generic.0
+        }
+        if !has_mulx_adx() {
+            return;
+        }
+        // The largest input in [0, 2p), which maximizes all carries.
+        // sage: (2*p - 1).digits(2^64)
+        let max = Fp751Element([0xfffffffffffffffd, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0xffffffffffffffff, 0xdd5fffffffffffff, 0xc7d92d0a93f0f151, 0xb52b363427ef98ed, 0x109d30cfadd7d0ed, 0x0ac56a08b964ae90, 0x1c25213f2f75b8cd, 0x0000dfcbaa83ee38]);
+        assert!(kernels_match(max, max));
+        assert!(kernels_match(Fp751Element::zero(), max));
+        QuickCheck::new().max_tests(MAX_TESTS)
+                         .quickcheck(kernels_match as fn(Fp751Element, Fp751Element) -> bool);
+    }
+
+    #[test]
+    fn prime_field_element_from_bytes_rejects_non_canonical() {
+        // The encoding of p itself is out of range.
+        let mut p_bytes = (-(&PrimeFieldElement::one())).to_le_bytes();
+        p_bytes[0] += 1;
+        assert!(PrimeFieldElement::from_le_bytes(&p_bytes).is_none());
+        p_bytes.reverse();
+        assert!(PrimeFieldElement::from_be_bytes(&p_bytes).is_none());
+    }
+
+    #[test]
+    fn extension_field_element_to_string_round_trip() {
+        fn round_trips(x: ExtensionFieldElement) -> bool {
+            let decimal: ExtensionFieldElement = x.to_string().parse().unwrap();
+            let hex: ExtensionFieldElement = format!("{:#x}", x).parse().unwrap();
+            let real: ExtensionFieldElement = x.real().to_string().parse().unwrap();
+            let imag: ExtensionFieldElement = format!("{}*i", x.imag()).parse().unwrap();
+            x.vartime_eq(&decimal) && x.vartime_eq(&hex) && x.vartime_eq(&(&real + &imag))
+        }
+        QuickCheck::new().max_tests(MAX_TESTS)
+                         .quickcheck(round_trips as fn(ExtensionFieldElement) -> bool);
+    }
+
+    #[test]
+    fn prime_field_element_random_is_canonical() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            let x = PrimeFieldElement::random(&mut rng);
+            // from_le_bytes only accepts integers in [0, p).
+            let y = PrimeFieldElement::from_le_bytes(&x.to_le_bytes()).unwrap();
+            assert!(x.vartime_eq(&y));
+        }
+        let x = ExtensionFieldElement::random(&mut rng);
+        let y = ExtensionFieldElement::random(&mut rng);
+        assert!(!x.vartime_eq(&y), "Two random elements should not collide");
+    }
+
+    #[test]
+    fn prime_field_element_is_square() {
+        fn square_is_square(x: PrimeFieldElement) -> bool {
+            x.square().is_square()
+        }
+        QuickCheck::new().max_tests(MAX_TESTS)
+                         .quickcheck(square_is_square as fn(PrimeFieldElement) -> bool);
+
+        // Since p = 3 mod 4, -1 is not a square in F_p.
+        assert!(!(-(&PrimeFieldElement::one())).is_square());
+        assert!(PrimeFieldElement::zero().is_square());
+    }
+
+    #[test]
+    fn lazy_reduction_matches_eager() {
+        fn difference_of_products(x: ExtensionFieldElement, y: ExtensionFieldElement, z: ExtensionFieldElement, w: ExtensionFieldElement) -> bool {
+            let eager = &(&x * &y) - &(&z * &w);
+            let lazy = (&x.mul_unreduced(&y) - &z.mul_unreduced(&w)).reduce();
+            let square = x.square_unreduced().reduce();
+            eager.vartime_eq(&lazy) && square.vartime_eq(&x.square())
+        }
+        QuickCheck::new().max_tests(MAX_TESTS)
+                         .quickcheck(difference_of_products as fn(ExtensionFieldElement, ExtensionFieldElement, ExtensionFieldElement, ExtensionFieldElement) -> bool);
+    }
+
+    #[test]
+    fn extension_field_element_is_square() {
+        fn square_is_square(x: ExtensionFieldElement) -> bool {
+            x.square().is_square()
+        }
+        QuickCheck::new().max_tests(MAX_TESTS)
+                         .quickcheck(square_is_square as fn(ExtensionFieldElement) -> bool);
+
+        // Every element of F_p is a square in F_{p^2}; in particular -1 = i^2.
+        assert!((-(&ExtensionFieldElement::one())).is_square());
+        // sage: (2 + 3*i).is_square()
+        // False
+        let two_plus_3i = ExtensionFieldElement::from_str_const("2", "3");
+        assert!(!two_plus_3i.is_square());
+    }
+
+    #[test]
+    fn extension_field_element_sqrt() {
+        fn square_root(x: ExtensionFieldElement) -> bool {
+            // Construct y = x^2 so we're sure y is square.
+            let y = x.square();
+            let z = y.sqrt();
+            // Now z = sqrt(y), so z^2 == y
+            z.square().vartime_eq(&y)
+        }
+        QuickCheck::new().max_tests(MAX_TESTS)
+                         .quickcheck(square_root as fn(ExtensionFieldElement) -> bool);
+
+        // Nonsquares of F_p have purely imaginary roots.
+        let minus_one = -(&ExtensionFieldElement::one());
+        assert!(minus_one.sqrt().square().vartime_eq(&minus_one));
+        assert!(ExtensionFieldElement::zero().sqrt().vartime_eq(&ExtensionFieldElement::zero()));
+    }
+
+    #[test]
+    fn extension_field_element_is_cube() {
+        fn cube_is_cube(x: ExtensionFieldElement) -> bool {
+            (&x.square() * &x).is_cube()
+        }
+        QuickCheck::new().max_tests(MAX_TESTS)
+                         .quickcheck(cube_is_cube as fn(ExtensionFieldElement) -> bool);
+
+        // sage: (2 + 3*i)^((p^2 - 1)/3) == 1
+        // False
+        let two_plus_3i = ExtensionFieldElement::from_str_const("2", "3");
+        assert!(!two_plus_3i.is_cube());
+        // Every element of F_p is a cube in F_{p^2}.
+        assert!(ExtensionFieldElement::from_u64(5).is_cube());
+    }
+
+    #[test]
+    fn cyclotomic_element_arithmetic_matches_extension_field() {
+        fn matches(x: CyclotomicElement) -> bool {
+            let x2 = x.A.square();
+            let one = CyclotomicElement::one();
+            x.square().A.vartime_eq(&x2) &&
+            x.cube().A.vartime_eq(&(&x2 * &x.A)) &&
+            (&x * &x.inv()).vartime_eq(&one)
+        }
+        QuickCheck::new().max_tests(MAX_TESTS)
+                         .quickcheck(matches as fn(CyclotomicElement) -> bool);
+    }
+
+    #[test]
+    fn cyclotomic_element_exp() {
+        fn exp_matches_pow(x: CyclotomicElement, e: u64) -> bool {
+            let mut y = ExtensionFieldElement::one();
+            for i in (0..64).rev() {
+                y = y.square();
+                if (e >> i) & 1 == 1 {
+                    y = &y * &x.A;
+                }
+            }
+            x.exp(&e.to_le_bytes()).A.vartime_eq(&y)
+        }
+        QuickCheck::new().max_tests(MAX_TESTS)
+                         .quickcheck(exp_matches_pow as fn(CyclotomicElement, u64) -> bool);
+
+        // x^(p+1) = 1, where p+1 = 2^372 * 3^239.
+        let x = CyclotomicElement::project(&ExtensionFieldElement::from_str_const("2", "3"));
+        assert!(x.pow2k(372).pow3k(239).vartime_eq(&CyclotomicElement::one()));
+    }
+
+    #[test]
+    fn cyclotomic_element_is_cube() {
+        fn cube_is_cube(x: CyclotomicElement) -> bool {
+            x.cube().is_cube()
+        }
+        QuickCheck::new().max_tests(MAX_TESTS)
+                         .quickcheck(cube_is_cube as fn(CyclotomicElement) -> bool);
+
+        let two_plus_3i = ExtensionFieldElement::from_str_const("2", "3");
+        assert!(!CyclotomicElement::project(&two_plus_3i).is_cube());
+    }
+
     #[test]
     fn fp751_element_conditional_swap() {
         let one: Fp751Element;
@@ -941,6 +1898,12 @@ mod bench {
         b.iter(|| mul751(&BENCH_X, &BENCH_Y, &mut z));
     }
 
+    #[bench]
+    fn fp751_sqr(b: &mut Bencher) {
+        let mut z = Fp751X2::zero();
+        b.iter(|| sqr751(&BENCH_X, &mut z));
+    }
+
     #[bench]
     fn fp751_rdc(b: &mut Bencher) {
         let mut z = Fp751Element::zero();
@@ -0,0 +1,228 @@
+// This file is part of sidh-rs.
+// Copyright (c) 2017 Erkan Tairi
+// See LICENSE for licensing information.
+//
+// Author:
+// - Erkan Tairi <erkan.tairi@gmail.com>
+//
+
+//! Fixed-base scalar multiplication by the SIDH bases `P_A` and `P_B` of
+//! `E_0(F_p)`, with comb tables precomputed at build time.
+//!
+//! The multiples are computed on the twisted Edwards curve
+//! `2x^2 + y^2 = 1 - 2x^2y^2` over `F_p`, which is birational to
+//! `E_0 : y^2 = x^3 + x` via `(u, v) -> (u/v, (u - 1)/(u + 1))`. As `a = 2` is a
+//! square and `d = -2` is not, its addition formulas are complete, so the comb
+//! needs no special cases for the identity or for doublings.
+//!
+//! This module also holds the tables `x([2^i]Q)` for `Q = \tau(P)` of the
+//! fixed-basis three-point ladder, which computes `x(P + [m]Q)` without any
+//! doublings.
+//
+// The tables follow the comb method of Lim and Lee: a scalar of 384 bits is
+// written as TEETH = 6 rows of SPACING = 64 bits, each split into BLOCKS = 2
+// blocks of BLOCK_SIZE = 32 bits, so that
+//
+//   [m]P = sum_c [2^c] sum_b T_b[u_(b,c)],
+//
+// where u_(b,c) has the bits m_(j*SPACING + b*BLOCK_SIZE + c) for the rows j, and
+// T_b[u] = sum_j u_j [2^(j*SPACING + b*BLOCK_SIZE)]P. This takes BLOCK_SIZE
+// doublings and BLOCKS*BLOCK_SIZE mixed additions, against 384 differential
+// additions and doublings for the Montgomery ladder. The entries are stored as
+// (x, y, dxy) for the mixed additions, and the table entry T_b[0] = O is left
+// out. The tables are written by build/fixed_base.rs.
+
+use field::PrimeFieldElement;
+use constants::*;
+
+use subtle::ConditionallySelectable;
+use subtle::ConstantTimeEq;
+use subtle::Choice;
+
+/// The largest scalar length in bytes handled by the comb tables.
+pub(crate) const FIXED_BASE_SCALAR_BYTES: usize = 48;
+
+// The comb parameters, as described above.
+const TEETH: usize = 6;
+const BLOCKS: usize = 2;
+const SPACING: usize = 64;
+const BLOCK_SIZE: usize = 32;
+const TABLE_SIZE: usize = (1 << TEETH) - 1;
+
+/// A point `(x, y)` on the twisted Edwards curve, with `dxy = d*x*y`.
+#[derive(Copy, Clone)]
+pub(crate) struct AffineEdwardsPoint {
+    x: PrimeFieldElement,
+    y: PrimeFieldElement,
+    dxy: PrimeFieldElement,
+}
+
+impl ConditionallySelectable for AffineEdwardsPoint {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        AffineEdwardsPoint{
+            x: PrimeFieldElement::conditional_select(&a.x, &b.x, choice),
+            y: PrimeFieldElement::conditional_select(&a.y, &b.y, choice),
+            dxy: PrimeFieldElement::conditional_select(&a.dxy, &b.dxy, choice)
+        }
+    }
+}
+
+/// The comb tables `T_b[u]` of a base point, for `0 < u < 2^TEETH`.
+pub(crate) type FixedBaseTable = [[AffineEdwardsPoint; TABLE_SIZE]; BLOCKS];
+
+include!(concat!(env!("OUT_DIR"), "/fixed_base_tables.rs"));
+
+// A point (X:Y:Z:T) on the twisted Edwards curve, with x = X/Z, y = Y/Z and
+// T = XY/Z.
+#[derive(Copy, Clone)]
+struct ExtendedEdwardsPoint {
+    X: PrimeFieldElement,
+    Y: PrimeFieldElement,
+    Z: PrimeFieldElement,
+    T: PrimeFieldElement,
+}
+
+impl ExtendedEdwardsPoint {
+    fn identity() -> ExtendedEdwardsPoint {
+        let one = PrimeFieldElement::one();
+        ExtendedEdwardsPoint{ X: PrimeFieldElement::zero(), Y: one, Z: one, T: PrimeFieldElement::zero() }
+    }
+    // Compute [2]P, with the doubling formulas of Hisil, Wong, Carter and Dawson
+    // for a = 2.
+    fn double(&self) -> ExtendedEdwardsPoint {
+        let a = self.X.square();                // = X^2
+        let b = self.Y.square();                // = Y^2
+        let mut c = self.Z.square();
+        c = &c + &c;                            // = 2Z^2
+        let d = &a + &a;                        // = aX^2
+        let e = &(&(&self.X + &self.Y).square() - &a) - &b; // = 2XY
+        let g = &d + &b;                        // = aX^2 + Y^2
+        let f = &g - &c;                        // = aX^2 + Y^2 - 2Z^2
+        let h = &d - &b;                        // = aX^2 - Y^2
+        ExtendedEdwardsPoint{ X: &e * &f, Y: &g * &h, Z: &f * &g, T: &e * &h }
+    }
+    // Compute P + Q for an affine Q, with the unified addition formulas of Hisil,
+    // Wong, Carter and Dawson for a = 2, which are complete on this curve.
+    fn add_affine(&self, Q: &AffineEdwardsPoint) -> ExtendedEdwardsPoint {
+        let a = &self.X * &Q.x;                 // = X_1 x_2
+        let b = &self.Y * &Q.y;                 // = Y_1 y_2
+        let c = &self.T * &Q.dxy;               // = d T_1 x_2 y_2
+        let e = &(&(&(&self.X + &self.Y) * &(&Q.x + &Q.y)) - &a) - &b; // = X_1 y_2 + Y_1 x_2
+        let f = &self.Z - &c;                   // = Z_1 - d T_1 x_2 y_2
+        let g = &self.Z + &c;                   // = Z_1 + d T_1 x_2 y_2
+        let h = &(&b - &a) - &a;                // = Y_1 y_2 - a X_1 x_2
+        ExtendedEdwardsPoint{ X: &e * &f, Y: &g * &h, Z: &f * &g, T: &e * &h }
+    }
+}
+
+/// Returns the comb table of `P = (affine_xP, affine_yP)` if it is one of the
+/// SIDH bases `P_A` and `P_B`. Takes variable time in the (public) base point.
+pub(crate) fn fixed_base_table(affine_xP: &PrimeFieldElement, affine_yP: &PrimeFieldElement) -> Option<&'static FixedBaseTable> {
+    if affine_xP.vartime_eq(&AFFINE_X_PA) && affine_yP.vartime_eq(&AFFINE_Y_PA) {
+        Some(&FIXED_BASE_TABLE_PA)
+    } else if affine_xP.vartime_eq(&AFFINE_X_PB) && affine_yP.vartime_eq(&AFFINE_Y_PB) {
+        Some(&FIXED_BASE_TABLE_PB)
+    } else {
+        None
+    }
+}
+
+/// Returns the affine `x([2^i]Q)` for `Q = \tau(P)` and `0 <= i < n`, if `P` is
+/// one of the SIDH bases `P_A` and `P_B` and the bits of a scalar of
+/// `scalar_len` bytes from `n` on can be skipped, either as `[2^n]Q = O` or as
+/// the scalar has at most `n` bits. Takes variable time in the (public) base
+/// point.
+pub(crate) fn fixed_basis_table(affine_xP: &PrimeFieldElement, scalar_len: usize) -> Option<&'static [PrimeFieldElement]> {
+    if affine_xP.vartime_eq(&AFFINE_X_PA) {
+        // Q_A has order 2^372, so the table stops at [2^371]Q_A.
+        Some(&FIXED_BASIS_TABLE_PA[..])
+    } else if affine_xP.vartime_eq(&AFFINE_X_PB) && 8 * scalar_len <= FIXED_BASIS_TABLE_PB.len() {
+        Some(&FIXED_BASIS_TABLE_PB[..])
+    } else {
+        None
+    }
+}
+
+/// Given the comb table of `P` and a scalar `m` of at most `FIXED_BASE_SCALAR_BYTES`
+/// little-endian bytes, compute `[m]P = (U : V : W)` on `E_0(F_p)`. The comb
+/// starts from the identity `(0 : l : l : 0)` for `l = blinding`, which must be
+/// nonzero.
+///
+/// This function's execution time is dependent only on the byte-length of the
+/// input scalar. The result is degenerate if `[m]P` is `O` or `(0,0)`.
+pub(crate) fn fixed_base_scalar_mul(table: &FixedBaseTable, scalar: &[u8], blinding: &PrimeFieldElement) ->
+                                   (PrimeFieldElement, PrimeFieldElement, PrimeFieldElement)
+{
+    assert!(scalar.len() <= FIXED_BASE_SCALAR_BYTES, "scalar is too long for the comb tables");
+    let bit = |k: usize| -> u8 {
+        if k / 8 < scalar.len() { (scalar[k / 8] >> (k % 8)) & 0x1 } else { 0 }
+    };
+
+    let mut R = ExtendedEdwardsPoint::identity();
+    R.Y = *blinding;
+    R.Z = *blinding;
+    for c in (0..BLOCK_SIZE).rev() {
+        R = R.double();
+        for (b, block) in table.iter().enumerate() {
+            let mut u: u8 = 0;
+            for j in 0..TEETH {
+                u |= bit(j * SPACING + b * BLOCK_SIZE + c) << j;
+            }
+            R = R.add_affine(&select(block, u));
+        }
+    }
+
+    // (u, v) = ((1 + y)/(1 - y), u/x) on E_0.
+    let ZpY = &R.Z + &R.Y;
+    let U = &ZpY * &R.X;                        // = (Z + Y)X
+    let V = &ZpY * &R.Z;                        // = (Z + Y)Z
+    let W = &(&R.Z - &R.Y) * &R.X;              // = (Z - Y)X
+    (U, V, W)
+}
+
+// Returns T[u - 1], or the identity if u = 0, in constant time.
+fn select(table: &[AffineEdwardsPoint; TABLE_SIZE], u: u8) -> AffineEdwardsPoint {
+    let mut T = AffineEdwardsPoint{ x: PrimeFieldElement::zero(), y: PrimeFieldElement::one(), dxy: PrimeFieldElement::zero() };
+    for (k, entry) in table.iter().enumerate() {
+        T.conditional_assign(entry, u.ct_eq(&((k + 1) as u8)));
+    }
+    T
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use curve::ProjectivePoint;
+    use rand::RngCore;
+
+    #[test]
+    fn fixed_base_versus_ladder() {
+        let mut rng = rand::thread_rng();
+        let bases = [(AFFINE_X_PA, AFFINE_Y_PA), (AFFINE_X_PB, AFFINE_Y_PB)];
+        for (affine_x, affine_y) in bases.iter() {
+            for len in [1, 47, 48].iter() {
+                let mut scalar = [0u8; FIXED_BASE_SCALAR_BYTES + 1];
+                rng.fill_bytes(&mut scalar[..*len]);
+                scalar[0] |= 1;
+                // The comb tables handle up to 48 bytes, and longer scalars use the ladder.
+                let xR = ProjectivePoint::secret_point(affine_x, affine_y, &scalar[..*len]);
+                let xR_ladder = ProjectivePoint::secret_point(affine_x, affine_y, &scalar[..]);
+                assert!(xR.vartime_eq(&xR_ladder));
+            }
+        }
+        let all_ones = [0xff; FIXED_BASE_SCALAR_BYTES];
+        let mut padded = [0xff; FIXED_BASE_SCALAR_BYTES + 1];
+        padded[FIXED_BASE_SCALAR_BYTES] = 0;
+        let xR = ProjectivePoint::secret_point(&AFFINE_X_PB, &AFFINE_Y_PB, &all_ones);
+        assert!(xR.vartime_eq(&ProjectivePoint::secret_point(&AFFINE_X_PB, &AFFINE_Y_PB, &padded)));
+    }
+
+    #[test]
+    fn fixed_base_tables_match_bases() {
+        for (table, affine_x) in [(&FIXED_BASE_TABLE_PA, AFFINE_X_PA), (&FIXED_BASE_TABLE_PB, AFFINE_X_PB)].iter() {
+            // T_0[1] = P, so that [1]P = (x_P : y_P : 1) on E_0.
+            let (U, _, W) = fixed_base_scalar_mul(table, &[1], &PrimeFieldElement::one());
+            assert!(U.vartime_eq(&(affine_x * &W)));
+        }
+    }
+}
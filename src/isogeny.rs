@@ -6,11 +6,20 @@
 // - Erkan Tairi <erkan.tairi@gmail.com>
 //
 
-//! This module contains internal isogeny representation and operations 
-//! for SIDH, which is not part of the public API.
+//! This module contains isogenies between Montgomery curves: the 2-, 3- and
+//! 4-isogenies used by SIDH, 8- and 9-isogenies as larger steps, odd-degree Vélu
+//! isogenies, and chains of them computed from a kernel point of order `2^n` or
+//! `3^n` on any curve, together with their duals.
 
 use field::{Fp751Element, ExtensionFieldElement};
 use curve::{ProjectiveCurveParameters, ProjectivePoint};
+use poly;
+
+use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "std")]
+use poly::{Polynomial, ProductTree};
+
+use heapless::Vec;
 
 /// Represents a 3-isogeny phi, holding the data necessary to evaluate phi.
 #[derive(Copy, Clone)]
@@ -53,19 +62,19 @@ impl ThreeIsogeny {
     /// phi.
     pub fn eval(&self, xP: &ProjectivePoint) -> ProjectivePoint {
         let phi = *self;
-        let mut t0 = &phi.X * &xP.X; // = X3*XP
-        let mut t1 = &phi.Z * &xP.Z; // = Z3*XP
-        let mut t2 = &t0 - &t1;      // = X3*XP - Z3*ZP
-        t0 = &phi.Z * &xP.X;         // = Z3*XP
-        t1 = &phi.X * &xP.Z;         // = X3*ZP
-        t0 = &t0 - &t1;              // = Z3*XP - X3*ZP
-        t2 = t2.square();            // = (X3*XP - Z3*ZP)^2
-        t0 = t0.square();            // = (Z3*XP - X3*ZP)^2
-        let x = &t2 * &xP.X;         // = XP*(X3*XP - Z3*ZP)^2
-        let z = &t0 * &xP.Z;         // = ZP*(Z3*XP - X3*ZP)^2
-        let xQ = ProjectivePoint{ X: x, Z: z };
+        // The differences of products are computed unreduced, and reduced once.
+        let mut t0 = phi.X.mul_unreduced(&xP.X);      // = X3*XP
+        let mut t1 = phi.Z.mul_unreduced(&xP.Z);      // = Z3*ZP
+        let mut t2 = (&t0 - &t1).reduce();            // = X3*XP - Z3*ZP
+        t0 = phi.Z.mul_unreduced(&xP.X);              // = Z3*XP
+        t1 = phi.X.mul_unreduced(&xP.Z);              // = X3*ZP
+        let mut t3 = (&t0 - &t1).reduce();            // = Z3*XP - X3*ZP
+        t2 = t2.square();                             // = (X3*XP - Z3*ZP)^2
+        t3 = t3.square();                             // = (Z3*XP - X3*ZP)^2
+        let x = &t2 * &xP.X;                          // = XP*(X3*XP - Z3*ZP)^2
+        let z = &t3 * &xP.Z;                          // = ZP*(Z3*XP - X3*ZP)^2
 
-        xQ
+        ProjectivePoint{ X: x, Z: z }
     }
 }
 
@@ -136,24 +145,24 @@ impl FourIsogeny {
         // X_Q = Xprime*( 16*(X_4 + Z_4)*(X_4 - Z_4)*X_4^2*Z_4^4 )
         // Z_Q = Zprime*( 16*(X_4 + Z_4)*(X_4 - Z_4)*X_4^2*Z_4^4 )
         //
-        let mut t0 = &xP.X * &phi.XZ2;          // = 2*X*X_4*Z_4
-        let mut t1 = &xP.Z * &phi.Xsq_plus_Zsq; // = (X_4^2 + Z_4^2)*Z
-        t0 = &t0 - &t1;                         // = -X_4^2*Z + 2*X*X_4*Z_4 - Z*Z_4^2
-        t1 = &xP.Z * &phi.Xsq_minus_Zsq;        // = (X_4^2 - Z_4^2)*Z
-        let mut t2 = (&t0 - &t1).square();      // = 4*(X_4*Z - X*Z_4)^2*X_4^2
+        // Differences of products are computed unreduced, and reduced once.
+        let mut t0 = (&xP.X.mul_unreduced(&phi.XZ2)
+                      - &xP.Z.mul_unreduced(&phi.Xsq_plus_Zsq)).reduce();
+                                                // = -X_4^2*Z + 2*X*X_4*Z_4 - Z*Z_4^2
+        let mut t1 = &xP.Z * &phi.Xsq_minus_Zsq; // = (X_4^2 - Z_4^2)*Z
+        let t2 = (&t0 - &t1).square();          // = 4*(X_4*Z - X*Z_4)^2*X_4^2
         t0 = &t0 * &t1;
         t0 = &t0 + &t0;
         t0 = &t0 + &t0;                         // = 4*(2*X*X_4*Z_4 - (X_4^2 + Z_4^2)*Z)*(X_4^2 - Z_4^2)*Z
         t1 = &t0 + &t2;                         // = 4*(X*X_4 - Z*Z_4)^2*Z_4^2
         t0 = &t0 * &t2;                         // = Zprime * 16*(X_4 + Z_4)*(X_4 - Z_4)*X_4^2
         let z = &t0 * &phi.Zpow4;               // = Zprime * 16*(X_4 + Z_4)*(X_4 - Z_4)*X_4^2*Z_4^4
-        t2 = &t2 * &phi.Zpow4;                  // = 4*(X_4*Z - X*Z_4)^2*X_4^2*Z_4^4
-        t0 = &t1 * &phi.Xpow4;                  // = 4*(X*X_4 - Z*Z_4)^2*X_4^4*Z_4^2
-        t0 = &t2 - &t0;                         // = -4*(X*X_4^2 - 2*X_4*Z*Z_4 + X*Z_4^2)*X*(X_4^2 - Z_4^2)*X_4^2*Z_4^2
+        t0 = (&t2.mul_unreduced(&phi.Zpow4)     // = 4*(X_4*Z - X*Z_4)^2*X_4^2*Z_4^4
+              - &t1.mul_unreduced(&phi.Xpow4)).reduce(); // - 4*(X*X_4 - Z*Z_4)^2*X_4^4*Z_4^2
+                                                // = -4*(X*X_4^2 - 2*X_4*Z*Z_4 + X*Z_4^2)*X*(X_4^2 - Z_4^2)*X_4^2*Z_4^2
         let x = &t1 * &t0;                      // = Xprime * 16*(X_4 + Z_4)*(X_4 - Z_4)*X_4^2*Z_4^4
-        let xQ = ProjectivePoint{ X: x, Z: z };
 
-        xQ
+        ProjectivePoint{ X: x, Z: z }
     }
 }
 
@@ -204,15 +213,1171 @@ impl FirstFourIsogeny {
         t1 = &phi.C + &phi.C;                  // = 2*C
         t1 = &t1 - &phi.A;                     // = 2*C - A
         let z = &t1 * &t0;                     // = (2*C - A)*X*Z*(X-Z)^2
-        let xQ = ProjectivePoint{ X: x, Z: z };
 
-        xQ
+        ProjectivePoint{ X: x, Z: z }
+    }
+}
+
+/// Represents a 2-isogeny phi whose kernel is not `<(0,0)>`, holding the data
+/// necessary to evaluate phi.
+#[derive(Copy, Clone)]
+pub struct TwoIsogeny {
+    pub X: ExtensionFieldElement,
+    pub Z: ExtensionFieldElement,
+}
+
+impl TwoIsogeny {
+    /// Given a two-torsion point `x2 = x(P_2) != (0 : 1)` on the curve `E_(A:C)`,
+    /// construct the two-isogeny `phi : E_(A:C) -> E_(A:C)/<P_2> = E_(A':C')`.
+    ///
+    /// Returns a tuple `(codomain, isogeny) = (E_(A':C'), phi)`.
+    //
+    // Renes: for x2 = (X2 : Z2), (A':C') = (2(Z2^2 - 2X2^2) : Z2^2).
+    pub fn compute_two_isogeny(x2: &ProjectivePoint) -> (ProjectiveCurveParameters, TwoIsogeny) {
+        let isogeny = TwoIsogeny{ X: x2.X, Z: x2.Z };
+        let v0 = x2.X.square();          // = X2^2
+        let c = x2.Z.square();           // = Z2^2
+        let mut a = &v0 + &v0;           // = 2X2^2
+        a = &c - &a;                     // = Z2^2 - 2X2^2
+        a = &a + &a;                     // = 2(Z2^2 - 2X2^2)
+        let codomain = ProjectiveCurveParameters{ A: a, C: c };
+
+        (codomain, isogeny)
+    }
+    /// Given a 2-isogeny phi and a point `xP = x(P)`, compute `x(Q)`, the x-coordinate
+    /// of the image `Q = phi(P)` of `P` under `phi : E_(A:C) -> E_(A':C')`.
+    ///
+    /// The output `xQ = x(Q)` is then a point on the curve `E_(A':C')`; the curve
+    /// parameters are returned by the compute_two_isogeny function used to construct
+    /// phi.
+    pub fn eval(&self, xP: &ProjectivePoint) -> ProjectivePoint {
+        let phi = *self;
+        // The differences of products are computed unreduced, and reduced once.
+        let mut t0 = phi.X.mul_unreduced(&xP.X);      // = X2*XP
+        let mut t1 = phi.Z.mul_unreduced(&xP.Z);      // = Z2*ZP
+        let t2 = (&t0 - &t1).reduce();                // = X2*XP - Z2*ZP
+        t0 = phi.Z.mul_unreduced(&xP.X);              // = Z2*XP
+        t1 = phi.X.mul_unreduced(&xP.Z);              // = X2*ZP
+        let t3 = (&t0 - &t1).reduce();                // = Z2*XP - X2*ZP
+        let x = &t2 * &xP.X;                          // = XP*(X2*XP - Z2*ZP)
+        let z = &t3 * &xP.Z;                          // = ZP*(Z2*XP - X2*ZP)
+
+        ProjectivePoint{ X: x, Z: z }
+    }
+}
+
+/// Represents the 2-isogeny phi with kernel `<(0,0)>`, holding the data necessary
+/// to evaluate phi.
+//
+// See compute_zero_two_isogeny for more details.
+#[derive(Copy, Clone)]
+pub struct ZeroTwoIsogeny {
+    pub C: ExtensionFieldElement,
+    pub B: ExtensionFieldElement,
+    pub D: ExtensionFieldElement,
+}
+
+impl ZeroTwoIsogeny {
+    /// Construct the two-isogeny `phi : E_(A:C) -> E_(A:C)/<(0,0)> = E_(A':C')`.
+    ///
+    /// Returns a tuple `(codomain, isogeny) = (E_(A':C'), phi)`. Unlike the other
+    /// isogenies, this takes a square root, and runs in variable time.
+    //
+    // With a = A/C, u = x + 1/x maps E_(A:C) to v^2 = (u + a)(u - 2)(u + 2). Moving
+    // a root r of the right-hand side to zero gives a curve v^2 = w(w^2 + a'w + s^2),
+    // which is E_(a'/s) after scaling w by s, for
+    //
+    //   r = 2:  a' = a + 6, s^2 = 4(a + 2),
+    //   r = -2: a' = a - 6, s^2 = 4(2 - a),
+    //   r = -a: a' = -2a,   s^2 = a^2 - 4.
+    //
+    // The product of the three s^2 is -16(a^2 - 4)^2, a square in F_{p^2}, so at
+    // least one of them is a square. Then (X : Z) maps to (C(X^2 + Z^2) + BXZ : DXZ),
+    // with B = -2C, 2C or A and D = Cs.
+    pub fn compute_zero_two_isogeny(domain: &ProjectiveCurveParameters) -> (ProjectiveCurveParameters, ZeroTwoIsogeny) {
+        let C = domain.C;
+        let C2 = &C + &C;                              // = 2C
+        let C4 = &C2 + &C2;                            // = 4C
+        let C6 = &C4 + &C2;                            // = 6C
+        let t0 = &C * &(&domain.A + &C2);              // = C(A + 2C) = C^2 (a + 2)
+        let t1 = &C * &(&C2 - &domain.A);              // = C(2C - A) = C^2 (2 - a)
+        let (a, B, D) = if t0.is_square() {
+            let t = t0.sqrt();
+            (&domain.A + &C6, -(&C2), &t + &t)         // = (A + 6C, -2C, 2C sqrt(a + 2))
+        } else if t1.is_square() {
+            let t = t1.sqrt();
+            (&domain.A - &C6, C2, &t + &t)             // = (A - 6C, 2C, 2C sqrt(2 - a))
+        } else {
+            let t = (&domain.A.square() - &C2.square()).sqrt();
+            (-(&(&domain.A + &domain.A)), domain.A, t) // = (-2A, A, C sqrt(a^2 - 4))
+        };
+        let codomain = ProjectiveCurveParameters{ A: a, C: D };
+        let isogeny = ZeroTwoIsogeny{ C, B, D };
+
+        (codomain, isogeny)
+    }
+    /// Given a 2-isogeny phi and a point `xP = x(P)`, compute `x(Q)`, the x-coordinate
+    /// of the image `Q = phi(P)` of `P` under `phi : E_(A:C) -> E_(A':C')`.
+    ///
+    /// The output `xQ = x(Q)` is then a point on the curve `E_(A':C')`; the curve
+    /// parameters are returned by the compute_zero_two_isogeny function used to
+    /// construct phi.
+    pub fn eval(&self, xP: &ProjectivePoint) -> ProjectivePoint {
+        let phi = *self;
+        let t0 = &xP.X * &xP.Z;                          // = X*Z
+        let t1 = &xP.X.square() + &xP.Z.square();        // = X^2 + Z^2
+        let x = &(&phi.C * &t1) + &(&phi.B * &t0);      // = C(X^2 + Z^2) + B*X*Z
+        let z = &phi.D * &t0;                            // = D*X*Z
+
+        ProjectivePoint{ X: x, Z: z }
+    }
+}
+
+/// Represents an 8-isogeny phi whose kernel does not contain `(0,0)`, holding the
+/// data necessary to evaluate phi.
+//
+// See compute_eight_isogeny for more details.
+#[derive(Copy, Clone)]
+pub struct EightIsogeny {
+    pub four: FourIsogeny,
+    pub two: TwoIsogeny,
+}
+
+impl EightIsogeny {
+    /// Given an eight-torsion point `x8 = x(P_8)` on the curve `E_(A:C)`, such that
+    /// `(0,0)` is not in `<P_8>`, construct the eight-isogeny `phi : E_(A:C) ->
+    /// E_(A:C)/<P_8> = E_(A':C')`.
+    ///
+    /// Returns a tuple `(codomain, isogeny) = (E_(A':C'), phi)`.
+    //
+    // phi is the four-isogeny with kernel <[2]P_8> followed by the two-isogeny with
+    // kernel generated by the image of P_8, so one step of a strategy walk replaces
+    // three levels of two-isogenies.
+    pub fn compute_eight_isogeny(domain: &ProjectiveCurveParameters, x8: &ProjectivePoint) -> (ProjectiveCurveParameters, EightIsogeny) {
+        let (_, four) = FourIsogeny::compute_four_isogeny(&x8.pow2k(domain, 1));
+        let (codomain, two) = TwoIsogeny::compute_two_isogeny(&four.eval(x8));
+
+        (codomain, EightIsogeny{ four, two })
+    }
+    /// Given an 8-isogeny phi and a point `xP = x(P)`, compute `x(Q)`, the x-coordinate
+    /// of the image `Q = phi(P)` of `P` under `phi : E_(A:C) -> E_(A':C')`.
+    ///
+    /// The output `xQ = x(Q)` is then a point on the curve `E_(A':C')`; the curve
+    /// parameters are returned by the compute_eight_isogeny function used to construct
+    /// phi.
+    pub fn eval(&self, xP: &ProjectivePoint) -> ProjectivePoint {
+        self.two.eval(&self.four.eval(xP))
+    }
+}
+
+/// The largest number `(l-1)/2` of kernel points a `VeluIsogeny` can hold without
+/// std, so that its degree is at most `l = 257`. With std, the kernel points are
+/// kept on the heap, and the degree is not limited.
+pub const MAX_VELU_KERNEL_POINTS: usize = 128;
+
+#[cfg(feature = "std")]
+type VeluKernel = ::std::vec::Vec<(ExtensionFieldElement, ExtensionFieldElement)>;
+#[cfg(not(feature = "std"))]
+type VeluKernel = Vec<(ExtensionFieldElement, ExtensionFieldElement), MAX_VELU_KERNEL_POINTS>;
+
+/// Represents an isogeny phi of odd degree `l` with cyclic kernel `<K>`, holding
+/// the data necessary to evaluate phi.
+//
+// See compute_velu_isogeny for more details.
+#[derive(Clone)]
+pub struct VeluIsogeny {
+    // (X_i + Z_i, X_i - Z_i) for x([i]K) = (X_i : Z_i), 1 <= i <= (l-1)/2.
+    kernel: VeluKernel,
+}
+
+impl VeluIsogeny {
+    /// Given `xK = x(K)` for a point `K` of odd order `l` on the curve `E_(A:C)`,
+    /// construct the isogeny `phi : E_(A:C) -> E_(A:C)/<K> = E_(A':C')`. The order
+    /// `l` does not need to be prime, but `<K>` must be cyclic of order `l`.
+    ///
+    /// Returns a tuple `(codomain, isogeny) = (E_(A':C'), phi)`. Panics if `l` is
+    /// even, less than 3, or, without std, larger than `2*MAX_VELU_KERNEL_POINTS + 1`.
+    //
+    // The codomain is computed on the twisted Edwards curve `(A + 2C)x^2 + y^2 = 1 +
+    // (A - 2C)x^2y^2` birational to E_(A:C), where the Edwards y-coordinate of [i]K
+    // is (Y_i : Z_i) = (X_i - Z_i : X_i + Z_i). By Moody-Shumow and Meyer-Reith,
+    // the codomain is the Edwards curve with a' = a^l (prod Z_i)^8 and
+    // d' = d^l (prod Y_i)^8, and so (A':C') = (2(a' + d') : a' - d'). This is the
+    // projective form of the Costello-Hisil formula A' = pi^2 (A/C - 6sigma + 6tau),
+    // with pi, sigma and tau the product and sums of the x([i]K) and their inverses.
+    pub fn compute_velu_isogeny(domain: &ProjectiveCurveParameters, xK: &ProjectivePoint, degree: usize) ->
+                               (ProjectiveCurveParameters, VeluIsogeny)
+    {
+        assert!(degree % 2 == 1 && degree >= 3, "Velu isogenies must have odd degree at least 3");
+        let s = (degree - 1) / 2;
+        assert!(cfg!(feature = "std") || s <= MAX_VELU_KERNEL_POINTS, "Velu isogeny degree is too large");
+
+        // x([i]K) for 1 <= i <= s, as x([i+1]K) = xADD(x([i]K), x(K), x([i-1]K)).
+        let mut kernel = VeluKernel::new();
+        let mut xPrev = *xK;
+        let mut xCur = xK.pow2k(domain, 1);
+        kernel.extend(Some((&xK.X + &xK.Z, &xK.X - &xK.Z)));
+        for _ in 1..s {
+            kernel.extend(Some((&xCur.X + &xCur.Z, &xCur.X - &xCur.Z)));
+            let xNext = xCur.add(xK, &xPrev);
+            xPrev = xCur;
+            xCur = xNext;
+        }
+
+        let codomain = velu_codomain(domain, &kernel, degree);
+
+        (codomain, VeluIsogeny{ kernel })
+    }
+    /// Returns the degree `l` of phi.
+    pub fn degree(&self) -> usize {
+        2 * self.kernel.len() + 1
+    }
+    /// Given an isogeny phi and a point `xP = x(P)`, compute `x(Q)`, the x-coordinate
+    /// of the image `Q = phi(P)` of `P` under `phi : E_(A:C) -> E_(A':C')`.
+    ///
+    /// The output `xQ = x(Q)` is then a point on the curve `E_(A':C')`; the curve
+    /// parameters are returned by the compute_velu_isogeny function used to construct
+    /// phi.
+    //
+    // Cost: (4M+2a)(l-1)/2 + 2M+2S+2a
+    pub fn eval(&self, xP: &ProjectivePoint) -> ProjectivePoint {
+        velu_eval(&self.kernel, xP)
+    }
+}
+
+/// Represents a 9-isogeny phi with cyclic kernel, holding the data necessary to
+/// evaluate phi.
+//
+// See compute_nine_isogeny for more details.
+#[derive(Copy, Clone)]
+pub struct NineIsogeny {
+    // (X_i + Z_i, X_i - Z_i) for x([i]K) = (X_i : Z_i), 1 <= i <= 4.
+    kernel: [(ExtensionFieldElement, ExtensionFieldElement); 4],
+}
+
+impl NineIsogeny {
+    /// Given a nine-torsion point `x9 = x(P_9)` on the curve `E_(A:C)`, construct the
+    /// nine-isogeny `phi : E_(A:C) -> E_(A:C)/<P_9> = E_(A':C')`.
+    ///
+    /// Returns a tuple `(codomain, isogeny) = (E_(A':C'), phi)`.
+    //
+    // This is a VeluIsogeny of degree 9 held inline, so that it can be a step of
+    // an IsogenyChain. The codomain is the same as that of the two three-isogenies
+    // with kernels <[3]P_9> and the image of <P_9>.
+    pub fn compute_nine_isogeny(domain: &ProjectiveCurveParameters, x9: &ProjectivePoint) -> (ProjectiveCurveParameters, NineIsogeny) {
+        let x2 = x9.pow2k(domain, 1);           // = x([2]P_9)
+        let x3 = x2.add(x9, x9);                // = x([3]P_9)
+        let x4 = x2.pow2k(domain, 1);           // = x([4]P_9)
+        let mut kernel = [(ExtensionFieldElement::zero(), ExtensionFieldElement::zero()); 4];
+        for (k, xS) in kernel.iter_mut().zip([x9, &x2, &x3, &x4].iter()) {
+            *k = (&xS.X + &xS.Z, &xS.X - &xS.Z);
+        }
+        let codomain = velu_codomain(domain, &kernel, 9);
+
+        (codomain, NineIsogeny{ kernel })
+    }
+    /// Given a 9-isogeny phi and a point `xP = x(P)`, compute `x(Q)`, the x-coordinate
+    /// of the image `Q = phi(P)` of `P` under `phi : E_(A:C) -> E_(A':C')`.
+    ///
+    /// The output `xQ = x(Q)` is then a point on the curve `E_(A':C')`; the curve
+    /// parameters are returned by the compute_nine_isogeny function used to construct
+    /// phi.
+    //
+    // Cost: 18M+2S+10a
+    pub fn eval(&self, xP: &ProjectivePoint) -> ProjectivePoint {
+        velu_eval(&self.kernel, xP)
+    }
+}
+
+/// The degree from which `OddIsogeny::compute_odd_isogeny` switches from classical
+/// Vélu to square-root Vélu.
+//
+// Measured with the *_compute_and_eval_degree_* benches, i.e. computing the
+// isogeny and pushing one point through it: classical Velu is about 1.4 times
+// faster at degree 257 and slightly faster at degree 1023, the two break even
+// around degree 2047, and square-root Velu is about 1.2 times faster at degree
+// 4095.
+pub const SQRT_VELU_THRESHOLD: usize = 2047;
+
+/// Represents an isogeny phi of odd degree `l >= 5` with cyclic kernel `<K>`,
+/// evaluated with the square-root Vélu algorithm of Bernstein, De Feo, Leroux and
+/// Smith, rather than in the `O(l)` operations of `VeluIsogeny`.
+///
+/// With the Karatsuba multiplication of `poly`, computing phi and evaluating it
+/// each take about `O(l^0.8)` operations, rather than the `O~(sqrt(l))` of fast
+/// multiplication, so this only pays off for large `l`; see `SQRT_VELU_THRESHOLD`.
+//
+// See compute_sqrt_velu_isogeny for more details.
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct SqrtVeluIsogeny {
+    degree: usize,
+    // The subproduct tree of prod (W - x_i), i in I.
+    tree_I: ProductTree,
+    // (x_j^2, -2x_j, -2(x_j^2 + 2a x_j + 1)) for j in J, with a = A/C.
+    coeffs_J: ::std::vec::Vec<(ExtensionFieldElement, ExtensionFieldElement, ExtensionFieldElement)>,
+    // x_k for k in K.
+    xs_K: ::std::vec::Vec<ExtensionFieldElement>,
+}
+
+#[cfg(feature = "std")]
+impl SqrtVeluIsogeny {
+    /// Given `xK = x(K)` for a point `K` of odd order `l` on the curve `E_(A:C)`,
+    /// construct the isogeny `phi : E_(A:C) -> E_(A:C)/<K> = E_(A':C')`. The order
+    /// `l` does not need to be prime, but `<K>` must be cyclic of order `l`.
+    ///
+    /// Returns a tuple `(codomain, isogeny) = (E_(A':C'), phi)`. Panics if `l` is
+    /// even or less than 5.
+    //
+    // Both the codomain and the images are given by h_S(alpha) = prod (alpha - x_s),
+    // where x_s = x([s]K) for s in S = {1, 3, ..., l-2}; see compute_velu_isogeny
+    // and eval. With b = floor(sqrt(l-1)/2) and b' = floor((l-1)/4b), S is split as
+    // (I + J) u (I - J) u K with I = {2b(2i+1) : 0 <= i < b'}, J = {1, 3, ..., 2b-1}
+    // and K the remaining odd s > 4bb', where x_s = x_(l-s) for the even l-s. Then
+    //
+    //   h_S(alpha) = Res_W(prod_i (W - x_i), E_J(alpha)(W)) h_K(alpha) / Delta,
+    //
+    // with E_J(alpha)(W) = prod_j (F0(W, x_j) alpha^2 + F1(W, x_j) alpha + F2(W, x_j))
+    // the biquadratic relation of x_(i+j) and x_(i-j), and Delta = Res_W(h_I, E_J(oo))
+    // a constant which cancels in every ratio we need. The resultant is computed by
+    // evaluating E_J(alpha) at the x_i with a product tree.
+    pub fn compute_sqrt_velu_isogeny(domain: &ProjectiveCurveParameters, xK: &ProjectivePoint, degree: usize) ->
+                                    (ProjectiveCurveParameters, SqrtVeluIsogeny)
+    {
+        assert!(degree % 2 == 1 && degree >= 5, "square-root Velu isogenies must have odd degree at least 5");
+        let b = isqrt(degree - 1) / 2;
+        let b_prime = (degree - 1) / (4 * b);
+        let k_max = degree - 1 - 4 * b * b_prime;
+
+        // x([m]K) for 1 <= m <= max(2b, k_max), then x([2b(2i+1)]K) for i in I,
+        // as x([m+n]K) = xADD(x([m]K), x([n]K), x([m-n]K)).
+        let m_max = if 2 * b > k_max { 2 * b } else { k_max };
+        let mut multiples = ::std::vec::Vec::with_capacity(m_max + b_prime + 1);
+        multiples.push(*xK);
+        multiples.push(xK.pow2k(domain, 1));
+        for m in 2..m_max {
+            let xNext = multiples[m - 1].add(xK, &multiples[m - 2]);
+            multiples.push(xNext);
+        }
+        let x2b = multiples[2 * b - 1];
+        let x4b = x2b.pow2k(domain, 1);
+        let mut xPrev = x2b;
+        let mut xCur = x2b;
+        for i in 0..b_prime {
+            multiples.push(xCur);
+            let xNext = if i == 0 { x4b.add(&x2b, &x2b) } else { xCur.add(&x4b, &xPrev) };
+            xPrev = xCur;
+            xCur = xNext;
+        }
+        // Normalise a = A/C along with the x([m]K).
+        multiples.push(ProjectivePoint{ X: domain.A, Z: domain.C });
+        let mut xs = batch_to_affine(&multiples);
+        let a = xs.pop().unwrap();
+
+        let xs_I = &xs[m_max..];
+        let coeffs_J = (0..b).map(|j| {
+            let x = &xs[2 * j];
+            let x2 = x.square();
+            let t = &(&x2 + &ExtensionFieldElement::one()) + &(&(&a + &a) * x);
+            (x2, -(&(x + x)), -(&(&t + &t)))
+        }).collect();
+        let xs_K = (1..=k_max / 2).map(|k| xs[2 * k - 1]).collect();
+        let tree_I = ProductTree::with_precision(xs_I, (2 * b + 1).saturating_sub(b_prime));
+        let isogeny = SqrtVeluIsogeny{ degree, tree_I, coeffs_J, xs_K };
+
+        // The codomain only depends on prod Z_i ~ h_S(-1) and prod Y_i ~ h_S(1).
+        let one = ExtensionFieldElement::one();
+        let m_one = -(&one);
+        let h_m1 = isogeny.h_S(&isogeny.E_J(&m_one, &one), &m_one, &one);
+        let h_1 = isogeny.h_S(&isogeny.E_J(&one, &one), &one, &one);
+        let codomain = velu_codomain(domain, &[(h_m1, h_1)], degree);
+
+        (codomain, isogeny)
+    }
+    /// Returns the degree `l` of phi.
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+    /// Given an isogeny phi and a point `xP = x(P)`, compute `x(Q)`, the x-coordinate
+    /// of the image `Q = phi(P)` of `P` under `phi : E_(A:C) -> E_(A':C')`.
+    ///
+    /// The output `xQ = x(Q)` is then a point on the curve `E_(A':C')`; the curve
+    /// parameters are returned by the compute_sqrt_velu_isogeny function used to
+    /// construct phi.
+    //
+    // As in VeluIsogeny::eval, (X' : Z') = (X (Delta h_S(Z : X))^2 : Z (Delta h_S(X : Z))^2).
+    //
+    // The quadratic factor of E_J(X : Z) for j is (u x_j^2 + v(-2x_j) + w) +
+    // (u(-2x_j) + v e_j + w(-2x_j)) W + (u + v(-2x_j) + w x_j^2) W^2, with u = X^2,
+    // v = XZ and w = Z^2, so swapping X and Z reverses it, and E_J(Z : X) is the
+    // reverse of E_J(X : Z). Both resultants need their own descent of the product
+    // tree of h_I, which is most of the cost.
+    pub fn eval(&self, xP: &ProjectivePoint) -> ProjectivePoint {
+        let E_J = self.E_J(&xP.X, &xP.Z);
+        let E_J_rev: Polynomial = E_J.iter().rev().cloned().collect();
+        let h = self.h_S(&E_J, &xP.X, &xP.Z);
+        let h_rev = self.h_S(&E_J_rev, &xP.Z, &xP.X);
+
+        ProjectivePoint{ X: &xP.X * &h_rev.square(), Z: &xP.Z * &h.square() }
+    }
+    // Compute E_J(X : Z), homogenised in (X : Z).
+    fn E_J(&self, X: &ExtensionFieldElement, Z: &ExtensionFieldElement) -> Polynomial {
+        let u = X.square();
+        let v = X * Z;
+        let w = Z.square();
+        let factors: ::std::vec::Vec<Polynomial> = self.coeffs_J.iter().map(|(c, d, e)| {
+            let vd = &v * d;
+            ::std::vec![&(&(&u * c) + &vd) + &w, &(&(&u + &w) * d) + &(&v * e), &(&u + &vd) + &(&w * c)]
+        }).collect();
+        poly::product(&factors)
+    }
+    // Compute Delta h_S(X : Z), homogenised in (X : Z), given E_J(X : Z).
+    fn h_S(&self, E_J: &[ExtensionFieldElement], X: &ExtensionFieldElement, Z: &ExtensionFieldElement) -> ExtensionFieldElement {
+        self.xs_K.iter().fold(self.tree_I.product_of_values(E_J), |h, x| &h * &(X - &(x * Z)))
+    }
+}
+
+/// An isogeny of odd degree, evaluated with classical Vélu or, from degree
+/// `SQRT_VELU_THRESHOLD` on and with std, with square-root Vélu.
+// VeluIsogeny stays unboxed, so that OddIsogeny also works without std.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone)]
+pub enum OddIsogeny {
+    Velu(VeluIsogeny),
+    #[cfg(feature = "std")]
+    SqrtVelu(SqrtVeluIsogeny),
+}
+
+impl OddIsogeny {
+    /// Given `xK = x(K)` for a point `K` of odd order `l` on the curve `E_(A:C)`,
+    /// construct the isogeny `phi : E_(A:C) -> E_(A:C)/<K> = E_(A':C')` with
+    /// whichever of `VeluIsogeny` and `SqrtVeluIsogeny` is faster for `l`.
+    ///
+    /// Returns a tuple `(codomain, isogeny) = (E_(A':C'), phi)`.
+    pub fn compute_odd_isogeny(domain: &ProjectiveCurveParameters, xK: &ProjectivePoint, degree: usize) ->
+                              (ProjectiveCurveParameters, OddIsogeny)
+    {
+        #[cfg(feature = "std")]
+        {
+            if degree >= SQRT_VELU_THRESHOLD {
+                let (codomain, phi) = SqrtVeluIsogeny::compute_sqrt_velu_isogeny(domain, xK, degree);
+                return (codomain, OddIsogeny::SqrtVelu(phi));
+            }
+        }
+        let (codomain, phi) = VeluIsogeny::compute_velu_isogeny(domain, xK, degree);
+        (codomain, OddIsogeny::Velu(phi))
+    }
+    /// Returns the degree `l` of phi.
+    pub fn degree(&self) -> usize {
+        match self {
+            OddIsogeny::Velu(phi) => phi.degree(),
+            #[cfg(feature = "std")]
+            OddIsogeny::SqrtVelu(phi) => phi.degree(),
+        }
+    }
+    /// Given an isogeny phi and a point `xP = x(P)`, compute `x(Q)`, the x-coordinate
+    /// of the image `Q = phi(P)` of `P` under `phi : E_(A:C) -> E_(A':C')`.
+    pub fn eval(&self, xP: &ProjectivePoint) -> ProjectivePoint {
+        match self {
+            OddIsogeny::Velu(phi) => phi.eval(xP),
+            #[cfg(feature = "std")]
+            OddIsogeny::SqrtVelu(phi) => phi.eval(xP),
+        }
+    }
+}
+
+/// Represents an isomorphism `x -> (x - r)/s` between two Montgomery curves, up to
+/// a quadratic twist, holding the data necessary to evaluate it.
+//
+// See compute_isomorphisms for more details.
+#[derive(Copy, Clone, Debug)]
+pub struct MontgomeryIsomorphism {
+    pub r: ExtensionFieldElement,
+    pub s: ExtensionFieldElement,
+}
+
+impl MontgomeryIsomorphism {
+    /// Compute the isomorphisms `iota : E_(A:C) -> E_(A':C')` of x-coordinates
+    /// between the two given curves. The list is empty if the curves are not
+    /// isomorphic, and has more than one entry if the curves have automorphisms
+    /// other than `-1`, as `E_0` does.
+    ///
+    /// Takes variable time.
+    //
+    // With a = A/C, the isomorphisms x' = (x - r)/s from E_a to another Montgomery
+    // curve move a root r of f(x) = x^3 + ax^2 + x to zero, and need s^2 = f'(r) =
+    // 3r^2 + 2ar + 1 for the coefficient of x' to be 1. The image is then E_a' for
+    // a' = (3r + a)/s.
+    pub fn compute_isomorphisms(domain: &ProjectiveCurveParameters, codomain: &ProjectiveCurveParameters) ->
+                                Vec<MontgomeryIsomorphism, 6>
+    {
+        let a = &domain.A * &domain.C.inv();
+        let a_prime = &codomain.A * &codomain.C.inv();
+        let one = ExtensionFieldElement::one();
+        let half = ExtensionFieldElement::from_u64(2).inv();
+
+        let mut roots: Vec<ExtensionFieldElement, 3> = Vec::new();
+        roots.push(ExtensionFieldElement::zero()).unwrap();
+        let disc = &a.square() - &ExtensionFieldElement::from_u64(4);  // = a^2 - 4
+        if disc.is_square() {
+            let t = disc.sqrt();
+            roots.push(&(&t - &a) * &half).unwrap();                     // = (-a + sqrt(a^2 - 4))/2
+            roots.push(&(-(&(&t + &a))) * &half).unwrap();               // = (-a - sqrt(a^2 - 4))/2
+        }
+
+        let mut isomorphisms = Vec::new();
+        for r in roots.iter() {
+            let r3 = &(r + r) + r;                                       // = 3r
+            let f = &(&(&(&r3 + &a) + &a) * r) + &one;                   // = 3r^2 + 2ar + 1
+            if !f.is_square() {
+                continue;
+            }
+            let t = f.sqrt();
+            for s in [t, -(&t)].iter() {
+                if (&(&r3 + &a) * &s.inv()).vartime_eq(&a_prime) {
+                    isomorphisms.push(MontgomeryIsomorphism{ r: *r, s: *s }).unwrap();
+                }
+            }
+        }
+        isomorphisms
+    }
+    /// Given an isomorphism iota and a point `xP = x(P)`, compute `x(iota(P))`.
+    pub fn eval(&self, xP: &ProjectivePoint) -> ProjectivePoint {
+        let x = &xP.X - &(&self.r * &xP.Z);    // = X - rZ
+        let z = &self.s * &xP.Z;               // = sZ
+
+        ProjectivePoint{ X: x, Z: z }
+    }
+}
+
+/// The largest number of isogenies in the chains built from `balanced_strategy`,
+/// i.e. by `two_power_isogeny` and `three_power_isogeny`.
+pub const MAX_ISOGENY_CHAIN_LENGTH: usize = 240;
+
+// The largest number of intermediate points kept while walking a strategy.
+const MAX_INT_POINTS: usize = 10;
+
+/// One isogeny of an isogeny walk or an `IsogenyChain`.
+// The walks hand each step to the recording by value, so steps are not boxed.
+#[allow(clippy::large_enum_variant)]
+#[derive(Copy, Clone)]
+pub enum IsogenyStep {
+    Two(TwoIsogeny),
+    ZeroTwo(ZeroTwoIsogeny),
+    FirstFour(FirstFourIsogeny),
+    /// The `FirstFourIsogeny` of `E_(-A:C)`, after the isomorphism `x -> -x` from
+    /// `E_(A:C)`, for the four-isogeny of `E_(A:C)` with kernel `<(-1,...)>`.
+    NegatedFirstFour(FirstFourIsogeny),
+    Four(FourIsogeny),
+    Eight(EightIsogeny),
+    Three(ThreeIsogeny),
+    Nine(NineIsogeny),
+    /// An isomorphism, closing the chain of a dual isogeny.
+    Isomorphism(MontgomeryIsomorphism),
+}
+
+impl IsogenyStep {
+    /// Given a point `xP = x(P)`, compute `x(phi(P))` for the isogeny phi of this step.
+    pub fn eval(&self, xP: &ProjectivePoint) -> ProjectivePoint {
+        match self {
+            IsogenyStep::Two(phi) => phi.eval(xP),
+            IsogenyStep::ZeroTwo(phi) => phi.eval(xP),
+            IsogenyStep::FirstFour(phi) => phi.eval(xP),
+            IsogenyStep::NegatedFirstFour(phi) => phi.eval(&ProjectivePoint{ X: -(&xP.X), Z: xP.Z }),
+            IsogenyStep::Four(phi) => phi.eval(xP),
+            IsogenyStep::Eight(phi) => phi.eval(xP),
+            IsogenyStep::Three(phi) => phi.eval(xP),
+            IsogenyStep::Nine(phi) => phi.eval(xP),
+            IsogenyStep::Isomorphism(iota) => iota.eval(xP),
+        }
+    }
+    /// Returns the degree of the isogeny of this step.
+    pub fn degree(&self) -> usize {
+        let (l, k) = self.prime_power();
+        l.pow(k)
+    }
+    /// Given the `domain` and `codomain` of the isogeny phi of this step, and
+    /// `xQ = x(Q)` for a point `Q` on the domain such that `Q` and the kernel of phi
+    /// generate `E[deg phi]`, compute the dual isogeny of phi.
+    ///
+    /// See `IsogenyChain::compute_dual` for details.
+    #[cfg(feature = "std")]
+    pub fn compute_dual(&self, domain: &ProjectiveCurveParameters, codomain: &ProjectiveCurveParameters, xQ: &ProjectivePoint) -> IsogenyChain {
+        let mut chain = IsogenyChain::identity(domain);
+        chain.push(*self, *codomain);
+        chain.compute_dual(xQ)
+    }
+    // The degree l^k of this step, as (l, k), with l = 1 for isomorphisms.
+    fn prime_power(&self) -> (usize, u32) {
+        match self {
+            IsogenyStep::Two(_) | IsogenyStep::ZeroTwo(_) => (2, 1),
+            IsogenyStep::FirstFour(_) | IsogenyStep::NegatedFirstFour(_) | IsogenyStep::Four(_) => (2, 2),
+            IsogenyStep::Eight(_) => (2, 3),
+            IsogenyStep::Three(_) => (3, 1),
+            IsogenyStep::Nine(_) => (3, 2),
+            IsogenyStep::Isomorphism(_) => (1, 0),
+        }
+    }
+    // The 2-isogeny of the curve with kernel <x2>.
+    fn compute_two(domain: &ProjectiveCurveParameters, x2: &ProjectivePoint) -> (ProjectiveCurveParameters, IsogenyStep) {
+        if x2.X.vartime_eq(&ExtensionFieldElement::zero()) {
+            let (codomain, phi) = ZeroTwoIsogeny::compute_zero_two_isogeny(domain);
+            (codomain, IsogenyStep::ZeroTwo(phi))
+        } else {
+            let (codomain, phi) = TwoIsogeny::compute_two_isogeny(x2);
+            (codomain, IsogenyStep::Two(phi))
+        }
+    }
+    // The 4-isogeny of the curve with kernel <x4>, where x4 = (1 : 1) and x4 = (-1 : 1)
+    // are the kernels containing (0,0).
+    fn compute_four(domain: &ProjectiveCurveParameters, x4: &ProjectivePoint) -> (ProjectiveCurveParameters, IsogenyStep) {
+        if x4.X.vartime_eq(&x4.Z) {
+            let (codomain, phi) = FirstFourIsogeny::compute_first_four_isogeny(domain);
+            (codomain, IsogenyStep::FirstFour(phi))
+        } else if x4.X.vartime_eq(&-(&x4.Z)) {
+            let negated = ProjectiveCurveParameters{ A: -(&domain.A), C: domain.C };
+            let (codomain, phi) = FirstFourIsogeny::compute_first_four_isogeny(&negated);
+            (codomain, IsogenyStep::NegatedFirstFour(phi))
+        } else {
+            let (codomain, phi) = FourIsogeny::compute_four_isogeny(x4);
+            (codomain, IsogenyStep::Four(phi))
+        }
+    }
+}
+
+/// Represents a chain of isogenies `phi = phi_n o ... o phi_1 : E_0 -> E_n`, holding
+/// each step's isogeny and codomain, so that further points can be pushed through
+/// phi after the chain has been computed.
+///
+/// When only the codomain and the images of points known in advance are needed,
+/// `four_isogeny_walk` and `three_isogeny_walk` compute them without keeping the
+/// steps.
+///
+/// This requires the `std` feature: the steps take about 1.35KB each, so a chain
+/// of the SIDH walk would take about 320KB of stack without the heap.
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct IsogenyChain {
+    domain: ProjectiveCurveParameters,
+    steps: ::std::vec::Vec<(IsogenyStep, ProjectiveCurveParameters)>,
+}
+
+#[cfg(feature = "std")]
+impl IsogenyChain {
+    // The empty chain, which is the identity on the domain.
+    fn identity(domain: &ProjectiveCurveParameters) -> IsogenyChain {
+        IsogenyChain{ domain: *domain, steps: ::std::vec::Vec::new() }
+    }
+    /// Given `xR = x(R)` for a point `R` of order `4^(n+1)` on the curve `E_(A:C)`,
+    /// with `x([4^n]R) = (1 : 1)` as for the SIDH kernels, compute the chain of `n+1`
+    /// four-isogenies with kernel `<R>`, walking the isogeny tree as directed by
+    /// `strategy`, of length `n`.
+    ///
+    /// The first step is the `FirstFourIsogeny` of `E_(A:C)`.
+    pub fn compute_four_isogeny_chain(domain: &ProjectiveCurveParameters, xR: &ProjectivePoint, strategy: &[u8]) -> IsogenyChain {
+        IsogenyChain::blinded_four_isogeny_chain(domain, xR, strategy, &mut no_blinding)
+    }
+    /// Like `compute_four_isogeny_chain`, but randomizing the projective
+    /// representations of the points of the walk after each isogeny, as
+    /// `ProjectivePoint::randomize` does.
+    pub fn compute_four_isogeny_chain_randomized<R: RngCore + CryptoRng>(domain: &ProjectiveCurveParameters, xR: &ProjectivePoint,
+                                                                         strategy: &[u8], rng: &mut R) -> IsogenyChain
+    {
+        IsogenyChain::blinded_four_isogeny_chain(domain, xR, strategy, &mut |xS: &mut ProjectivePoint| *xS = xS.randomize(rng))
+    }
+    fn blinded_four_isogeny_chain<B>(domain: &ProjectiveCurveParameters, xR: &ProjectivePoint, strategy: &[u8], blind: &mut B) -> IsogenyChain
+        where B: FnMut(&mut ProjectivePoint)
+    {
+        let mut chain = IsogenyChain::identity(domain);
+        walk_four(domain, xR, strategy, &mut [], &mut |phi, codomain| chain.push(phi, codomain), blind);
+        chain
+    }
+    /// Given `xR = x(R)` for a point `R` of order `3^n` on the curve `E_(A:C)`, compute
+    /// the chain of `n` three-isogenies with kernel `<R>`, walking the isogeny tree as
+    /// directed by `strategy`, of length `n`.
+    pub fn compute_three_isogeny_chain(domain: &ProjectiveCurveParameters, xR: &ProjectivePoint, strategy: &[u8]) -> IsogenyChain {
+        IsogenyChain::blinded_three_isogeny_chain(domain, xR, strategy, &mut no_blinding)
+    }
+    /// Like `compute_three_isogeny_chain`, but randomizing the projective
+    /// representations of the points of the walk after each isogeny, as
+    /// `ProjectivePoint::randomize` does.
+    pub fn compute_three_isogeny_chain_randomized<R: RngCore + CryptoRng>(domain: &ProjectiveCurveParameters, xR: &ProjectivePoint,
+                                                                          strategy: &[u8], rng: &mut R) -> IsogenyChain
+    {
+        IsogenyChain::blinded_three_isogeny_chain(domain, xR, strategy, &mut |xS: &mut ProjectivePoint| *xS = xS.randomize(rng))
+    }
+    fn blinded_three_isogeny_chain<B>(domain: &ProjectiveCurveParameters, xR: &ProjectivePoint, strategy: &[u8], blind: &mut B) -> IsogenyChain
+        where B: FnMut(&mut ProjectivePoint)
+    {
+        let mut chain = IsogenyChain::identity(domain);
+        walk(domain, xR, strategy, WalkDegree::Three, &mut [], &mut |phi, codomain| chain.push(phi, codomain), blind);
+        chain
+    }
+    /// Given `xR = x(R)` for a point `R` of order `8^n` on the curve `E_(A:C)`, such
+    /// that `(0,0)` is not in `<R>`, compute the chain of `n` eight-isogenies with
+    /// kernel `<R>`, walking the isogeny tree as directed by `strategy`, of length `n`.
+    ///
+    /// This is an alternative to the four-isogeny walk after the first step of
+    /// `compute_four_isogeny_chain`, whose strategy entries count multiplications by
+    /// 8 instead of by 4.
+    pub fn compute_eight_isogeny_chain(domain: &ProjectiveCurveParameters, xR: &ProjectivePoint, strategy: &[u8]) -> IsogenyChain {
+        let mut chain = IsogenyChain::identity(domain);
+        walk(domain, xR, strategy, WalkDegree::Eight, &mut [], &mut |phi, codomain| chain.push(phi, codomain), &mut no_blinding);
+        chain
+    }
+    /// Given `xR = x(R)` for a point `R` of order `9^n` on the curve `E_(A:C)`, compute
+    /// the chain of `n` nine-isogenies with kernel `<R>`, walking the isogeny tree as
+    /// directed by `strategy`, of length `n`.
+    ///
+    /// This computes the same codomain as `compute_three_isogeny_chain` with `2n`
+    /// steps, with strategy entries counting multiplications by 9 instead of by 3.
+    pub fn compute_nine_isogeny_chain(domain: &ProjectiveCurveParameters, xR: &ProjectivePoint, strategy: &[u8]) -> IsogenyChain {
+        let mut chain = IsogenyChain::identity(domain);
+        walk(domain, xR, strategy, WalkDegree::Nine, &mut [], &mut |phi, codomain| chain.push(phi, codomain), &mut no_blinding);
+        chain
+    }
+    /// Given `xK = x(K)` for a point `K` of order `2^n` on any curve `E_(A:C)`, compute
+    /// the chain of a two-isogeny, if `n` is odd, followed by `n/2` four-isogenies,
+    /// with kernel `<K>`.
+    ///
+    /// Steps whose kernel contains `(0,0)` are computed as a `ZeroTwoIsogeny` or a
+    /// (negated) `FirstFourIsogeny`. The steps are chosen depending on `K`, so this
+    /// runs in variable time.
+    pub fn compute_two_power_isogeny_chain(domain: &ProjectiveCurveParameters, xK: &ProjectivePoint, n: u32) -> IsogenyChain {
+        let mut chain = IsogenyChain::identity(domain);
+        walk_two_power(domain, xK, n, &mut [], &mut |phi, codomain| chain.push(phi, codomain));
+        chain
+    }
+    /// Given `xK = x(K)` for a point `K` of order `3^n` on any curve `E_(A:C)`, compute
+    /// the chain of `n` three-isogenies with kernel `<K>`.
+    pub fn compute_three_power_isogeny_chain(domain: &ProjectiveCurveParameters, xK: &ProjectivePoint, n: u32) -> IsogenyChain {
+        let mut chain = IsogenyChain::identity(domain);
+        walk_three_power(domain, xK, n, &mut [], &mut |phi, codomain| chain.push(phi, codomain));
+        chain
+    }
+    /// Given `xQ = x(Q)` for a point `Q` on the domain `E_0` such that `Q` and the
+    /// kernel of phi generate `E_0[deg phi]`, compute the dual isogeny `hat(phi) :
+    /// E_n -> E_0` of phi, so that `hat(phi) o phi = [deg phi]`.
+    ///
+    /// The kernel of `hat(phi)` is `<phi(Q)>`, and the chain of `hat(phi)` ends with an
+    /// isomorphism onto `E_0` if needed. Panics if phi is not of degree `2^n` or `3^n`,
+    /// or if `phi(Q)` does not generate the kernel of `hat(phi)`. Takes variable time.
+    pub fn compute_dual(&self, xQ: &ProjectivePoint) -> IsogenyChain {
+        let mut l = 1;
+        let mut n = 0;
+        for step in self.steps.iter() {
+            let (l_i, k_i) = step.0.prime_power();
+            assert!(l_i == 1 || l == 1 || l_i == l, "dual isogenies need a chain of prime-power degree");
+            l = l.max(l_i);
+            n += k_i;
+        }
+        let codomain = self.codomain();
+        if n == 0 {
+            return IsogenyChain::identity(&codomain);
+        }
+
+        let mul = |xS: &ProjectivePoint, curve: &ProjectiveCurveParameters, k: u32| {
+            if l == 2 { xS.pow2k(curve, k) } else { xS.pow3k(curve, k) }
+        };
+        let xK = self.eval(xQ);
+        assert!(!mul(&xK, &codomain, n - 1).Z.vartime_eq(&ExtensionFieldElement::zero()),
+                "the image of Q must generate the kernel of the dual isogeny");
+        let mut dual = if l == 2 {
+            IsogenyChain::compute_two_power_isogeny_chain(&codomain, &xK, n)
+        } else {
+            IsogenyChain::compute_three_power_isogeny_chain(&codomain, &xK, n)
+        };
+
+        // The codomain of the dual is E_0 up to isomorphism. If E_0 has extra
+        // automorphisms, pick the isomorphism for which hat(phi)(phi(T)) = [deg phi]T.
+        let isomorphisms = MontgomeryIsomorphism::compute_isomorphisms(&dual.codomain(), &self.domain);
+        let iota = if isomorphisms.len() == 1 {
+            isomorphisms[0]
+        } else {
+            let xT = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(3));
+            let xU = dual.eval(&self.eval(&xT));
+            let xV = mul(&xT, &self.domain, n);
+            *isomorphisms.iter().find(|iota| iota.eval(&xU).vartime_eq(&xV))
+                                .expect("the dual isogeny must end on an isomorphic curve")
+        };
+        if !(iota.r.vartime_eq(&ExtensionFieldElement::zero()) && iota.s.vartime_eq(&ExtensionFieldElement::one())) {
+            dual.push(IsogenyStep::Isomorphism(iota), self.domain);
+        }
+        dual
+    }
+    fn push(&mut self, phi: IsogenyStep, codomain: ProjectiveCurveParameters) {
+        self.steps.push((phi, codomain));
+    }
+    /// Returns the domain `E_0` of the chain.
+    pub fn domain(&self) -> ProjectiveCurveParameters {
+        self.domain
+    }
+    /// Returns the codomain `E_n` of the chain.
+    pub fn codomain(&self) -> ProjectiveCurveParameters {
+        self.steps.last().map_or(self.domain, |step| step.1)
+    }
+    /// Returns the number `n` of isogenies in the chain.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+    /// Returns whether the chain is empty, i.e. the identity on `E_0`.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+    /// Returns the codomain `E_i` of the `i`-th isogeny `phi_i`, for `1 <= i <= n`.
+    pub fn codomain_of_step(&self, i: usize) -> ProjectiveCurveParameters {
+        self.steps[i - 1].1
+    }
+    /// Given a point `xP = x(P)` on `E_0`, compute `x(phi(P))` on `E_n`.
+    pub fn eval(&self, xP: &ProjectivePoint) -> ProjectivePoint {
+        self.steps.iter().fold(*xP, |xQ, step| step.0.eval(&xQ))
+    }
+    /// Push the `points` on `E_0` through the chain, replacing each by its image on
+    /// `E_n`.
+    pub fn eval_points(&self, points: &mut [ProjectivePoint]) {
+        for step in self.steps.iter() {
+            eval_points(points, |xS| step.0.eval(xS));
+        }
+    }
+    /// Like `eval_points`, but randomizing the projective representations of the
+    /// `points` before the first and after each isogeny, as
+    /// `ProjectivePoint::randomize` does.
+    pub fn eval_points_randomized<R: RngCore + CryptoRng>(&self, points: &mut [ProjectivePoint], rng: &mut R) {
+        points.iter_mut().for_each(|xS| *xS = xS.randomize(rng));
+        for step in self.steps.iter() {
+            eval_points(points, |xS| step.0.eval(xS));
+            points.iter_mut().for_each(|xS| *xS = xS.randomize(rng));
+        }
+    }
+}
+
+/// Given `xR = x(R)` for a point `R` of order `4^(n+1)` on the curve `E_(A:C)`, with
+/// `x([4^n]R) = (1 : 1)` as for the SIDH kernels, compute the codomain of the chain
+/// of `n+1` four-isogenies with kernel `<R>`, walking the isogeny tree as directed by
+/// `strategy`, of length `n`, and replace each of the `points` by its image.
+///
+/// This is `IsogenyChain::compute_four_isogeny_chain` without keeping the steps.
+/// After each isogeny, `blind` is applied to the points of the walk and to the
+/// `points`, e.g. to randomize their projective representations; pass `no_blinding`
+/// to leave them as they are.
+pub fn four_isogeny_walk<B>(domain: &ProjectiveCurveParameters, xR: &ProjectivePoint, strategy: &[u8],
+                            points: &mut [ProjectivePoint], blind: &mut B) -> ProjectiveCurveParameters
+    where B: FnMut(&mut ProjectivePoint)
+{
+    walk_four(domain, xR, strategy, points, &mut no_recording, blind)
+}
+
+/// Given `xR = x(R)` for a point `R` of order `3^n` on the curve `E_(A:C)`, compute
+/// the codomain of the chain of `n` three-isogenies with kernel `<R>`, walking the
+/// isogeny tree as directed by `strategy`, of length `n`, and replace each of the
+/// `points` by its image.
+///
+/// This is `IsogenyChain::compute_three_isogeny_chain` without keeping the steps.
+/// See `four_isogeny_walk` for `blind`.
+pub fn three_isogeny_walk<B>(domain: &ProjectiveCurveParameters, xR: &ProjectivePoint, strategy: &[u8],
+                             points: &mut [ProjectivePoint], blind: &mut B) -> ProjectiveCurveParameters
+    where B: FnMut(&mut ProjectivePoint)
+{
+    walk(domain, xR, strategy, WalkDegree::Three, points, &mut no_recording, blind)
+}
+
+/// Given `xK = x(K)` for a point `K` of order `2^n` on the curve `E_(A:C)`, compute
+/// the isogeny `phi : E_(A:C) -> E_(A:C)/<K> = E_(A':C')`, replacing each of the
+/// `points` by its image under phi.
+///
+/// Returns the codomain `E_(A':C')`. See `IsogenyChain::compute_two_power_isogeny_chain`
+/// for details.
+///
+/// ```rust
+/// use sidh::{ExtensionFieldElement, ProjectiveCurveParameters, ProjectivePoint, two_power_isogeny};
+/// use sidh::constants::{AFFINE_X_PA, AFFINE_X_PB, E_A, E_B};
+///
+/// let E0 = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+/// // A kernel of order 2^5, and Bob's point of order 3^239.
+/// let xK = ProjectivePoint::from_affine_prime_field(&AFFINE_X_PA).pow2k(&E0, E_A - 5);
+/// let xQ = ProjectivePoint::from_affine_prime_field(&AFFINE_X_PB);
+///
+/// let mut points = [xK, xQ];
+/// let codomain = two_power_isogeny(&E0, &xK, 5, &mut points);
+/// assert!(points[0].Z.vartime_eq(&ExtensionFieldElement::zero()));
+/// assert!(!points[1].pow3k(&codomain, E_B - 1).Z.vartime_eq(&ExtensionFieldElement::zero()));
+/// ```
+pub fn two_power_isogeny(domain: &ProjectiveCurveParameters, xK: &ProjectivePoint, n: u32, points: &mut [ProjectivePoint]) -> ProjectiveCurveParameters {
+    walk_two_power(domain, xK, n, points, &mut no_recording)
+}
+
+/// Given `xK = x(K)` for a point `K` of order `3^n` on the curve `E_(A:C)`, compute
+/// the isogeny `phi : E_(A:C) -> E_(A:C)/<K> = E_(A':C')`, replacing each of the
+/// `points` by its image under phi.
+///
+/// Returns the codomain `E_(A':C')`.
+///
+/// ```rust
+/// use sidh::{ExtensionFieldElement, ProjectiveCurveParameters, ProjectivePoint, three_power_isogeny};
+/// use sidh::constants::{AFFINE_X_PA, AFFINE_X_PB, E_A, E_B};
+///
+/// let E0 = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+/// // A kernel of order 3^4, and Alice's point of order 2^372.
+/// let xK = ProjectivePoint::from_affine_prime_field(&AFFINE_X_PB).pow3k(&E0, E_B - 4);
+/// let xP = ProjectivePoint::from_affine_prime_field(&AFFINE_X_PA);
+///
+/// let mut points = [xK, xP];
+/// let codomain = three_power_isogeny(&E0, &xK, 4, &mut points);
+/// assert!(points[0].Z.vartime_eq(&ExtensionFieldElement::zero()));
+/// assert!(!points[1].pow2k(&codomain, E_A - 1).Z.vartime_eq(&ExtensionFieldElement::zero()));
+/// ```
+pub fn three_power_isogeny(domain: &ProjectiveCurveParameters, xK: &ProjectivePoint, n: u32, points: &mut [ProjectivePoint]) -> ProjectiveCurveParameters {
+    walk_three_power(domain, xK, n, points, &mut no_recording)
+}
+
+/// The blinding for the walks which leaves the points as they are.
+pub fn no_blinding(_: &mut ProjectivePoint) {}
+
+// The recording for the walks which does not keep the steps.
+fn no_recording(_: IsogenyStep, _: ProjectiveCurveParameters) {}
+
+// The prime-power degrees of the isogenies of a strategy walk.
+#[derive(Copy, Clone)]
+enum WalkDegree {
+    // Four-isogenies whose kernels do not contain (0,0), as after the first step
+    // of the SIDH walk.
+    Four,
+    // Four-isogenies with any kernel.
+    AnyFour,
+    // Eight-isogenies whose kernels do not contain (0,0).
+    #[cfg(feature = "std")]
+    Eight,
+    Three,
+    #[cfg(feature = "std")]
+    Nine,
+}
+
+impl WalkDegree {
+    // Multiply xS by the k-th power of the degree.
+    fn mul(self, xS: &ProjectivePoint, curve: &ProjectiveCurveParameters, k: u32) -> ProjectivePoint {
+        match self {
+            WalkDegree::Four | WalkDegree::AnyFour => xS.pow2k(curve, 2 * k),
+            #[cfg(feature = "std")]
+            WalkDegree::Eight => xS.pow2k(curve, 3 * k),
+            WalkDegree::Three => xS.pow3k(curve, k),
+            #[cfg(feature = "std")]
+            WalkDegree::Nine => xS.pow3k(curve, 2 * k),
+        }
+    }
+    // The isogeny of the curve with kernel <xS>.
+    fn compute(self, curve: &ProjectiveCurveParameters, xS: &ProjectivePoint) -> (ProjectiveCurveParameters, IsogenyStep) {
+        match self {
+            WalkDegree::Four => {
+                let (codomain, phi) = FourIsogeny::compute_four_isogeny(xS);
+                (codomain, IsogenyStep::Four(phi))
+            }
+            WalkDegree::AnyFour => IsogenyStep::compute_four(curve, xS),
+            #[cfg(feature = "std")]
+            WalkDegree::Eight => {
+                let (codomain, phi) = EightIsogeny::compute_eight_isogeny(curve, xS);
+                (codomain, IsogenyStep::Eight(phi))
+            }
+            WalkDegree::Three => {
+                let (codomain, phi) = ThreeIsogeny::compute_three_isogeny(xS);
+                (codomain, IsogenyStep::Three(phi))
+            }
+            #[cfg(feature = "std")]
+            WalkDegree::Nine => {
+                let (codomain, phi) = NineIsogeny::compute_nine_isogeny(curve, xS);
+                (codomain, IsogenyStep::Nine(phi))
+            }
+        }
+    }
+}
+
+// Walk the strategy.len() isogenies of the given degree with kernel <R> from the
+// domain, replacing each of the points by its image, and return the codomain. Each
+// isogeny is passed to record together with its codomain, and blind is applied to
+// the points of the walk and the points after each isogeny.
+fn walk<S, B>(domain: &ProjectiveCurveParameters, xR: &ProjectivePoint, strategy: &[u8], degree: WalkDegree,
+              points: &mut [ProjectivePoint], record: &mut S, blind: &mut B) -> ProjectiveCurveParameters
+    where S: FnMut(IsogenyStep, ProjectiveCurveParameters),
+          B: FnMut(&mut ProjectivePoint)
+{
+    assert!(!strategy.is_empty(), "isogeny strategies must not be empty");
+    let max = strategy.len();
+    let mut xR = *xR;
+    let mut current_curve = *domain;
+    let mut int_points: Vec<ProjectivePoint, MAX_INT_POINTS> = Vec::new();
+    let mut indices: Vec<usize, MAX_INT_POINTS> = Vec::new();
+    let mut i: usize = 0;
+    for j in 1..max {
+        while i < max-j {
+            int_points.push(xR).unwrap();
+            indices.push(i).unwrap();
+            let k = strategy[max-i-j];
+            xR = degree.mul(&xR, &current_curve, k as u32);
+            i += k as usize;
+        }
+        let (codomain, phi) = degree.compute(&current_curve, &xR);
+        current_curve = codomain;
+        record(phi, codomain);
+
+        eval_points(&mut int_points, |xS| phi.eval(xS));
+        eval_points(points, |xS| phi.eval(xS));
+        int_points.iter_mut().chain(points.iter_mut()).for_each(&mut *blind);
+
+        // Pop xR from int_points and i from indices.
+        xR = int_points.pop().unwrap();
+        i = indices.pop().unwrap();
+    }
+
+    let (codomain, phi) = degree.compute(&current_curve, &xR);
+    record(phi, codomain);
+    eval_points(points, |xS| phi.eval(xS));
+    points.iter_mut().for_each(&mut *blind);
+    codomain
+}
+
+// Walk the SIDH chain of four-isogenies with kernel <R>, whose first step is the
+// FirstFourIsogeny of the domain; see IsogenyChain::compute_four_isogeny_chain.
+fn walk_four<S, B>(domain: &ProjectiveCurveParameters, xR: &ProjectivePoint, strategy: &[u8],
+                   points: &mut [ProjectivePoint], record: &mut S, blind: &mut B) -> ProjectiveCurveParameters
+    where S: FnMut(IsogenyStep, ProjectiveCurveParameters),
+          B: FnMut(&mut ProjectivePoint)
+{
+    let (codomain, phi) = FirstFourIsogeny::compute_first_four_isogeny(domain);
+    record(IsogenyStep::FirstFour(phi), codomain);
+    let mut xR = phi.eval(xR);
+    blind(&mut xR);
+    eval_points(points, |xS| phi.eval(xS));
+    points.iter_mut().for_each(&mut *blind);
+    walk(&codomain, &xR, strategy, WalkDegree::Four, points, record, blind)
+}
+
+// Walk the chain of a two-isogeny, if n is odd, followed by n/2 four-isogenies with
+// kernel <K>; see IsogenyChain::compute_two_power_isogeny_chain.
+fn walk_two_power<S>(domain: &ProjectiveCurveParameters, xK: &ProjectivePoint, n: u32,
+                     points: &mut [ProjectivePoint], record: &mut S) -> ProjectiveCurveParameters
+    where S: FnMut(IsogenyStep, ProjectiveCurveParameters)
+{
+    let mut codomain = *domain;
+    let mut xK = *xK;
+    if n % 2 == 1 {
+        let (next, phi) = IsogenyStep::compute_two(domain, &xK.pow2k(domain, n - 1));
+        record(phi, next);
+        codomain = next;
+        xK = phi.eval(&xK);
+        eval_points(points, |xS| phi.eval(xS));
+    }
+    if n >= 2 {
+        codomain = walk(&codomain, &xK, &balanced_strategy(n as usize / 2), WalkDegree::AnyFour, points, record, &mut no_blinding);
+    }
+    codomain
+}
+
+// Walk the chain of n three-isogenies with kernel <K>; see
+// IsogenyChain::compute_three_power_isogeny_chain.
+fn walk_three_power<S>(domain: &ProjectiveCurveParameters, xK: &ProjectivePoint, n: u32,
+                       points: &mut [ProjectivePoint], record: &mut S) -> ProjectiveCurveParameters
+    where S: FnMut(IsogenyStep, ProjectiveCurveParameters)
+{
+    if n == 0 {
+        return *domain;
+    }
+    walk(domain, xK, &balanced_strategy(n as usize), WalkDegree::Three, points, record, &mut no_blinding)
+}
+
+// A strategy for a chain of n prime-power isogenies which splits each subtree in
+// half (at ceil(t/2) for t leaves), for chains without a precomputed optimal strategy.
+fn balanced_strategy(n: usize) -> Vec<u8, MAX_ISOGENY_CHAIN_LENGTH> {
+    (0..n).map(|t| (t - t / 2) as u8).collect()
+}
+
+// Evaluate an isogeny on the `points`.
+//
+// Each step only has a handful of points, too few for rayon to pay off (the
+// keygen benches are no faster with the `parallel` feature), so they are
+// evaluated in turn.
+fn eval_points<F>(points: &mut [ProjectivePoint], phi: F)
+    where F: Fn(&ProjectivePoint) -> ProjectivePoint
+{
+    for xS in points.iter_mut() {
+        *xS = phi(xS);
+    }
+}
+
+// The codomain of the Velu isogeny of degree l whose kernel <K> has the points
+// (X_i + Z_i, X_i - Z_i) for x([i]K) = (X_i : Z_i), 1 <= i <= (l-1)/2.
+//
+// See compute_velu_isogeny for the formulas.
+fn velu_codomain(domain: &ProjectiveCurveParameters, kernel: &[(ExtensionFieldElement, ExtensionFieldElement)], degree: usize) -> ProjectiveCurveParameters {
+    let mut prod_Y = ExtensionFieldElement::one();
+    let mut prod_Z = ExtensionFieldElement::one();
+    for (Z_i, Y_i) in kernel.iter() {
+        prod_Y = &prod_Y * Y_i;
+        prod_Z = &prod_Z * Z_i;
+    }
+    let C2 = &domain.C + &domain.C;
+    let a = poly::pow_vartime(&(&domain.A + &C2), degree);      // = (A + 2C)^l
+    let d = poly::pow_vartime(&(&domain.A - &C2), degree);      // = (A - 2C)^l
+    let a_prime = &a * &prod_Z.square().square().square();      // = (A + 2C)^l (prod Z_i)^8
+    let d_prime = &d * &prod_Y.square().square().square();      // = (A - 2C)^l (prod Y_i)^8
+    let A = &a_prime + &d_prime;
+
+    ProjectiveCurveParameters{ A: &A + &A, C: &a_prime - &d_prime }
+}
+
+// Evaluate the Velu isogeny with the given kernel points at xP.
+//
+// Costello-Hisil: (X' : Z') = (X prod (X X_i - Z Z_i)^2 : Z prod (X Z_i - Z X_i)^2),
+// where 2(X X_i - Z Z_i) = (X - Z)(X_i + Z_i) + (X + Z)(X_i - Z_i) and
+// 2(X Z_i - Z X_i) = (X - Z)(X_i + Z_i) - (X + Z)(X_i - Z_i).
+fn velu_eval(kernel: &[(ExtensionFieldElement, ExtensionFieldElement)], xP: &ProjectivePoint) -> ProjectivePoint {
+    let XmZ = &xP.X - &xP.Z;
+    let XpZ = &xP.X + &xP.Z;
+    let mut x = ExtensionFieldElement::one();
+    let mut z = ExtensionFieldElement::one();
+    for (XpZ_i, XmZ_i) in kernel.iter() {
+        let t0 = &XmZ * XpZ_i;              // = (X - Z)(X_i + Z_i)
+        let t1 = &XpZ * XmZ_i;              // = (X + Z)(X_i - Z_i)
+        x = &x * &(&t0 + &t1);              // *= 2(X X_i - Z Z_i)
+        z = &z * &(&t0 - &t1);              // *= 2(X Z_i - Z X_i)
+    }
+
+    ProjectivePoint{ X: &xP.X * &x.square(), Z: &xP.Z * &z.square() }
+}
+
+// Compute floor(sqrt(n)).
+#[cfg(feature = "std")]
+fn isqrt(n: usize) -> usize {
+    let mut r = (n as f64).sqrt() as usize;
+    while r * r > n {
+        r -= 1;
+    }
+    while (r + 1) * (r + 1) <= n {
+        r += 1;
     }
+    r
+}
+
+// Compute the affine x-coordinates of the points, with a single inversion.
+#[cfg(feature = "std")]
+fn batch_to_affine(points: &[ProjectivePoint]) -> ::std::vec::Vec<ExtensionFieldElement> {
+    let mut prefix = ::std::vec::Vec::with_capacity(points.len());
+    let mut acc = ExtensionFieldElement::one();
+    for P in points.iter() {
+        prefix.push(acc);
+        acc = &acc * &P.Z;
+    }
+    let mut inv = acc.inv();                   // = 1/(Z_0 ... Z_(n-1))
+    let mut xs = ::std::vec![ExtensionFieldElement::zero(); points.len()];
+    for (i, P) in points.iter().enumerate().rev() {
+        xs[i] = &P.X * &(&inv * &prefix[i]);   // = X_i/Z_i
+        inv = &inv * &P.Z;
+    }
+    xs
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use constants::{E_A, E_B, AFFINE_X_PA, AFFINE_Y_PA};
 
     // Test the first four-isogeny from the base curve E_0(F_{p^2}).
     #[test]
@@ -428,4 +1593,606 @@ mod test {
         assert!(sage_isogenized_xR.vartime_eq(&isogenized_xR), 
                 "\nExpected\n{:?}\nfound\n{:?}", sage_isogenized_xR.to_affine(), isogenized_xR.to_affine());
     }
+
+    // Returns x(K) for K of order 3^k on the curve, together with x(P) for a
+    // point P which is not in <K>.
+    fn three_power_kernel(curve: &ProjectiveCurveParameters, k: u32) -> (ProjectivePoint, ProjectivePoint) {
+        let (xP, _, xQ, _) = curve.generate_3_torsion_basis();
+        let xK = ProjectivePoint::from_affine(&xP).pow3k(curve, E_B - k);
+        (xK, ProjectivePoint::from_affine(&xQ))
+    }
+
+    fn same_curve(E1: &ProjectiveCurveParameters, E2: &ProjectiveCurveParameters) -> bool {
+        (&E1.A * &E2.C).vartime_eq(&(&E2.A * &E1.C))
+    }
+
+    #[test]
+    fn velu_isogeny_versus_three_isogeny() {
+        let e0 = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        // E_(12:-2) is 4-isogenous to E_0.
+        let e1 = ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) };
+        for curve in [e0, e1].iter() {
+            let (xK, xP) = three_power_kernel(curve, 1);
+            let (codomain, phi) = ThreeIsogeny::compute_three_isogeny(&xK);
+            let (velu_codomain, velu_phi) = VeluIsogeny::compute_velu_isogeny(curve, &xK, 3);
+            assert_eq!(velu_phi.degree(), 3);
+            assert!(same_curve(&codomain, &velu_codomain), "\nExpected\n{:?}\nfound\n{:?}", codomain, velu_codomain);
+
+            for xR in [xP, xP.pow2k(curve, 3), xK].iter() {
+                let xQ = phi.eval(xR);
+                let velu_xQ = velu_phi.eval(xR);
+                assert!(xQ.vartime_eq(&velu_xQ), "\nExpected\n{:?}\nfound\n{:?}", xQ, velu_xQ);
+            }
+        }
+    }
+
+    #[test]
+    fn velu_isogeny_versus_composed_three_isogenies() {
+        // E_(12:-2) is 4-isogenous to E_0.
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) };
+        for &k in [2, 3].iter() {
+            let (xK, xP) = three_power_kernel(&curve, k);
+            let (velu_codomain, velu_phi) = VeluIsogeny::compute_velu_isogeny(&curve, &xK, 3usize.pow(k));
+            assert_eq!(velu_phi.degree(), 3usize.pow(k));
+
+            // Compose k 3-isogenies, pushing the kernel through each of them.
+            let mut codomain = curve;
+            let mut xK = xK;
+            let mut xQ = xP;
+            for i in (0..k).rev() {
+                let (next, phi) = ThreeIsogeny::compute_three_isogeny(&xK.pow3k(&codomain, i));
+                codomain = next;
+                xK = phi.eval(&xK);
+                xQ = phi.eval(&xQ);
+            }
+            assert!(same_curve(&codomain, &velu_codomain), "\nExpected\n{:?}\nfound\n{:?}", codomain, velu_codomain);
+            assert!(xQ.vartime_eq(&velu_phi.eval(&xP)));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn sqrt_velu_isogeny_versus_velu_isogeny() {
+        // E_(12:-2) is 4-isogenous to E_0.
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) };
+        for &k in [2, 3, 4, 5].iter() {
+            let (xK, xP) = three_power_kernel(&curve, k);
+            let (velu_codomain, velu_phi) = VeluIsogeny::compute_velu_isogeny(&curve, &xK, 3usize.pow(k));
+            let (codomain, phi) = SqrtVeluIsogeny::compute_sqrt_velu_isogeny(&curve, &xK, 3usize.pow(k));
+            assert_eq!(phi.degree(), 3usize.pow(k));
+            assert!(same_curve(&codomain, &velu_codomain), "\nExpected\n{:?}\nfound\n{:?}", velu_codomain, codomain);
+
+            for xR in [xP, xP.pow2k(&curve, 3), xK.pow3k(&curve, k - 1)].iter() {
+                let xQ = phi.eval(xR);
+                let velu_xQ = velu_phi.eval(xR);
+                assert!(xQ.vartime_eq(&velu_xQ), "\nExpected\n{:?}\nfound\n{:?}", velu_xQ, xQ);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn sqrt_velu_isogeny_versus_composed_velu_isogenies() {
+        // E_(12:-2) is 4-isogenous to E_0.
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) };
+        let (xK, xP) = three_power_kernel(&curve, 6);
+        let (codomain, phi) = SqrtVeluIsogeny::compute_sqrt_velu_isogeny(&curve, &xK, 729);
+        assert_eq!(phi.degree(), 729);
+
+        // Compose two 27-isogenies, pushing the kernel through the first one.
+        let (velu_codomain, velu_phi) = VeluIsogeny::compute_velu_isogeny(&curve, &xK.pow3k(&curve, 3), 27);
+        let (velu_codomain, velu_psi) = VeluIsogeny::compute_velu_isogeny(&velu_codomain, &velu_phi.eval(&xK), 27);
+        assert!(same_curve(&codomain, &velu_codomain), "\nExpected\n{:?}\nfound\n{:?}", velu_codomain, codomain);
+        assert!(phi.eval(&xP).vartime_eq(&velu_psi.eval(&velu_phi.eval(&xP))));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn odd_isogenies_around_sqrt_velu_threshold() {
+        // E_(12:-2) is 4-isogenous to E_0.
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) };
+        for &k in [6, 7].iter() {
+            let degree = 3usize.pow(k);
+            let (xK, xP) = three_power_kernel(&curve, k);
+            let (codomain, phi) = OddIsogeny::compute_odd_isogeny(&curve, &xK, degree);
+            match phi {
+                OddIsogeny::Velu(_) => assert!(degree < SQRT_VELU_THRESHOLD),
+                OddIsogeny::SqrtVelu(_) => assert!(degree >= SQRT_VELU_THRESHOLD),
+            }
+
+            // Classical Velu holds any degree with std.
+            let (velu_codomain, velu_phi) = VeluIsogeny::compute_velu_isogeny(&curve, &xK, degree);
+            assert_eq!(velu_phi.degree(), degree);
+            assert!(same_curve(&codomain, &velu_codomain), "\nExpected\n{:?}\nfound\n{:?}", velu_codomain, codomain);
+            assert!(phi.eval(&xP).vartime_eq(&velu_phi.eval(&xP)));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn three_isogeny_chain_versus_odd_isogeny() {
+        // E_(12:-2) is 4-isogenous to E_0.
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) };
+        let (xK, xP) = three_power_kernel(&curve, 6);
+        let strategy = [0, 1, 1, 2, 2, 2];
+        let chain = IsogenyChain::compute_three_isogeny_chain(&curve, &xK, &strategy);
+        assert_eq!(chain.len(), 6);
+        assert!(same_curve(&chain.domain(), &curve));
+
+        let (codomain, phi) = OddIsogeny::compute_odd_isogeny(&curve, &xK, 729);
+        assert!(same_curve(&chain.codomain(), &codomain), "\nExpected\n{:?}\nfound\n{:?}", codomain, chain.codomain());
+        assert!(same_curve(&chain.codomain_of_step(6), &codomain));
+
+        let mut points = [xP, xP.pow2k(&curve, 1), xK];
+        chain.eval_points(&mut points);
+        for (xR, xQ) in [xP, xP.pow2k(&curve, 1), xK].iter().zip(points.iter()) {
+            assert!(xQ.vartime_eq(&phi.eval(xR)));
+            assert!(xQ.vartime_eq(&chain.eval(xR)));
+        }
+    }
+
+    // Returns x(R) for R of order 2^(2k), with x([4^(k-1)]R) = (1 : 1), on E_0.
+    fn sidh_two_power_kernel(k: u32) -> ProjectivePoint {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let mut scalar = [0x5a; 48];
+        scalar[47] = 0;
+        scalar[46] &= 15;
+        ProjectivePoint::secret_point(&AFFINE_X_PA, &AFFINE_Y_PA, &scalar).pow2k(&curve, E_A - 2 * k)
+    }
+
+    #[cfg(feature = "std")]
+    fn same_j_invariant(E1: &ProjectiveCurveParameters, E2: &ProjectiveCurveParameters) -> bool {
+        E1.j_invariant().vartime_eq(&E2.j_invariant())
+    }
+
+    #[test]
+    fn two_isogenies_commute_with_doubling() {
+        let i = ExtensionFieldElement{ A: Fp751Element::zero(), B: ExtensionFieldElement::one().A };
+        let xP = ProjectivePoint{ X: ExtensionFieldElement::from_u64(7), Z: ExtensionFieldElement::from_u64(5) };
+        let mut branches = [false; 3];
+        for k in 0..16 {
+            let A = &ExtensionFieldElement::from_u64(k + 3) + &(&i * &ExtensionFieldElement::from_u64(2 * k + 1));
+            let curve = ProjectiveCurveParameters{ A, C: ExtensionFieldElement::from_u64(k + 1) };
+            let (codomain, phi) = ZeroTwoIsogeny::compute_zero_two_isogeny(&curve);
+            branches[if phi.B.vartime_eq(&-(&(&curve.C + &curve.C))) { 0 } else if phi.B.vartime_eq(&(&curve.C + &curve.C)) { 1 } else { 2 }] = true;
+            assert!(phi.eval(&xP.pow2k(&curve, 1)).vartime_eq(&phi.eval(&xP).pow2k(&codomain, 1)));
+            assert!(phi.eval(&ProjectivePoint::from_affine(&ExtensionFieldElement::zero())).Z.vartime_eq(&ExtensionFieldElement::zero()));
+
+            // The other 2-torsion points are the roots of Cx^2 + Ax + C.
+            let disc = &curve.A.square() - &(&curve.C + &curve.C).square();
+            if disc.is_square() {
+                let x2 = ProjectivePoint{ X: &disc.sqrt() - &curve.A, Z: &curve.C + &curve.C };
+                let (codomain, phi) = TwoIsogeny::compute_two_isogeny(&x2);
+                assert!(phi.eval(&xP.pow2k(&curve, 1)).vartime_eq(&phi.eval(&xP).pow2k(&codomain, 1)));
+                assert!(phi.eval(&x2).Z.vartime_eq(&ExtensionFieldElement::zero()));
+            }
+        }
+        assert!(branches.iter().all(|&b| b), "not all kernel (0,0) normalisations were tested");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn two_power_isogeny_chain_versus_four_isogeny_chain() {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xR = sidh_two_power_kernel(3);
+        let xP = ProjectivePoint{ X: ExtensionFieldElement::from_u64(7), Z: ExtensionFieldElement::from_u64(5) };
+        let chain = IsogenyChain::compute_four_isogeny_chain(&curve, &xR, &[0, 1]);
+        let mut points = [xP];
+        let codomain = two_power_isogeny(&curve, &xR, 6, &mut points);
+        assert!(same_curve(&codomain, &chain.codomain()), "\nExpected\n{:?}\nfound\n{:?}", chain.codomain(), codomain);
+        assert!(points[0].vartime_eq(&chain.eval(&xP)));
+
+        // The same kernel on E_(-A:C), with x([4^2]R) = (-1 : 1).
+        let negated = ProjectiveCurveParameters{ A: -(&curve.A), C: curve.C };
+        let neg = |xS: &ProjectivePoint| ProjectivePoint{ X: -(&xS.X), Z: xS.Z };
+        let negated_chain = IsogenyChain::compute_two_power_isogeny_chain(&negated, &neg(&xR), 6);
+        assert!(same_curve(&negated_chain.codomain(), &chain.codomain()));
+        assert!(negated_chain.eval(&neg(&xP)).vartime_eq(&chain.eval(&xP)));
+
+        // An odd exponent, whose first step has kernel <(0,0)>.
+        let odd_chain = IsogenyChain::compute_two_power_isogeny_chain(&curve, &xR.pow2k(&curve, 1), 5);
+        assert_eq!(odd_chain.len(), 3);
+        let (codomain, _) = IsogenyStep::compute_two(&odd_chain.codomain(), &odd_chain.eval(&xR));
+        assert!(same_j_invariant(&codomain, &chain.codomain()));
+        let xQ = odd_chain.eval(&xP);
+        assert!(odd_chain.eval(&xP.pow2k(&curve, 1)).vartime_eq(&xQ.pow2k(&odd_chain.codomain(), 1)));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn two_power_isogeny_on_any_curve() {
+        // A kernel of order 2^5 on E_(12:-2), whose steps are all generic.
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) };
+        let (xP, _, xQ, _) = curve.generate_2_torsion_basis();
+        let xK = ProjectivePoint::from_affine(&xP).pow2k(&curve, E_A - 5);
+        let xS = ProjectivePoint::from_affine(&xQ);
+        let mut points = [xS, xS.pow2k(&curve, 1), xK];
+        let codomain = two_power_isogeny(&curve, &xK, 5, &mut points);
+        assert!(points[1].vartime_eq(&points[0].pow2k(&codomain, 1)));
+        assert!(points[2].Z.vartime_eq(&ExtensionFieldElement::zero()));
+
+        // Composing with the 2-isogeny of the next kernel point matches n = 6.
+        let xK6 = ProjectivePoint::from_affine(&xP).pow2k(&curve, E_A - 6);
+        let chain = IsogenyChain::compute_two_power_isogeny_chain(&curve, &xK6, 6);
+        let xK5 = IsogenyChain::compute_two_power_isogeny_chain(&curve, &xK, 5).eval(&xK6);
+        let (next, _) = IsogenyStep::compute_two(&codomain, &xK5);
+        assert!(same_j_invariant(&next, &chain.codomain()));
+    }
+
+    #[test]
+    fn three_power_isogeny_versus_odd_isogeny() {
+        // E_(12:-2) is 4-isogenous to E_0.
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) };
+        let (xK, xP) = three_power_kernel(&curve, 5);
+        let mut points = [xP, xK];
+        let codomain = three_power_isogeny(&curve, &xK, 5, &mut points);
+        let (velu_codomain, phi) = OddIsogeny::compute_odd_isogeny(&curve, &xK, 243);
+        assert!(same_curve(&codomain, &velu_codomain), "\nExpected\n{:?}\nfound\n{:?}", velu_codomain, codomain);
+        assert!(points[0].vartime_eq(&phi.eval(&xP)));
+        assert!(points[1].Z.vartime_eq(&ExtensionFieldElement::zero()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn eight_isogeny_chain_versus_four_isogeny_chain() {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xR = sidh_two_power_kernel(4);
+        let xP = ProjectivePoint{ X: ExtensionFieldElement::from_u64(7), Z: ExtensionFieldElement::from_u64(5) };
+        let four_chain = IsogenyChain::compute_four_isogeny_chain(&curve, &xR, &[0, 1, 1]);
+
+        // After the first four-isogeny, the kernel has order 2^6 = 8^2.
+        let (first_codomain, phi) = FirstFourIsogeny::compute_first_four_isogeny(&curve);
+        let eight_chain = IsogenyChain::compute_eight_isogeny_chain(&first_codomain, &phi.eval(&xR), &[0, 1]);
+        assert_eq!(eight_chain.len(), 2);
+        let codomain = eight_chain.codomain();
+        assert!(same_j_invariant(&codomain, &four_chain.codomain()));
+        assert!(eight_chain.eval(&phi.eval(&xR)).Z.vartime_eq(&ExtensionFieldElement::zero()));
+        let xQ = eight_chain.eval(&phi.eval(&xP));
+        let x2Q = eight_chain.eval(&phi.eval(&xP.pow2k(&curve, 1)));
+        assert!(x2Q.vartime_eq(&xQ.pow2k(&codomain, 1)));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn nine_isogeny_chain_versus_three_isogeny_chain() {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) };
+        let (xR, xP) = three_power_kernel(&curve, 6);
+        let three_chain = IsogenyChain::compute_three_isogeny_chain(&curve, &xR, &[0, 1, 1, 2, 2, 2]);
+        let nine_chain = IsogenyChain::compute_nine_isogeny_chain(&curve, &xR, &[0, 1, 1]);
+        assert_eq!(nine_chain.len(), 3);
+        assert!(same_curve(&nine_chain.codomain(), &three_chain.codomain()),
+                "\nExpected\n{:?}\nfound\n{:?}", three_chain.codomain(), nine_chain.codomain());
+        assert!(nine_chain.eval(&xP).vartime_eq(&three_chain.eval(&xP)));
+    }
+
+    // E_0, which has extra automorphisms, and E_(12:-2).
+    #[cfg(feature = "std")]
+    fn dual_test_curves() -> [ProjectiveCurveParameters; 2] {
+        [ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() },
+         ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) }]
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn dual_isogeny_steps() {
+        let mut rng = rand::thread_rng();
+        for curve in dual_test_curves().iter() {
+            let (xP2, _, xQ2, _) = curve.generate_2_torsion_basis();
+            let (xP2, xQ2) = (ProjectivePoint::from_affine(&xP2), ProjectivePoint::from_affine(&xQ2));
+            let (xK3, xQ3) = three_power_kernel(curve, 1);
+            let xQ3 = xQ3.pow3k(curve, E_B - 1);
+
+            let (codomain, phi) = ThreeIsogeny::compute_three_isogeny(&xK3);
+            let steps = [
+                (IsogenyStep::compute_two(curve, &xP2.pow2k(curve, E_A - 1)), xQ2.pow2k(curve, E_A - 1)),
+                (IsogenyStep::compute_four(curve, &xP2.pow2k(curve, E_A - 2)), xQ2.pow2k(curve, E_A - 2)),
+                ((codomain, IsogenyStep::Three(phi)), xQ3),
+            ];
+            for ((codomain, phi), xQ) in steps.iter() {
+                let dual = phi.compute_dual(curve, codomain, xQ);
+                assert!(same_curve(&dual.codomain(), curve));
+                for _ in 0..4 {
+                    let xT = ProjectivePoint::random(curve, &mut rng);
+                    let xU = if phi.degree() == 3 { xT.pow3k(curve, 1) } else { xT.pow2k(curve, phi.degree().trailing_zeros()) };
+                    assert!(dual.eval(&phi.eval(&xT)).vartime_eq(&xU));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn dual_isogeny_chains() {
+        let mut rng = rand::thread_rng();
+        for curve in dual_test_curves().iter() {
+            let (xP2, _, xQ2, _) = curve.generate_2_torsion_basis();
+            let (xP2, xQ2) = (ProjectivePoint::from_affine(&xP2), ProjectivePoint::from_affine(&xQ2));
+            for &n in [5, 6].iter() {
+                let chain = IsogenyChain::compute_two_power_isogeny_chain(curve, &xP2.pow2k(curve, E_A - n), n);
+                let dual = chain.compute_dual(&xQ2.pow2k(curve, E_A - n));
+                assert!(same_curve(&dual.domain(), &chain.codomain()));
+                assert!(same_curve(&dual.codomain(), curve));
+                for _ in 0..4 {
+                    let xT = ProjectivePoint::random(curve, &mut rng);
+                    assert!(dual.eval(&chain.eval(&xT)).vartime_eq(&xT.pow2k(curve, n)));
+                }
+            }
+
+            let (xK, xQ) = three_power_kernel(curve, 4);
+            let xQ = xQ.pow3k(curve, E_B - 4);
+            let chains = [IsogenyChain::compute_three_power_isogeny_chain(curve, &xK, 4),
+                          IsogenyChain::compute_nine_isogeny_chain(curve, &xK, &[0, 1])];
+            for chain in chains.iter() {
+                let dual = chain.compute_dual(&xQ);
+                assert!(same_curve(&dual.codomain(), curve));
+                for _ in 0..4 {
+                    let xT = ProjectivePoint::random(curve, &mut rng);
+                    assert!(dual.eval(&chain.eval(&xT)).vartime_eq(&xT.pow3k(curve, 4)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn four_isogeny_chain_versus_four_isogenies() {
+        // As for the SIDH secret kernels, x([4^2]R) = (1 : 1) for R of order 4^3.
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let mut scalar = [0x5a; 48];
+        scalar[47] = 0;
+        scalar[46] &= 15;
+        let xR = ProjectivePoint::secret_point(&AFFINE_X_PA, &AFFINE_Y_PA, &scalar).pow2k(&curve, E_A - 6);
+        let xS = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(7));
+        let chain = IsogenyChain::compute_four_isogeny_chain(&curve, &xR, &[0, 1]);
+        assert_eq!(chain.len(), 3);
+
+        let (mut codomain, phi) = FirstFourIsogeny::compute_first_four_isogeny(&curve);
+        let mut xR = phi.eval(&xR);
+        let mut xT = phi.eval(&xS);
+        for i in (0..2).rev() {
+            let (next, phi) = FourIsogeny::compute_four_isogeny(&xR.pow2k(&codomain, 2 * i));
+            codomain = next;
+            xR = phi.eval(&xR);
+            xT = phi.eval(&xT);
+        }
+        assert!(same_curve(&chain.codomain(), &codomain), "\nExpected\n{:?}\nfound\n{:?}", codomain, chain.codomain());
+        assert!(chain.eval(&xS).vartime_eq(&xT));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn isogeny_walks_versus_chains() {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xS = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(7));
+        let mut scalar = [0x5a; 48];
+        scalar[47] = 0;
+        scalar[46] &= 15;
+        let xR = ProjectivePoint::secret_point(&AFFINE_X_PA, &AFFINE_Y_PA, &scalar).pow2k(&curve, E_A - 10);
+        let chain = IsogenyChain::compute_four_isogeny_chain(&curve, &xR, &[0, 1, 1, 2]);
+        let mut points = [xS];
+        let codomain = four_isogeny_walk(&curve, &xR, &[0, 1, 1, 2], &mut points, &mut no_blinding);
+        assert!(codomain.A.vartime_eq(&chain.codomain().A) && codomain.C.vartime_eq(&chain.codomain().C));
+        assert!(points[0].vartime_eq(&chain.eval(&xS)));
+
+        let (xK, _) = three_power_kernel(&curve, 5);
+        let chain = IsogenyChain::compute_three_isogeny_chain(&curve, &xK, &[0, 1, 1, 2, 2]);
+        let mut points = [xS];
+        let codomain = three_isogeny_walk(&curve, &xK, &[0, 1, 1, 2, 2], &mut points, &mut no_blinding);
+        assert!(codomain.A.vartime_eq(&chain.codomain().A) && codomain.C.vartime_eq(&chain.codomain().C));
+        assert!(points[0].vartime_eq(&chain.eval(&xS)));
+    }
+}
+
+#[cfg(all(test, feature = "bench"))]
+mod bench {
+    use super::*;
+    use constants::{AFFINE_X_PA, AFFINE_Y_PA, AFFINE_X_PB, AFFINE_Y_PB};
+    use test::Bencher;
+
+    fn bench_point() -> ProjectivePoint {
+        ProjectivePoint{ X: ExtensionFieldElement::from_u64(7), Z: ExtensionFieldElement::from_u64(5) }
+    }
+
+    #[bench]
+    fn three_isogeny_eval(b: &mut Bencher) {
+        let xP3 = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let (_, phi) = ThreeIsogeny::compute_three_isogeny(&xP3);
+        let xR = bench_point();
+
+        b.iter(|| phi.eval(&xR));
+    }
+
+    #[bench]
+    fn four_isogeny_eval(b: &mut Bencher) {
+        let xP4 = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let (_, phi) = FourIsogeny::compute_four_isogeny(&xP4);
+        let xR = bench_point();
+
+        b.iter(|| phi.eval(&xR));
+    }
+
+    #[bench]
+    fn two_isogeny_eval(b: &mut Bencher) {
+        let xP2 = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let (_, phi) = TwoIsogeny::compute_two_isogeny(&xP2);
+        let xR = bench_point();
+
+        b.iter(|| phi.eval(&xR));
+    }
+
+    #[bench]
+    fn eight_isogeny_eval(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xP8 = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let (_, phi) = EightIsogeny::compute_eight_isogeny(&curve, &xP8);
+        let xR = bench_point();
+
+        b.iter(|| phi.eval(&xR));
+    }
+
+    #[bench]
+    fn nine_isogeny_eval(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xP9 = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let (_, phi) = NineIsogeny::compute_nine_isogeny(&curve, &xP9);
+        let xR = bench_point();
+
+        b.iter(|| phi.eval(&xR));
+    }
+
+    // A kernel of order 2^366 = 4^183 = 8^122 on the codomain of the first step of
+    // Alice's walk.
+    #[cfg(feature = "std")]
+    fn alice_kernel_after_first_step() -> (ProjectiveCurveParameters, ProjectivePoint) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xR = ProjectivePoint::secret_point(&AFFINE_X_PA, &AFFINE_Y_PA, &[0x5a; 47]);
+        let (codomain, phi) = FirstFourIsogeny::compute_first_four_isogeny(&curve);
+        (codomain, phi.eval(&xR).pow2k(&codomain, 4))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn four_isogeny_chain_2_366(b: &mut Bencher) {
+        let (curve, xR) = alice_kernel_after_first_step();
+
+        b.iter(|| IsogenyChain::compute_two_power_isogeny_chain(&curve, &xR, 366));
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn eight_isogeny_chain_2_366(b: &mut Bencher) {
+        let (curve, xR) = alice_kernel_after_first_step();
+        let strategy = balanced_strategy(122);
+
+        b.iter(|| IsogenyChain::compute_eight_isogeny_chain(&curve, &xR, &strategy));
+    }
+
+    // A kernel of order 3^238 = 9^119 on the starting curve.
+    #[cfg(feature = "std")]
+    fn bob_kernel() -> (ProjectiveCurveParameters, ProjectivePoint) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xR = ProjectivePoint::secret_point(&AFFINE_X_PB, &AFFINE_Y_PB, &[0x5a; 47]);
+        (curve, xR.pow3k(&curve, 1))
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn three_isogeny_chain_3_238(b: &mut Bencher) {
+        let (curve, xR) = bob_kernel();
+        let strategy = balanced_strategy(238);
+
+        b.iter(|| IsogenyChain::compute_three_isogeny_chain(&curve, &xR, &strategy));
+    }
+
+    #[bench]
+    #[cfg(feature = "std")]
+    fn nine_isogeny_chain_3_238(b: &mut Bencher) {
+        let (curve, xR) = bob_kernel();
+        let strategy = balanced_strategy(119);
+
+        b.iter(|| IsogenyChain::compute_nine_isogeny_chain(&curve, &xR, &strategy));
+    }
+
+    #[bench]
+    fn velu_isogeny_eval_degree_27(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xK = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let (_, phi) = VeluIsogeny::compute_velu_isogeny(&curve, &xK, 27);
+        let xR = bench_point();
+
+        b.iter(|| phi.eval(&xR));
+    }
+
+    #[bench]
+    fn velu_isogeny_compute_degree_27(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xK = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+
+        b.iter(|| VeluIsogeny::compute_velu_isogeny(&curve, &xK, 27));
+    }
+
+    #[bench]
+    fn sqrt_velu_isogeny_eval_degree_243(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xK = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let (_, phi) = SqrtVeluIsogeny::compute_sqrt_velu_isogeny(&curve, &xK, 243);
+        let xR = bench_point();
+
+        b.iter(|| phi.eval(&xR));
+    }
+
+    #[bench]
+    fn sqrt_velu_isogeny_compute_degree_243(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xK = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+
+        b.iter(|| SqrtVeluIsogeny::compute_sqrt_velu_isogeny(&curve, &xK, 243));
+    }
+
+    // Crossover benches for SQRT_VELU_THRESHOLD: compute the isogeny and push
+    // one point through it, as a single step of an isogeny walk does.
+
+    #[bench]
+    fn velu_isogeny_compute_and_eval_degree_257(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xK = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let xR = bench_point();
+
+        b.iter(|| VeluIsogeny::compute_velu_isogeny(&curve, &xK, 257).1.eval(&xR));
+    }
+
+    #[bench]
+    fn sqrt_velu_isogeny_compute_and_eval_degree_257(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xK = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let xR = bench_point();
+
+        b.iter(|| SqrtVeluIsogeny::compute_sqrt_velu_isogeny(&curve, &xK, 257).1.eval(&xR));
+    }
+
+    #[bench]
+    fn velu_isogeny_compute_and_eval_degree_1023(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xK = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let xR = bench_point();
+
+        b.iter(|| VeluIsogeny::compute_velu_isogeny(&curve, &xK, 1023).1.eval(&xR));
+    }
+
+    #[bench]
+    fn sqrt_velu_isogeny_compute_and_eval_degree_1023(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xK = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let xR = bench_point();
+
+        b.iter(|| SqrtVeluIsogeny::compute_sqrt_velu_isogeny(&curve, &xK, 1023).1.eval(&xR));
+    }
+
+    #[bench]
+    fn velu_isogeny_compute_and_eval_degree_4095(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xK = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let xR = bench_point();
+
+        b.iter(|| VeluIsogeny::compute_velu_isogeny(&curve, &xK, 4095).1.eval(&xR));
+    }
+
+    #[bench]
+    fn sqrt_velu_isogeny_compute_and_eval_degree_4095(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let xK = ProjectivePoint::from_affine(&ExtensionFieldElement::from_u64(11));
+        let xR = bench_point();
+
+        b.iter(|| SqrtVeluIsogeny::compute_sqrt_velu_isogeny(&curve, &xK, 4095).1.eval(&xR));
+    }
+
+    #[bench]
+    fn first_four_isogeny_eval(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+        let (_, phi) = FirstFourIsogeny::compute_first_four_isogeny(&curve);
+        let xR = bench_point();
+
+        b.iter(|| phi.eval(&xR));
+    }
 }
+
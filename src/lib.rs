@@ -30,6 +30,18 @@
 //! 
 //! TThe library provides a generic field arithmetic implementation, therefore, making it 
 //! compatible with many different architectures (such as x64, x86, and ARM).
+//!
+//! Besides SIDH, the field elements, Montgomery curves and points, and isogenies
+//! between the curves are re-exported at the crate root:
+//!
+//! ```rust
+//! use sidh::{ExtensionFieldElement, ProjectiveCurveParameters, ProjectivePoint};
+//!
+//! let curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+//! let xP = ProjectivePoint::from_affine(&"7 + 5*i".parse().unwrap());
+//! let x4P = xP.pow2k(&curve, 2);
+//! assert!(!x4P.vartime_eq(&xP));
+//! ```
 
 //-----------------------------------------------------------------------------//
 //                          External Dependencies                              //
@@ -42,6 +54,11 @@ extern crate rand;
 extern crate rand_core;
 extern crate heapless;
 
+// The test harness links std, so tests may use it even without the `std` feature.
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
+
 #[cfg(all(test, feature = "bench"))]
 extern crate test;
 #[cfg(any(test, feature = "bench"))]
@@ -50,20 +67,27 @@ extern crate quickcheck;
 // Used for traits related to constant-time code.
 extern crate subtle;
 
+// Used for batched key generation.
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
 //-----------------------------------------------------------------------------//
 //                             Internal Modules                                //
 //-----------------------------------------------------------------------------//
 
 // Arithmetic backends (for x64 and x86 arhitectures) live here.
 pub(crate) mod backend;
-// Finite field arithmetic.
+// Finite field arithmetic. The field element types are re-exported below.
 pub(crate) mod field;
-// Internal curve operations which are not part of the public API.
+// Curve operations. The curve and point types are re-exported below.
 #[macro_use]
 pub(crate) mod curve;
-// Internal isogeny operations which are not part of the public API.
+// Isogeny operations. The isogeny types and walks are re-exported below.
 pub(crate) mod isogeny;
 
+// Fixed-base comb tables for the SIDH bases, generated by the build script.
+pub(crate) mod fixed_base;
+
 //-----------------------------------------------------------------------------//
 //                              Public Modules                                 //
 //-----------------------------------------------------------------------------//
@@ -72,3 +96,33 @@ pub(crate) mod isogeny;
 pub mod constants;
 // Supersingular Isogeny Diffie-Hellman (SIDH) operations.
 pub mod sidh;
+// Tate and Weil pairings on the 2^e- and 3^e-torsion.
+pub mod pairing;
+// Discrete logarithms in the groups of 2^e-th and 3^e-th roots of unity.
+pub mod discrete_log;
+// Short Weierstrass models of the Montgomery curves.
+pub mod weierstrass;
+// Twisted Edwards models of the Montgomery curves.
+pub mod edwards;
+// Dense polynomials over F_{p^2}.
+pub mod poly;
+// Classical modular polynomials and walks in the isogeny graphs of j-invariants.
+pub mod modular;
+
+//-----------------------------------------------------------------------------//
+//                             Public Re-exports                               //
+//-----------------------------------------------------------------------------//
+
+// Field elements, which appear throughout the public modules.
+pub use field::{Fp751Element, PrimeFieldElement, ExtensionFieldElement, CyclotomicElement,
+                ParseFieldElementError, FP751_ENCODED_BYTES};
+// Montgomery curves and points on them.
+pub use curve::{ProjectiveCurveParameters, ProjectivePoint, MontgomeryPoint};
+// Isogenies between Montgomery curves, and walks and chains of them.
+pub use isogeny::{TwoIsogeny, ZeroTwoIsogeny, ThreeIsogeny, FourIsogeny, FirstFourIsogeny,
+                  EightIsogeny, NineIsogeny, VeluIsogeny, OddIsogeny, MontgomeryIsomorphism,
+                  IsogenyStep, MAX_VELU_KERNEL_POINTS, SQRT_VELU_THRESHOLD, MAX_ISOGENY_CHAIN_LENGTH,
+                  four_isogeny_walk, three_isogeny_walk, two_power_isogeny, three_power_isogeny,
+                  no_blinding};
+#[cfg(feature = "std")]
+pub use isogeny::{SqrtVeluIsogeny, IsogenyChain};
@@ -0,0 +1,192 @@
+// This file is part of sidh-rs.
+// Copyright (c) 2017 Erkan Tairi
+// See LICENSE for licensing information.
+//
+// Author:
+// - Erkan Tairi <erkan.tairi@gmail.com>
+//
+
+//! This module contains the classical modular polynomials `Phi_2` and `Phi_3`,
+//! and walks in the `l`-isogeny graphs of j-invariants over `F_{p^2}` built on
+//! them, for `l = 2, 3`.
+//!
+//! Two j-invariants `j` and `j'` are `l`-isogenous if and only if `Phi_l(j, j') = 0`,
+//! so the neighbors of `j` in the `l`-isogeny graph are the roots of the
+//! polynomial `Phi_l(j, Y)` of degree `l + 1`, counted with multiplicity. For a
+//! supersingular `j`, all `l + 1` of them lie in `F_{p^2}`.
+//!
+//! The roots are found with `poly::roots`, which takes a few hundred polynomial
+//! multiplications. All of this takes variable time.
+
+use field::ExtensionFieldElement;
+use poly::{self, Polynomial};
+
+use heapless::Vec;
+use rand_core::{CryptoRng, RngCore};
+
+/// The coefficients of `X^i Y^k` in `Phi_2(X, Y)`, as `PHI_2[i][k]`.
+const PHI_2: [[i128; 4]; 4] = [
+    [-157464000000000,   8748000000,   -162000,  1],
+    [      8748000000,     40773375,      1488,  0],
+    [         -162000,         1488,        -1,  0],
+    [               1,            0,         0,  0],
+];
+
+/// The coefficients of `X^i Y^k` in `Phi_3(X, Y)`, as `PHI_3[i][k]`.
+const PHI_3: [[i128; 5]; 5] = [
+    [                     0, 1855425871872000000000,  452984832000000,  36864000,  1],
+    [1855425871872000000000,   -770845966336000000,    8900222976000,  -1069956,  0],
+    [       452984832000000,         8900222976000,       2587918086,      2232,  0],
+    [              36864000,              -1069956,             2232,        -1,  0],
+    [                     1,                     0,                0,         0,  0],
+];
+
+/// Compute `Phi_2(x, y)`.
+pub fn phi_2(x: &ExtensionFieldElement, y: &ExtensionFieldElement) -> ExtensionFieldElement {
+    poly::eval(&coefficients(2, x), y)
+}
+
+/// Compute `Phi_3(x, y)`.
+pub fn phi_3(x: &ExtensionFieldElement, y: &ExtensionFieldElement) -> ExtensionFieldElement {
+    poly::eval(&coefficients(3, x), y)
+}
+
+/// Returns the neighbors of `j` in the `l`-isogeny graph over `F_{p^2}`, for
+/// `l = 2` or `3`: the roots of `Phi_l(j, Y)` in `F_{p^2}`, with multiplicity.
+///
+/// For a supersingular `j`, there are `l + 1` of them. Panics if `l` is not 2 or
+/// 3. Takes variable time.
+pub fn neighbors(j: &ExtensionFieldElement, l: u32) -> Vec<ExtensionFieldElement, 4> {
+    poly::roots(&coefficients(l, j))
+}
+
+/// Take a non-backtracking random walk of `length` steps from `j` in the
+/// `l`-isogeny graph over `F_{p^2}`, for `l = 2` or `3`, and return the
+/// j-invariant it ends on.
+///
+/// Each step moves to a uniformly random neighbor, counted with multiplicity,
+/// other than the one the walk came from, unless that is the only neighbor.
+/// Panics if `l` is not 2 or 3, or if the walk gets stuck on a j-invariant
+/// without neighbors in `F_{p^2}`. Takes variable time.
+pub fn random_walk<R: RngCore + CryptoRng>(j: &ExtensionFieldElement, l: u32, length: usize, rng: &mut R) -> ExtensionFieldElement {
+    let mut current = *j;
+    let mut previous: Option<ExtensionFieldElement> = None;
+    for _ in 0..length {
+        let mut choices = neighbors(&current, l);
+        assert!(!choices.is_empty(), "random walk reached a j-invariant without neighbors");
+        if let Some(prev) = previous {
+            if choices.len() > 1 {
+                if let Some(i) = choices.iter().position(|j_i| j_i.vartime_eq(&prev)) {
+                    choices.swap_remove(i);
+                }
+            }
+        }
+        let next = choices[(rng.next_u32() as usize) % choices.len()];
+        previous = Some(current);
+        current = next;
+    }
+    current
+}
+
+// The coefficients of Phi_l(x, Y) in Y.
+fn coefficients(l: u32, x: &ExtensionFieldElement) -> Polynomial {
+    match l {
+        2 => coefficients_of(&PHI_2, x),
+        3 => coefficients_of(&PHI_3, x),
+        _ => panic!("only the modular polynomials Phi_2 and Phi_3 are supported"),
+    }
+}
+
+fn coefficients_of<const N: usize>(phi: &[[i128; N]; N], x: &ExtensionFieldElement) -> Polynomial {
+    (0..N).map(|k| {
+        let column: Vec<ExtensionFieldElement, 5> = phi.iter().map(|row| from_i128(row[k])).collect();
+        poly::eval(&column, x)
+    }).collect()
+}
+
+fn from_i128(c: i128) -> ExtensionFieldElement {
+    // = 2^64
+    const TWO_64: ExtensionFieldElement = ExtensionFieldElement::from_str_const("18446744073709551616", "0");
+    let m = c.unsigned_abs();
+    let x = &(&ExtensionFieldElement::from_u64((m >> 64) as u64) * &TWO_64) + &ExtensionFieldElement::from_u64(m as u64);
+    if c < 0 { -(&x) } else { x }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use curve::{ProjectiveCurveParameters, ProjectivePoint};
+    use isogeny::{ThreeIsogeny, TwoIsogeny, ZeroTwoIsogeny};
+
+    // E_0 and E_(12:-2), which are 4-isogenous.
+    fn test_curves() -> [ProjectiveCurveParameters; 2] {
+        [ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() },
+         ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) }]
+    }
+
+    // Whether xs and ys are equal as multisets.
+    fn same_multiset(xs: &[ExtensionFieldElement], ys: &[ExtensionFieldElement]) -> bool {
+        let mut ys: Vec<ExtensionFieldElement, 4> = ys.iter().cloned().collect();
+        for x in xs.iter() {
+            match ys.iter().position(|y| y.vartime_eq(x)) {
+                Some(i) => { ys.swap_remove(i); }
+                None => return false,
+            }
+        }
+        ys.is_empty()
+    }
+
+    #[test]
+    fn modular_polynomials_are_symmetric() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..4 {
+            let x = ExtensionFieldElement::random(&mut rng);
+            let y = ExtensionFieldElement::random(&mut rng);
+            assert!(phi_2(&x, &y).vartime_eq(&phi_2(&y, &x)));
+            assert!(phi_3(&x, &y).vartime_eq(&phi_3(&y, &x)));
+        }
+        // E_0 : y^2 = x^3 + x is 2-isogenous to y^2 = x^3 - 4x, with j = 1728 too.
+        let j = ExtensionFieldElement::from_u64(1728);
+        assert!(phi_2(&j, &j).vartime_eq(&ExtensionFieldElement::zero()));
+    }
+
+    #[test]
+    fn neighbors_versus_isogenies() {
+        for curve in test_curves().iter() {
+            let a = &curve.A * &curve.C.inv();
+            let j = curve.j_invariant();
+
+            // The 2-torsion points are (0,0) and the roots of x^2 + ax + 1.
+            let mut two_isogenous: Vec<ExtensionFieldElement, 4> = Vec::new();
+            two_isogenous.push(ZeroTwoIsogeny::compute_zero_two_isogeny(curve).0.j_invariant()).unwrap();
+            for x in poly::roots(&[ExtensionFieldElement::one(), a, ExtensionFieldElement::one()]).iter() {
+                let (codomain, _) = TwoIsogeny::compute_two_isogeny(&ProjectivePoint::from_affine(x));
+                two_isogenous.push(codomain.j_invariant()).unwrap();
+            }
+            assert!(same_multiset(&neighbors(&j, 2), &two_isogenous));
+
+            // The 3-torsion points have x-coordinates the roots of the 3-division
+            // polynomial 3x^4 + 4ax^3 + 6x^2 - 1.
+            let psi_3 = [-(&ExtensionFieldElement::one()), ExtensionFieldElement::zero(), ExtensionFieldElement::from_u64(6),
+                         &ExtensionFieldElement::from_u64(4) * &a, ExtensionFieldElement::from_u64(3)];
+            let three_isogenous: Vec<ExtensionFieldElement, 4> = poly::roots(&psi_3).iter().map(|x| {
+                ThreeIsogeny::compute_three_isogeny(&ProjectivePoint::from_affine(x)).0.j_invariant()
+            }).collect();
+            assert_eq!(three_isogenous.len(), 4);
+            assert!(same_multiset(&neighbors(&j, 3), &three_isogenous));
+        }
+    }
+
+    #[test]
+    fn random_walks_stay_supersingular() {
+        let mut rng = rand::thread_rng();
+        let j = test_curves()[0].j_invariant();
+        for &l in [2, 3].iter() {
+            let next = random_walk(&j, l, 1, &mut rng);
+            assert!(neighbors(&j, l).iter().any(|j_i| j_i.vartime_eq(&next)));
+
+            let end = random_walk(&j, l, 6, &mut rng);
+            assert_eq!(neighbors(&end, l).len(), l as usize + 1);
+        }
+    }
+}
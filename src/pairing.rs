@@ -0,0 +1,522 @@
+// This file is part of sidh-rs.
+// Copyright (c) 2017 Erkan Tairi
+// See LICENSE for licensing information.
+//
+// Author:
+// - Erkan Tairi <erkan.tairi@gmail.com>
+//
+
+//! This module contains the reduced Tate and Weil pairings on the `2^372`- and
+//! `3^239`-torsion of supersingular Montgomery curves.
+//!
+//! Curves are given as `E_(A:C) : Cy^2 = Cx^3 + Ax^2 + Cx`, and points in affine
+//! `(x, y)` coordinates. The Miller loops are doubling-only and tripling-only
+//! loops, the latter using parabolas, as described in [3] and implemented in
+//! `sage/sidh_pairings.sage`. Since `E(F_{p^2})` contains the full `2^372`- and
+//! `3^239`-torsion, the pairings take values in `F_{p^2}`.
+
+use field::{CyclotomicElement, ExtensionFieldElement};
+use curve::ProjectiveCurveParameters;
+use constants::*;
+
+/// A point `(X:Y:Z)` in the Miller loop, stored as `(X^2, XZ, Z^2, YZ)`.
+#[derive(Copy, Clone)]
+struct MillerPoint {
+    X2: ExtensionFieldElement,
+    XZ: ExtensionFieldElement,
+    Z2: ExtensionFieldElement,
+    YZ: ExtensionFieldElement,
+}
+
+impl MillerPoint {
+    fn from_affine(x: &ExtensionFieldElement, y: &ExtensionFieldElement) -> MillerPoint {
+        MillerPoint{ X2: x.square(), XZ: *x, Z2: ExtensionFieldElement::one(), YZ: *y }
+    }
+}
+
+/// The tangent line `ly*y - lx*x + l0` at a point `T`, and the vertical line
+/// `ly*x - v0` at `[2]T`, scaled by the same factor.
+struct Line {
+    lx: ExtensionFieldElement,
+    ly: ExtensionFieldElement,
+    l0: ExtensionFieldElement,
+    v0: ExtensionFieldElement,
+}
+
+/// The parabola `ly*y + lx2 + lx1*x + lx0*x^2` through a point `T` with
+/// multiplicity 3, and the vertical line `vx + v0*x` at `[3]T`.
+struct Parabola {
+    ly: ExtensionFieldElement,
+    lx2: ExtensionFieldElement,
+    lx1: ExtensionFieldElement,
+    lx0: ExtensionFieldElement,
+    vx: ExtensionFieldElement,
+    v0: ExtensionFieldElement,
+}
+
+// Given `T` on `y^2 = x^3 + ax^2 + x`, compute `[2]T` and the tangent line at `T`.
+//
+// Cost: 9M+5S+7a+1s
+fn dbl_and_line(T: &MillerPoint, a: &ExtensionFieldElement) -> (MillerPoint, Line) {
+    let t0 = &T.YZ + &T.YZ;          // = 2YZ
+    let ly = t0.square();            // = (2YZ)^2
+    let mut l0 = &T.X2 - &T.Z2;      // = X^2 - Z^2
+    let v0 = l0.square();            // = (X^2 - Z^2)^2
+    l0 = &t0 * &l0;                  // = 2YZ(X^2 - Z^2)
+    let lx = &(&T.YZ * &ly) + &(&T.XZ * &l0);
+
+    let mut t1 = &T.X2 + &T.Z2;      // = X^2 + Z^2
+    t1 = a * &t1;                    // = a(X^2 + Z^2)
+    let t2 = &T.XZ + &T.XZ;          // = 2XZ
+    t1 = &(&t2 + &t1) + &t2;         // = 4XZ + a(X^2 + Z^2)
+    t1 = &t2 * &t1;                  // = 2XZ(4XZ + a(X^2 + Z^2))
+
+    let T2 = MillerPoint{
+        X2: v0.square(),
+        XZ: l0.square(),
+        Z2: ly.square(),
+        YZ: &l0 * &(&v0 + &t1),
+    };
+    let line = Line{ lx, ly: &T.XZ * &ly, l0: &T.X2 * &l0, v0: &T.XZ * &v0 };
+    (T2, line)
+}
+
+// Given `T` on `y^2 = x^3 + ax^2 + x`, compute `[3]T` and the parabola through `T`.
+//
+// Cost: 19M+6S+15a+6s
+fn triple_and_parabola(T: &MillerPoint, a: &ExtensionFieldElement) -> (MillerPoint, Parabola) {
+    let mut ly = &T.YZ + &T.YZ;      // = 2YZ
+    ly = &ly * &ly.square();         // = (2YZ)^3
+
+    let aXZ = a * &T.XZ;
+    let mut t0 = &aXZ + &T.Z2;
+    t0 = &t0 + &t0;                  // = 2(aXZ + Z^2)
+    let mut t1 = &T.X2 + &T.Z2;      // = X^2 + Z^2
+    let t2 = &T.X2 + &T.X2;          // = 2X^2
+    let t3 = (&T.X2 - &T.Z2).square(); // = (X^2 - Z^2)^2
+    let t4 = &t2 + &t0;
+    let lx2 = &(&t2 * &t4) - &t3;
+
+    let mut lx1 = &t4 + &t1;
+    t1 = t1.square();                // = (X^2 + Z^2)^2
+    lx1 = &t1 + &(&aXZ * &lx1);
+    lx1 = &t3 + &(&lx1 + &lx1);
+
+    let mut lx0 = &t3 - &(&T.Z2 * &t0);
+    lx0 = &t1 - &(&lx0 + &lx0);
+
+    let lx2_ = &T.Z2 * &lx2;
+    let mut lx1_ = &T.XZ * &lx1;
+    lx1_ = &lx1_ + &lx1_;
+    let lx0_ = &T.X2 * &lx0;
+
+    let s3 = lx2.square();           // = lx2^2
+    let s2 = &ly * &s3;              // = ly*lx2^2
+    let s4 = lx0.square();           // = lx0^2
+    let s0 = &T.X2 * &s4.square();   // = X^2*lx0^4
+
+    let mut YZ = &lx0 * &lx1;
+    YZ = &(&YZ + &YZ) + &s3;
+    YZ = &(&lx0_ * &lx2) * &YZ;
+    YZ = -&(&lx2_ * &(&s0 + &YZ));
+
+    let T3 = MillerPoint{
+        X2: &ly * &s0,
+        XZ: &(&T.XZ * &s2) * &s4,
+        Z2: &(&T.Z2 * &s2) * &s3,
+        YZ,
+    };
+    let parabola = Parabola{ ly, lx2: lx2_, lx1: lx1_, lx0: lx0_, vx: T3.Z2, v0: -&T3.XZ };
+    (T3, parabola)
+}
+
+// Compute the numerator and denominator of `f_{2^372,P}(Q)`, where `P` must have
+// order `2^372`.
+fn miller_loop_2e(a: &ExtensionFieldElement, xP: &ExtensionFieldElement, yP: &ExtensionFieldElement,
+                  xQ: &ExtensionFieldElement, yQ: &ExtensionFieldElement) -> (ExtensionFieldElement, ExtensionFieldElement)
+{
+    let mut T = MillerPoint::from_affine(xP, yP);
+    let mut n = ExtensionFieldElement::one();
+    let mut d = ExtensionFieldElement::one();
+
+    for _ in 0..(E_A - 1) {
+        let (T2, line) = dbl_and_line(&T, a);
+        // n = n^2 * (ly*y - lx*x + l0), d = d^2 * (ly*x - v0).
+        let l = &(&(&line.ly * yQ) - &(&line.lx * xQ)) + &line.l0;
+        let v = &(&line.ly * xQ) - &line.v0;
+        n = &n.square() * &l;
+        d = &d.square() * &v;
+        T = T2;
+    }
+    // Now T = (X^2, XZ, Z^2, YZ) has order 2, so the tangent is the vertical
+    // line Z*x - X.
+    n = &n.square() * &(&(&T.Z2 * xQ) - &T.XZ);
+    d = &d.square() * &T.Z2;
+    (n, d)
+}
+
+// Compute the numerator and denominator of `f_{3^239,P}(Q)`, where `P` must have
+// order `3^239`.
+fn miller_loop_3e(a: &ExtensionFieldElement, xP: &ExtensionFieldElement, yP: &ExtensionFieldElement,
+                  xQ: &ExtensionFieldElement, yQ: &ExtensionFieldElement) -> (ExtensionFieldElement, ExtensionFieldElement)
+{
+    let mut T = MillerPoint::from_affine(xP, yP);
+    let mut n = ExtensionFieldElement::one();
+    let mut d = ExtensionFieldElement::one();
+
+    for _ in 0..(E_B - 1) {
+        let (T3, parabola) = triple_and_parabola(&T, a);
+        // n = n^3 * (ly*y + lx2 + lx1*x + lx0*x^2) * v0,
+        // d = d^3 * (vx + v0*x) * lx0*x.
+        let t = &parabola.lx0 * xQ;
+        let ld = &(&parabola.vx + &(&parabola.v0 * xQ)) * &t;
+        let mut ln = &(&parabola.lx1 + &t) * xQ;
+        ln = &(&(&parabola.ly * yQ) + &(&parabola.lx2 + &ln)) * &parabola.v0;
+        n = &(&n.square() * &n) * &ln;
+        d = &(&d.square() * &d) * &ld;
+        T = T3;
+    }
+    // Now T has order 3, so the parabola is the tangent line
+    // D*y + lambda*x + mu*x^2 at (X:Y:Z) = (XZ:YZ:Z^2), and the vertical at
+    // [3]T = O is trivial.
+    let X = T.XZ;
+    let Y = T.YZ;
+    let Z = T.Z2;
+    let X2 = X.square();
+    let XZ = &X * &Z;
+    let tXZ = &XZ + &XZ;
+    let lambda = &(&(&X2 + &Z.square()) + &(&X2 + &X2)) + &(a * &tXZ);
+    let mu = &(&tXZ - &Y.square()) + &(a * &X2);
+    let YZ = &Y * &Z;
+    let D = &YZ + &YZ;
+
+    let ld = &mu * &xQ.square();
+    let ln = &(&(&D * yQ) + &(&lambda * xQ)) + &ld;
+    n = &(&n.square() * &n) * &ln;
+    d = &(&d.square() * &d) * &ld;
+    (n, d)
+}
+
+// Compute `(n/d)^(p-1)`, which lies in the subgroup of order `p+1`.
+fn easy_part(n: &ExtensionFieldElement, d: &ExtensionFieldElement) -> CyclotomicElement {
+    // (n/d)^(p-1) = (n*conj(d))^(p-1), as d^(p+1) = d*conj(d) lies in F_p.
+    let conj_d = ExtensionFieldElement{ A: d.A, B: -&d.B };
+    CyclotomicElement::project(&(n * &conj_d))
+}
+
+// Compute `(n/d)^((p^2-1)/2^372) = (n/d)^((p-1)*3^239)`.
+fn final_exponentiation_2e(n: &ExtensionFieldElement, d: &ExtensionFieldElement) -> ExtensionFieldElement {
+    easy_part(n, d).pow3k(E_B).A
+}
+
+// Compute `(n/d)^((p^2-1)/3^239) = (n/d)^((p-1)*2^372)`.
+fn final_exponentiation_3e(n: &ExtensionFieldElement, d: &ExtensionFieldElement) -> ExtensionFieldElement {
+    easy_part(n, d).pow2k(E_A).A
+}
+
+/// Compute the reduced Tate pairing `t(P, Q) = f_{2^372,P}(Q)^((p^2-1)/2^372)`
+/// on the curve `E_(A:C)`.
+///
+/// `P` must have order exactly `2^372`, and `Q` must not be a multiple of `P`.
+/// The result is a `2^372`-th root of unity.
+pub fn tate_pairing_2e(curve: &ProjectiveCurveParameters,
+                       affine_xP: &ExtensionFieldElement, affine_yP: &ExtensionFieldElement,
+                       affine_xQ: &ExtensionFieldElement, affine_yQ: &ExtensionFieldElement) -> ExtensionFieldElement
+{
+    let a = &curve.A * &curve.C.inv();
+    let (n, d) = miller_loop_2e(&a, affine_xP, affine_yP, affine_xQ, affine_yQ);
+    final_exponentiation_2e(&n, &d)
+}
+
+/// Compute the reduced Tate pairing `t(P, Q) = f_{3^239,P}(Q)^((p^2-1)/3^239)`
+/// on the curve `E_(A:C)`.
+///
+/// `P` must have order exactly `3^239`, and `Q` must not be a multiple of `P`.
+/// The result is a `3^239`-th root of unity.
+pub fn tate_pairing_3e(curve: &ProjectiveCurveParameters,
+                       affine_xP: &ExtensionFieldElement, affine_yP: &ExtensionFieldElement,
+                       affine_xQ: &ExtensionFieldElement, affine_yQ: &ExtensionFieldElement) -> ExtensionFieldElement
+{
+    let a = &curve.A * &curve.C.inv();
+    let (n, d) = miller_loop_3e(&a, affine_xP, affine_yP, affine_xQ, affine_yQ);
+    final_exponentiation_3e(&n, &d)
+}
+
+/// Compute the Weil pairing `e(P, Q) = f_{2^372,P}(Q) / f_{2^372,Q}(P)` on the
+/// curve `E_(A:C)`.
+///
+/// `P` and `Q` must both have order exactly `2^372`, and must generate distinct
+/// subgroups. The result is a `2^372`-th root of unity.
+pub fn weil_pairing_2e(curve: &ProjectiveCurveParameters,
+                       affine_xP: &ExtensionFieldElement, affine_yP: &ExtensionFieldElement,
+                       affine_xQ: &ExtensionFieldElement, affine_yQ: &ExtensionFieldElement) -> ExtensionFieldElement
+{
+    let a = &curve.A * &curve.C.inv();
+    let (n1, d1) = miller_loop_2e(&a, affine_xP, affine_yP, affine_xQ, affine_yQ);
+    let (n2, d2) = miller_loop_2e(&a, affine_xQ, affine_yQ, affine_xP, affine_yP);
+    &(&n1 * &d2) * &(&d1 * &n2).inv()
+}
+
+/// Compute the Weil pairing `e(P, Q) = -f_{3^239,P}(Q) / f_{3^239,Q}(P)` on the
+/// curve `E_(A:C)`.
+///
+/// `P` and `Q` must both have order exactly `3^239`, and must generate distinct
+/// subgroups. The result is a `3^239`-th root of unity.
+pub fn weil_pairing_3e(curve: &ProjectiveCurveParameters,
+                       affine_xP: &ExtensionFieldElement, affine_yP: &ExtensionFieldElement,
+                       affine_xQ: &ExtensionFieldElement, affine_yQ: &ExtensionFieldElement) -> ExtensionFieldElement
+{
+    let a = &curve.A * &curve.C.inv();
+    let (n1, d1) = miller_loop_3e(&a, affine_xP, affine_yP, affine_xQ, affine_yQ);
+    let (n2, d2) = miller_loop_3e(&a, affine_xQ, affine_yQ, affine_xP, affine_yP);
+    // The parabolas above are normalized so that the quotient of the Miller
+    // functions already carries the sign (-1)^(3^239) = -1.
+    &(&n1 * &d2) * &(&d1 * &n2).inv()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use field::PrimeFieldElement;
+    use curve::MontgomeryPoint;
+
+    // Reference values computed by `sage/test_vectors.py`, independently of this module.
+    //
+    // The curve is obtained from `E_(6:1)` by two 3-isogenies, and written with a
+    // random projective factor C. P and Q are the bases of the 2^372- and 3^239-torsion
+    // given by `generate_2_torsion_basis` and `generate_3_torsion_basis` in
+    // `sage/sidh_curve_and_isogeny_arithmetic.sage`, and the pairings are computed with
+    // textbook Miller loops on them.
+    const CURVE_A: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "2341043197209941991220519211619242041294598820532266398372157975001515989197428110084845584470281249404904414134892150192999032184099327420653892619511306818637883211283599346309298278898128478867136153639350095319994399832439",
+        "9221111434819607248328703668015403090416189270230638775720430459490474958686394301316049708684118082289706142929973344975240500415268374403185247960921533090119620475701346181489922176944655998757187110091076852079193907186348");
+    const CURVE_C: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "5567862735586870181692075744408061194115543800802669412303557080130253262030656668387767993794760218773725317600308695815314499693866785155373972856268355830657718660853133331641855944113898691502448967534707048084356647543388",
+        "8427682645450728827990654303677966176180390740375467148708597865629881552256142685168798117480464164548157967542807262837573300376067227525407707032292192722554697622100407172562937983328378484595610432440688689052570271569373");
+    const BASIS_XP2: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "9323324132124010362899938665393152911131170638320339593891343172291881746593361173816183944395813681024078062221371920617424703886814545558432083276777273747776833647888592759655656879042215426815821658958852085409637159046667",
+        "7160165348388038797508799537168334216615194044109891983648738522289912331635230920961401877076609151693909624685558134595635893865049432289950327140284581769586400369827619140394841187956645481611402455231790238253655681496868");
+    const BASIS_YP2: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "199277083125881415355148629499940123628151849524742329952865572645141002230096894973645988449743144840082696789550815050870745736346891085960884740135641239604669538955129032889591515012554954558562536613402552378354318989618",
+        "7738575293329501637425658687479215743350100757188281895359410430961057561394776644548347239138314523532820443495493894699846668436091998347107075000892827031974879419160561249176272008695201568341091118448695591856106708759505");
+    const BASIS_XQ2: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "4202989022499411381922357334948682550678944175389067653735609832171468300488438450640325925678127369749878693235212969390743160776883744187155081033070884527783326628185229230367376418873486960646396508568835334780885583240468",
+        "1321589317563748401806112950251463411315621319736786036218768750915586967950639280482799953447197780701710264593046608959302691522383009331170847752520720275264263748539301645472481754756650577870289363490557864394585438787830");
+    const BASIS_YQ2: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "7076355774304323182991166539103780363658650899596370310945908042655298761630748511733398694119796533192551351858992968403747580016763264318808457890795026176155116768580474926358467520493453833573027998638823430313381781958730",
+        "320533459852869054681199087162326815143306449032947659890388117454364290035390249959018987792978968642664622736922938349488992915375383008769994449347001670524154075027968775443729435706158695078965299705115492642661327790987");
+    const TATE_2E: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "3505051234446737097395850043703200677882712432109402059830685251283333654588058815307844569970549053904875787022875175434404901156084484684958003500104182605261166355303790586657455212684710563830767123798116915189244248515121",
+        "5479807448834523508680446374829274331408767114542649274482022687814313472279804703745569723204752696382157043354677200890332379189357747525266392859370964501362129961862012044806724985609894020950734798526834869495124468700348");
+    const WEIL_2E: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "8361424411734756911538728898682775122970720357734388805661857343074282202883220882186670036181351110071943182261320046131324896883626682906982936102958780249883936296990944500877036603757745483330430607150323456576158953275139",
+        "2466788043071301667571303559289673187348266985912877592151206646763753696186374728063894018004811931508720186065674491231364227743703116502986653934075889273482076798267822294176377118291181981694824045627246989617552221404909");
+    const BASIS_XP3: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "5316054876270868505204131040107337094682843032546080413210082702414556891865468015972827751568675791406803773556044064951308047031615877511605189903076860116894391069228819141201486611055782563140636135268013173434370422869181",
+        "7433550267538536682972708087664655546978200075732371675622940852822789444620011608356403912226917521139958043676252387463818269999416720433498550023342417091000816462808884064946716608619653922573437916762248372279245678688804");
+    const BASIS_YP3: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "2178890656715141781056362313405753977893001001437455548382291924329513932713832842028581406670927293553193450125063018190566615233114560173096362580080588999849467275482120517728200052612920041179414832050663216275261273067767",
+        "7884846435726037317654493099192351610408289991249438286595313983786246137911713866959526720262697964096171263633308324836674968490525912812152846538781590754375858143204755491278071168444001361590782390759942586103568384048204");
+    const BASIS_XQ3: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "1091907573717625250568641788590406624880097012315051689897133754420007978560160223461392531519499294696113083417050070558032168037933623469716158079994214707596087011774903655406373190303151931677895597183702347751724026581530",
+        "4646604962182435013597742885473908772262609327075993628372891812577426926268895955537520478632919758788071830584438676554583204738726483417696548112917614180410072547461542886846662029752316923896974596456574632759338344740413");
+    const BASIS_YQ3: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "1602083723928903971446873247180859280872109152185282620031889205021947070702849497995693288673469228094369896647554563402628701104852157956378909860197045264110867931454622490755612222057287828994398318346232073582704037155861",
+        "7540608280583959620310875061610154383731786778859849438094641725810137086741593256009646152100826498480805052040614267686624497852768049295195889121186273219830791988433777087783187741724697204422844801921153437529740587786171");
+    const TATE_3E: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "1038819294861505842299378491926692533860071163665383300333519606424578029288773021988674115523199594307984777515969289680755791450614773519453056168567597431927413672729926523044628465481953428872696847618544291198696821851590",
+        "1130812338208541828838585435100996210191998651926189146145675411714937510146161077350689977051801795867585486378680991217188221302535659287439547671999263061684412417045505342687740055701044166545549068235840636084328643839108");
+    const WEIL_3E: ExtensionFieldElement = ExtensionFieldElement::from_str_const(
+        "7960923230859125984335414038498248918677979443004627666875519917910906833221706846004118238514910396581171386288653555969881045788674859915182450111180728311586557674813550263195535092139588710548694762983718808063940151035191",
+        "2997637660733321089685843098365338107319381086339987854181870572849913181594852825987378200927462709300661904990614995539941240397153234499661883015316321936295898078077576961804456407181521891861382100328723816339798732037907");
+
+    const CURVE: ProjectiveCurveParameters = ProjectiveCurveParameters{ A: CURVE_A, C: CURVE_C };
+    const BASIS_2E: [ExtensionFieldElement; 4] = [BASIS_XP2, BASIS_YP2, BASIS_XQ2, BASIS_YQ2];
+    const BASIS_3E: [ExtensionFieldElement; 4] = [BASIS_XP3, BASIS_YP3, BASIS_XQ3, BASIS_YQ3];
+
+    // Check that x is a root of unity of order exactly l^e.
+    fn has_order(x: &ExtensionFieldElement, l: u64, e: u32) -> bool {
+        let pow = |mut y: ExtensionFieldElement, k: u32| {
+            for _ in 0..k {
+                y = if l == 2 { y.square() } else { &y.square() * &y };
+            }
+            y
+        };
+        let y = pow(*x, e - 1);
+        !y.vartime_eq(&ExtensionFieldElement::one()) && pow(y, 1).vartime_eq(&ExtensionFieldElement::one())
+    }
+
+    // The basis (P_B, tau(P_B)) of E_0[3^239], where tau(x, y) = (-x, iy) is the
+    // distortion map.
+    fn e0_basis_3e() -> [ExtensionFieldElement; 4] {
+        let xP = ExtensionFieldElement::new(&AFFINE_X_PB, &PrimeFieldElement::zero());
+        let yP = ExtensionFieldElement::new(&AFFINE_Y_PB, &PrimeFieldElement::zero());
+        let xQ = -&xP;
+        let yQ = ExtensionFieldElement::new(&PrimeFieldElement::zero(), &AFFINE_Y_PB);
+        [xP, yP, xQ, yQ]
+    }
+
+    #[test]
+    fn tate_pairing_2e_versus_test_vectors() {
+        let t = tate_pairing_2e(&CURVE, &BASIS_XP2, &BASIS_YP2, &BASIS_XQ2, &BASIS_YQ2);
+        assert!(t.vartime_eq(&TATE_2E), "\nExpected {}\nfound {}", TATE_2E, t);
+        assert!(has_order(&t, 2, E_A));
+    }
+
+    #[test]
+    fn tate_pairing_3e_versus_test_vectors() {
+        let t = tate_pairing_3e(&CURVE, &BASIS_XP3, &BASIS_YP3, &BASIS_XQ3, &BASIS_YQ3);
+        assert!(t.vartime_eq(&TATE_3E), "\nExpected {}\nfound {}", TATE_3E, t);
+        assert!(has_order(&t, 3, E_B));
+    }
+
+    #[test]
+    fn weil_pairing_2e_versus_test_vectors() {
+        let w = weil_pairing_2e(&CURVE, &BASIS_XP2, &BASIS_YP2, &BASIS_XQ2, &BASIS_YQ2);
+        assert!(w.vartime_eq(&WEIL_2E), "\nExpected {}\nfound {}", WEIL_2E, w);
+        assert!(has_order(&w, 2, E_A));
+    }
+
+    #[test]
+    fn weil_pairing_3e_versus_test_vectors() {
+        let w = weil_pairing_3e(&CURVE, &BASIS_XP3, &BASIS_YP3, &BASIS_XQ3, &BASIS_YQ3);
+        assert!(w.vartime_eq(&WEIL_3E), "\nExpected {}\nfound {}", WEIL_3E, w);
+        assert!(has_order(&w, 3, E_B));
+    }
+
+    #[test]
+    fn weil_pairing_is_alternating() {
+        let w_inv = weil_pairing_2e(&CURVE, &BASIS_XQ2, &BASIS_YQ2, &BASIS_XP2, &BASIS_YP2);
+        assert!((&WEIL_2E * &w_inv).vartime_eq(&ExtensionFieldElement::one()));
+        // e(P, -Q) = e(P, Q)^-1
+        let w_neg = weil_pairing_2e(&CURVE, &BASIS_XP2, &BASIS_YP2, &BASIS_XQ2, &-&BASIS_YQ2);
+        assert!(w_neg.vartime_eq(&w_inv));
+
+        let [xP, yP, xQ, yQ] = e0_basis_3e();
+        let curve = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let w = weil_pairing_3e(&curve, &xP, &yP, &xQ, &yQ);
+        let w_inv = weil_pairing_3e(&curve, &xQ, &yQ, &xP, &yP);
+        assert!(has_order(&w, 3, E_B));
+        assert!((&w * &w_inv).vartime_eq(&ExtensionFieldElement::one()));
+        let w_neg = weil_pairing_3e(&curve, &xP, &yP, &xQ, &-&yQ);
+        assert!(w_neg.vartime_eq(&w_inv));
+    }
+
+    #[test]
+    fn weil_pairing_is_quotient_of_tate_pairings() {
+        // For embedding degree one, e(P, Q)^((p^2-1)/n) = t(P, Q) / t(Q, P).
+        let t = tate_pairing_2e(&CURVE, &BASIS_XQ2, &BASIS_YQ2, &BASIS_XP2, &BASIS_YP2);
+        let lhs = final_exponentiation_2e(&WEIL_2E, &ExtensionFieldElement::one());
+        assert!(lhs.vartime_eq(&(&TATE_2E * &t.inv())));
+
+        let t = tate_pairing_3e(&CURVE, &BASIS_XQ3, &BASIS_YQ3, &BASIS_XP3, &BASIS_YP3);
+        let lhs = final_exponentiation_3e(&WEIL_3E, &ExtensionFieldElement::one());
+        assert!(lhs.vartime_eq(&(&TATE_3E * &t.inv())));
+    }
+
+    // Check e([a]P, [b]Q) = e(P, Q)^(ab) for the pairing e, where e(P, Q) = ePQ.
+    fn check_bilinearity<F>(pairing: F, basis: &[ExtensionFieldElement; 4], ePQ: &ExtensionFieldElement, a: u8, b: u8)
+        where F: Fn(&ExtensionFieldElement, &ExtensionFieldElement, &ExtensionFieldElement, &ExtensionFieldElement) -> ExtensionFieldElement
+    {
+        let [xP, yP, xQ, yQ] = basis;
+        let P = MontgomeryPoint::from_affine(xP, yP);
+        let Q = MontgomeryPoint::from_affine(xQ, yQ);
+
+        let (xaP, yaP) = P.scalar_mul(&CURVE, &[a]).to_affine();
+        let (xbQ, ybQ) = Q.scalar_mul(&CURVE, &[b]).to_affine();
+        let mut ePQ_ab = ExtensionFieldElement::one();
+        for _ in 0..(a as u32) * (b as u32) {
+            ePQ_ab = &ePQ_ab * ePQ;
+        }
+        let e = pairing(&xaP, &yaP, &xbQ, &ybQ);
+        assert!(e.vartime_eq(&ePQ_ab), "\nExpected {}\nfound {}", ePQ_ab, e);
+    }
+
+    // Check w(P + Q, [2]P + Q) = w(P, Q)^(1-2) = w(Q, P) for the Weil pairing w,
+    // which is additive in each argument and alternating.
+    fn check_weil_additivity<F>(weil: F, basis: &[ExtensionFieldElement; 4])
+        where F: Fn(&ExtensionFieldElement, &ExtensionFieldElement, &ExtensionFieldElement, &ExtensionFieldElement) -> ExtensionFieldElement
+    {
+        let [xP, yP, xQ, yQ] = basis;
+        let P = MontgomeryPoint::from_affine(xP, yP);
+        let Q = MontgomeryPoint::from_affine(xQ, yQ);
+        let (xR, yR) = P.add(&Q, &CURVE).to_affine();
+        let (xS, yS) = P.double(&CURVE).add(&Q, &CURVE).to_affine();
+        assert!(weil(&xR, &yR, &xS, &yS).vartime_eq(&weil(xQ, yQ, xP, yP)));
+    }
+
+    #[test]
+    fn pairings_are_bilinear() {
+        check_bilinearity(|xP, yP, xQ, yQ| tate_pairing_2e(&CURVE, xP, yP, xQ, yQ),
+                          &BASIS_2E, &TATE_2E, 3, 5);
+        check_bilinearity(|xP, yP, xQ, yQ| weil_pairing_2e(&CURVE, xP, yP, xQ, yQ),
+                          &BASIS_2E, &WEIL_2E, 3, 5);
+        check_bilinearity(|xP, yP, xQ, yQ| tate_pairing_3e(&CURVE, xP, yP, xQ, yQ),
+                          &BASIS_3E, &TATE_3E, 2, 5);
+        check_bilinearity(|xP, yP, xQ, yQ| weil_pairing_3e(&CURVE, xP, yP, xQ, yQ),
+                          &BASIS_3E, &WEIL_3E, 2, 5);
+
+        check_weil_additivity(|xP, yP, xQ, yQ| weil_pairing_2e(&CURVE, xP, yP, xQ, yQ),
+                              &BASIS_2E);
+        check_weil_additivity(|xP, yP, xQ, yQ| weil_pairing_3e(&CURVE, xP, yP, xQ, yQ),
+                              &BASIS_3E);
+    }
+
+    #[test]
+    fn generated_torsion_bases_versus_test_vectors() {
+        let (xP, yP, xQ, yQ) = CURVE.generate_2_torsion_basis();
+        assert!(xP.vartime_eq(&BASIS_XP2) && yP.vartime_eq(&BASIS_YP2));
+        assert!(xQ.vartime_eq(&BASIS_XQ2) && yQ.vartime_eq(&BASIS_YQ2));
+        assert!(has_order(&weil_pairing_2e(&CURVE, &xP, &yP, &xQ, &yQ), 2, E_A));
+
+        let (xP, yP, xQ, yQ) = CURVE.generate_3_torsion_basis();
+        assert!(xP.vartime_eq(&BASIS_XP3) && yP.vartime_eq(&BASIS_YP3));
+        assert!(xQ.vartime_eq(&BASIS_XQ3) && yQ.vartime_eq(&BASIS_YQ3));
+        assert!(has_order(&weil_pairing_3e(&CURVE, &xP, &yP, &xQ, &yQ), 3, E_B));
+    }
+}
+
+#[cfg(all(test, feature = "bench"))]
+mod bench {
+    use super::*;
+    use field::PrimeFieldElement;
+    use test::Bencher;
+
+    fn e0_basis(x: &PrimeFieldElement, y: &PrimeFieldElement) -> [ExtensionFieldElement; 4] {
+        let xP = ExtensionFieldElement::new(x, &PrimeFieldElement::zero());
+        let yP = ExtensionFieldElement::new(y, &PrimeFieldElement::zero());
+        [xP, yP, -&xP, ExtensionFieldElement::new(&PrimeFieldElement::zero(), y)]
+    }
+
+    #[bench]
+    fn tate_pairing_2e(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let [xP, yP, xQ, yQ] = e0_basis(&AFFINE_X_PA, &AFFINE_Y_PA);
+
+        b.iter(|| super::tate_pairing_2e(&curve, &xP, &yP, &xQ, &yQ));
+    }
+
+    #[bench]
+    fn tate_pairing_3e(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let [xP, yP, xQ, yQ] = e0_basis(&AFFINE_X_PB, &AFFINE_Y_PB);
+
+        b.iter(|| super::tate_pairing_3e(&curve, &xP, &yP, &xQ, &yQ));
+    }
+
+    #[bench]
+    fn weil_pairing_2e(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let [xP, yP, xQ, yQ] = e0_basis(&AFFINE_X_PA, &AFFINE_Y_PA);
+
+        b.iter(|| super::weil_pairing_2e(&curve, &xP, &yP, &xQ, &yQ));
+    }
+
+    #[bench]
+    fn weil_pairing_3e(b: &mut Bencher) {
+        let curve = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let [xP, yP, xQ, yQ] = e0_basis(&AFFINE_X_PB, &AFFINE_Y_PB);
+
+        b.iter(|| super::weil_pairing_3e(&curve, &xP, &yP, &xQ, &yQ));
+    }
+}
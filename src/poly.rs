@@ -0,0 +1,668 @@
+// This file is part of sidh-rs.
+// Copyright (c) 2017 Erkan Tairi
+// See LICENSE for licensing information.
+//
+// Author:
+// - Erkan Tairi <erkan.tairi@gmail.com>
+//
+
+//! This module contains dense polynomials over `F_{p^2}`, with the arithmetic
+//! needed by isogeny algorithms: schoolbook and Karatsuba multiplication,
+//! division with remainder, gcds, resultants, root finding for small degrees, and
+//! multipoint evaluation with product trees.
+//!
+//! A `Polynomial` is its vector of coefficients in increasing degree, and the
+//! functions here take coefficient slices. Inputs may have trailing zero
+//! coefficients; except for the products, which keep every coefficient, results
+//! have none, so that the zero polynomial is empty.
+//!
+//! Without the `std` feature, a `Polynomial` holds at most `MAX_POLYNOMIAL_LENGTH`
+//! coefficients, and product trees are not available. All of this takes variable
+//! time.
+
+use field::{PrimeFieldElement, ExtensionFieldElement};
+use constants::{E_A, E_B};
+
+use heapless::Vec;
+
+/// The number of coefficients a `Polynomial` can hold without the `std` feature.
+pub const MAX_POLYNOMIAL_LENGTH: usize = 16;
+
+/// A dense polynomial over `F_{p^2}`, as its coefficients in increasing degree.
+#[cfg(feature = "std")]
+pub type Polynomial = ::std::vec::Vec<ExtensionFieldElement>;
+/// A dense polynomial over `F_{p^2}`, as its coefficients in increasing degree.
+#[cfg(not(feature = "std"))]
+pub type Polynomial = Vec<ExtensionFieldElement, MAX_POLYNOMIAL_LENGTH>;
+
+// Below this length, mul uses schoolbook multiplication.
+const KARATSUBA_THRESHOLD: usize = 4;
+
+/// Returns the degree of `f`, or `None` for the zero polynomial.
+pub fn degree(f: &[ExtensionFieldElement]) -> Option<usize> {
+    let zero = ExtensionFieldElement::zero();
+    f.iter().rposition(|c| !c.vartime_eq(&zero))
+}
+
+/// Returns `f` without its trailing zero coefficients.
+pub fn trim(f: &[ExtensionFieldElement]) -> Polynomial {
+    let len = degree(f).map_or(0, |d| d + 1);
+    from_slice(&f[..len])
+}
+
+/// Returns the monic linear polynomial `Y - r`.
+pub fn linear(r: &ExtensionFieldElement) -> Polynomial {
+    from_slice(&[-r, ExtensionFieldElement::one()])
+}
+
+/// Returns `f` scaled to have leading coefficient 1, or the zero polynomial if
+/// `f` is zero.
+pub fn monic(f: &[ExtensionFieldElement]) -> Polynomial {
+    let f = trim(f);
+    match f.last() {
+        Some(lead) => scale(&f, &lead.inv()),
+        None => Polynomial::new(),
+    }
+}
+
+/// Compute `c*f`.
+pub fn scale(f: &[ExtensionFieldElement], c: &ExtensionFieldElement) -> Polynomial {
+    let cf: Polynomial = f.iter().map(|f_i| f_i * c).collect();
+    trim(&cf)
+}
+
+/// Compute `f + g`.
+pub fn add(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement]) -> Polynomial {
+    trim(&add_untrimmed(f, g))
+}
+
+/// Compute `f - g`.
+pub fn sub(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement]) -> Polynomial {
+    let minus_g: Polynomial = g.iter().map(|c| -c).collect();
+    add(f, &minus_g)
+}
+
+/// Compute `f(x)` by Horner's rule.
+pub fn eval(f: &[ExtensionFieldElement], x: &ExtensionFieldElement) -> ExtensionFieldElement {
+    f.iter().rev().fold(ExtensionFieldElement::zero(), |acc, f_i| &(&acc * x) + f_i)
+}
+
+/// Compute `x^e` by square-and-multiply, in time depending on `e`.
+pub fn pow_vartime(x: &ExtensionFieldElement, e: usize) -> ExtensionFieldElement {
+    let mut r = ExtensionFieldElement::one();
+    for j in (0..usize::BITS).rev() {
+        r = r.square();
+        if (e >> j) & 1 == 1 {
+            r = &r * x;
+        }
+    }
+    r
+}
+
+/// Compute `f*g` by schoolbook multiplication, in `O(deg f deg g)` operations.
+///
+/// The product has `f.len() + g.len() - 1` coefficients, or none if either
+/// operand is empty.
+pub fn mul_schoolbook(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement]) -> Polynomial {
+    if f.is_empty() || g.is_empty() {
+        return Polynomial::new();
+    }
+    let mut h = zeros(f.len() + g.len() - 1);
+    for (i, f_i) in f.iter().enumerate() {
+        for (j, g_j) in g.iter().enumerate() {
+            h[i + j] = &h[i + j] + &(f_i * g_j);
+        }
+    }
+    h
+}
+
+/// Compute `f*g` by Karatsuba multiplication, in `O(n^1.59)` operations for
+/// operands with `n` coefficients.
+///
+/// The product has `f.len() + g.len() - 1` coefficients, or none if either
+/// operand is empty.
+pub fn mul_karatsuba(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement]) -> Polynomial {
+    karatsuba(f, g, 2)
+}
+
+/// Compute `f*g`, with Karatsuba multiplication for long enough operands.
+///
+/// The product has `f.len() + g.len() - 1` coefficients, or none if either
+/// operand is empty.
+pub fn mul(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement]) -> Polynomial {
+    karatsuba(f, g, KARATSUBA_THRESHOLD)
+}
+
+/// Compute the product of the polynomials, as a balanced product tree.
+pub fn product(factors: &[Polynomial]) -> Polynomial {
+    match factors.len() {
+        0 => from_slice(&[ExtensionFieldElement::one()]),
+        1 => factors[0].clone(),
+        n => mul(&product(&factors[..n / 2]), &product(&factors[n / 2..])),
+    }
+}
+
+/// Compute `f*g mod modulus`, for a nonzero modulus.
+pub fn mul_mod(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement], modulus: &[ExtensionFieldElement]) -> Polynomial {
+    divrem(&mul(f, g), modulus).1
+}
+
+/// Compute the quotient and remainder `(q, r)` of `f` by `g`, with `f = qg + r`
+/// and `deg r < deg g`.
+///
+/// Panics if `g` is zero.
+pub fn divrem(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement]) -> (Polynomial, Polynomial) {
+    let g = trim(g);
+    assert!(!g.is_empty(), "division by the zero polynomial");
+    let mut r = trim(f);
+    if r.len() < g.len() {
+        return (Polynomial::new(), r);
+    }
+    let lead_inv = g[g.len() - 1].inv();
+    let mut q = zeros(r.len() + 1 - g.len());
+    for k in (0..q.len()).rev() {
+        let c = &r[k + g.len() - 1] * &lead_inv;
+        for (i, g_i) in g.iter().enumerate() {
+            r[k + i] = &r[k + i] - &(&c * g_i);
+        }
+        q[k] = c;
+    }
+    (q, trim(&r[..g.len() - 1]))
+}
+
+/// Compute the monic greatest common divisor of `f` and `g`, by Euclid's
+/// algorithm. It is zero if both `f` and `g` are.
+pub fn gcd(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement]) -> Polynomial {
+    let mut a = trim(f);
+    let mut b = trim(g);
+    while !b.is_empty() {
+        let r = divrem(&a, &b).1;
+        a = b;
+        b = r;
+    }
+    monic(&a)
+}
+
+/// Compute the resultant `Res(f, g)`, by Euclid's algorithm.
+///
+/// With `f = a prod (Y - x_i)` of degree `m` and `g = b prod (Y - y_k)` of degree
+/// `n`, this is `a^n b^m prod (x_i - y_k) = a^n prod g(x_i)`, and it is zero if
+/// and only if `f` and `g` have a common root, or one of them is zero.
+//
+// With r = f mod g of degree k, Res(f, g) = (-1)^(mn) lc(g)^(m-k) Res(g, r), and
+// Res(f, c) = c^m for a constant c.
+pub fn resultant(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement]) -> ExtensionFieldElement {
+    let zero = ExtensionFieldElement::zero();
+    let mut a = trim(f);
+    let mut b = trim(g);
+    if a.is_empty() || b.is_empty() {
+        return zero;
+    }
+    let mut res = ExtensionFieldElement::one();
+    loop {
+        let m = a.len() - 1;
+        let n = b.len() - 1;
+        if n == 0 {
+            return &res * &pow_vartime(&b[0], m);
+        }
+        let sign = m * n % 2 == 1;
+        if m < n {
+            if sign {
+                res = -(&res);
+            }
+            ::core::mem::swap(&mut a, &mut b);
+            continue;
+        }
+        let r = divrem(&a, &b).1;
+        if r.is_empty() {
+            return zero;
+        }
+        res = &res * &pow_vartime(&b[n], m - (r.len() - 1));
+        if sign {
+            res = -(&res);
+        }
+        a = b;
+        b = r;
+    }
+}
+
+/// Returns the roots of `f` in `F_{p^2}`, with multiplicity, for a nonzero `f` of
+/// degree at most 4.
+///
+/// Panics if `f` is zero or of degree more than 4.
+//
+// The roots are found by computing g = gcd(f, Y^(p^2) - Y), and splitting it by
+// gcd(g, (Y + d)^((p^2 - 1)/2) - 1) for varying d. Both exponentiations use
+// p + 1 = 2^372 * 3^239 and the Frobenius map, so they take a few hundred
+// polynomial multiplications.
+pub fn roots(f: &[ExtensionFieldElement]) -> Vec<ExtensionFieldElement, 4> {
+    let zero = ExtensionFieldElement::zero();
+    let mut f = monic(f);
+    assert!(!f.is_empty() && f.len() <= 5, "only nonzero polynomials of degree at most 4 are supported");
+
+    let mut roots = Vec::new();
+    // Remove the roots at zero, so that Y is invertible modulo f.
+    while f.len() > 1 && f[0].vartime_eq(&zero) {
+        roots.push(zero).unwrap();
+        f = from_slice(&f[1..]);
+    }
+    if f.len() == 1 {
+        return roots;
+    }
+
+    // Y^p = Y^(p+1) / Y, and Y^(p^2) = (Y^p)^p is Y^p with conjugated coefficients,
+    // evaluated at Y^p.
+    let y = linear(&zero);
+    let mut y_inv: Polynomial = f[1..].iter().map(|c| -c).collect();
+    y_inv = scale(&y_inv, &f[0].inv());                  // = -(f - f(0))/(Y f(0))
+    let y_p = mul_mod(&pow_p_plus_1(&y, &f), &y_inv, &f); // = Y^p mod f
+    let y_p2 = frobenius(&y_p, &y_p, &f);                 // = Y^(p^2) mod f
+    let g = gcd(&f, &sub(&y_p2, &y));
+
+    let mut distinct: Vec<ExtensionFieldElement, 4> = Vec::new();
+    split(&g, &y_p, &mut distinct);
+    for r in distinct.iter() {
+        while !f.is_empty() && eval(&f, r).vartime_eq(&zero) {
+            roots.push(*r).unwrap();
+            f = divrem(&f, &linear(r)).0;
+        }
+    }
+    roots
+}
+
+// Append the roots of g, a product of distinct monic linear factors, where y_p is
+// Y^p modulo a multiple of g.
+fn split(g: &[ExtensionFieldElement], y_p: &[ExtensionFieldElement], roots: &mut Vec<ExtensionFieldElement, 4>) {
+    match g.len() {
+        0 | 1 => {}
+        2 => roots.push(-(&g[0])).unwrap(),
+        3 => {
+            // Y^2 + bY + c has the roots (-b +- sqrt(b^2 - 4c))/2.
+            let half = ExtensionFieldElement::from_u64(2).inv();
+            let t = (&g[1].square() - &(&ExtensionFieldElement::from_u64(4) * &g[0])).sqrt();
+            roots.push(&(&t - &g[1]) * &half).unwrap();
+            roots.push(&(-(&(&t + &g[1]))) * &half).unwrap();
+        }
+        _ => {
+            // (Y + d)^((p^2-1)/2) = 1 at a root r if and only if the norm u(r) =
+            // (r + d)^(p+1) in F_p is a square, i.e. if u^((p+1)/2) = u. Taking
+            // d = k + i outside of F_p also splits roots in F_p, whose norms
+            // would otherwise all be squares.
+            let y_p = divrem(y_p, g).1;
+            let i = ExtensionFieldElement::new(&PrimeFieldElement::zero(), &PrimeFieldElement::one());
+            let mut k = 0;
+            loop {
+                let d = &ExtensionFieldElement::from_u64(k) + &i;
+                let d_p = ExtensionFieldElement::new(&d.real(), &-(&d.imag()));
+                let u = mul_mod(&linear(&-(&d)), &add(&y_p, &[d_p]), g); // = (Y + d)^(p+1)
+                let w = pow_half_p_plus_1(&u, g);                      // = u^((p+1)/2)
+                let s = gcd(g, &sub(&w, &u));
+                if s.len() > 1 && s.len() < g.len() {
+                    split(&s, &y_p, roots);
+                    split(&divrem(g, &s).0, &y_p, roots);
+                    return;
+                }
+                k += 1;
+            }
+        }
+    }
+}
+
+// Compute x^(p+1) = x^(3^239 2^372) modulo f.
+fn pow_p_plus_1(x: &[ExtensionFieldElement], f: &[ExtensionFieldElement]) -> Polynomial {
+    let mut y: Polynomial = from_slice(x);
+    for _ in 0..E_B {
+        y = mul_mod(&mul_mod(&y, &y, f), &y, f);
+    }
+    for _ in 0..E_A {
+        y = mul_mod(&y, &y, f);
+    }
+    y
+}
+
+// Compute x^((p+1)/2) = x^(3^239 2^371) modulo f.
+fn pow_half_p_plus_1(x: &[ExtensionFieldElement], f: &[ExtensionFieldElement]) -> Polynomial {
+    let mut y: Polynomial = from_slice(x);
+    for _ in 0..E_B {
+        y = mul_mod(&mul_mod(&y, &y, f), &y, f);
+    }
+    for _ in 1..E_A {
+        y = mul_mod(&y, &y, f);
+    }
+    y
+}
+
+// Compute h(Y)^p = h^sigma(Y^p) modulo f, where h^sigma has the conjugated
+// coefficients of h, by Horner's rule.
+fn frobenius(h: &[ExtensionFieldElement], y_p: &[ExtensionFieldElement], f: &[ExtensionFieldElement]) -> Polynomial {
+    let mut result = Polynomial::new();
+    for c in h.iter().rev() {
+        let c_p = ExtensionFieldElement::new(&c.real(), &-(&c.imag()));
+        result = add(&mul_mod(&result, y_p, f), &[c_p]);
+    }
+    result
+}
+
+// The polynomial with the given coefficients.
+#[cfg(feature = "std")]
+fn from_slice(f: &[ExtensionFieldElement]) -> Polynomial {
+    f.to_vec()
+}
+#[cfg(not(feature = "std"))]
+fn from_slice(f: &[ExtensionFieldElement]) -> Polynomial {
+    Vec::from_slice(f).expect("polynomial is too long")
+}
+
+// The zero polynomial with n (zero) coefficients.
+fn zeros(n: usize) -> Polynomial {
+    (0..n).map(|_| ExtensionFieldElement::zero()).collect()
+}
+
+// Compute f + g, keeping max(f.len(), g.len()) coefficients.
+fn add_untrimmed(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement]) -> Polynomial {
+    let (long, short) = if f.len() >= g.len() { (f, g) } else { (g, f) };
+    let mut h: Polynomial = from_slice(long);
+    for (h_i, s_i) in h.iter_mut().zip(short.iter()) {
+        *h_i = &*h_i + s_i;
+    }
+    h
+}
+
+// Compute f*g, with Karatsuba multiplication down to operands of the given length.
+fn karatsuba(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement], threshold: usize) -> Polynomial {
+    if f.len() < threshold || g.len() < threshold {
+        return mul_schoolbook(f, g);
+    }
+    let mut h = zeros(f.len() + g.len() - 1);
+    let (long, short) = if f.len() >= g.len() { (f, g) } else { (g, f) };
+    let m = long.len() / 2;
+    let (long0, long1) = long.split_at(m);
+    let (z0, z1, z2);
+    if short.len() <= m {
+        // long*short = long0*short + X^m long1*short.
+        z0 = karatsuba(long0, short, threshold);
+        z1 = karatsuba(long1, short, threshold);
+        z2 = Polynomial::new();
+    } else {
+        // (long0 + X^m long1)(short0 + X^m short1), with three half-size products.
+        let (short0, short1) = short.split_at(m);
+        z0 = karatsuba(long0, short0, threshold);
+        z2 = karatsuba(long1, short1, threshold);
+        let mut t = karatsuba(&add_untrimmed(long0, long1), &add_untrimmed(short0, short1), threshold);
+        for (i, z0_i) in z0.iter().enumerate() {
+            t[i] = &t[i] - z0_i;
+        }
+        for (i, z2_i) in z2.iter().enumerate() {
+            t[i] = &t[i] - z2_i;
+        }
+        z1 = t;
+    }
+    for (i, c) in z0.iter().enumerate() {
+        h[i] = &h[i] + c;
+    }
+    for (i, c) in z1.iter().enumerate() {
+        h[i + m] = &h[i + m] + c;
+    }
+    for (i, c) in z2.iter().enumerate() {
+        h[i + 2 * m] = &h[i + 2 * m] + c;
+    }
+    h
+}
+
+// Compute 1/f mod X^n by Newton iteration, for f with f(0) = 1.
+#[cfg(feature = "std")]
+fn inv_series(f: &[ExtensionFieldElement], n: usize) -> Polynomial {
+    if n == 0 {
+        return Polynomial::new();
+    }
+    let two = ExtensionFieldElement::from_u64(2);
+    debug_assert!(f[0].vartime_eq(&ExtensionFieldElement::one()));
+    let mut h = ::std::vec![ExtensionFieldElement::one()];
+    while h.len() < n {
+        let k = if 2 * h.len() < n { 2 * h.len() } else { n };
+        // h <- h(2 - fh) mod X^k
+        let mut e = mul(&f[..if f.len() < k { f.len() } else { k }], &h);
+        e.resize(k, ExtensionFieldElement::zero());
+        for e_i in e.iter_mut() {
+            *e_i = -(&*e_i);
+        }
+        e[0] = &e[0] + &two;
+        h = mul(&h, &e);
+        h.truncate(k);
+    }
+    h
+}
+
+// Compute f*g mod X^k, with schoolbook multiplication of the needed terms for
+// short operands.
+#[cfg(feature = "std")]
+fn mul_low(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement], k: usize) -> Polynomial {
+    if f.len() >= KARATSUBA_THRESHOLD && g.len() >= KARATSUBA_THRESHOLD {
+        let mut h = mul(f, g);
+        h.resize(k, ExtensionFieldElement::zero());
+        return h;
+    }
+    let mut h = zeros(k);
+    for (i, f_i) in f.iter().enumerate().take(k) {
+        for (j, g_j) in g.iter().enumerate().take(k - i) {
+            h[i + j] = &h[i + j] + &(f_i * g_j);
+        }
+    }
+    h
+}
+
+// Compute f mod g, for monic g of degree n and deg f < n + inv.len(), given
+// inv = rev(g)^-1 mod X^inv.len(), where rev(q) = rev(f) inv mod X^(deg f - n + 1).
+#[cfg(feature = "std")]
+fn rem_with_inverse(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement], inv: &[ExtensionFieldElement]) -> Polynomial {
+    let n = g.len() - 1;
+    if f.len() <= n {
+        return f.to_vec();
+    }
+    let m = f.len() - n;
+    debug_assert!(m <= inv.len());
+    let f_rev: Polynomial = f.iter().rev().take(m).cloned().collect();
+    let mut q = mul_low(&f_rev, &inv[..m], m);
+    q.reverse();
+    let qg = mul_low(&q, g, n);
+    (0..n).map(|i| &f[i] - &qg[i]).collect()
+}
+
+// Up to this many points, a ProductTree node evaluates by Horner's rule instead
+// of reducing further.
+#[cfg(feature = "std")]
+const PRODUCT_TREE_LEAF_SIZE: usize = 4;
+
+/// A subproduct tree of `prod (Y - x_i)` for points `x_i`, with the inverses
+/// needed to reduce modulo each node, for multipoint evaluation in
+/// `O~(n)` operations for `n` points.
+//
+// Polynomials are reduced modulo each node with a precomputed inverse of its
+// reversal, and evaluated by Horner's rule at the leaves.
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct ProductTree {
+    node: Polynomial,
+    // rev(node)^-1 mod X^precision, for inner nodes.
+    inv: Polynomial,
+    // The points of the node, for leaves.
+    points: Polynomial,
+    children: Option<::std::boxed::Box<(ProductTree, ProductTree)>>,
+}
+
+#[cfg(feature = "std")]
+impl ProductTree {
+    /// Build the product tree of the points `xs`.
+    ///
+    /// Panics if there are no points.
+    pub fn new(xs: &[ExtensionFieldElement]) -> ProductTree {
+        ProductTree::with_precision(xs, xs.len())
+    }
+    // Build the tree for the (non-empty) points xs, able to reduce polynomials of
+    // degree less than len(xs) + precision without a long division at the root.
+    pub(crate) fn with_precision(xs: &[ExtensionFieldElement], precision: usize) -> ProductTree {
+        assert!(!xs.is_empty(), "product trees need at least one point");
+        if xs.len() <= PRODUCT_TREE_LEAF_SIZE {
+            let node = product(&xs.iter().map(linear).collect::<::std::vec::Vec<_>>());
+            return ProductTree{ node, inv: Polynomial::new(), points: xs.to_vec(), children: None };
+        }
+        // A child only reduces the remainder modulo its parent.
+        let (left, right) = xs.split_at(xs.len() / 2);
+        let left = ProductTree::with_precision(left, right.len());
+        let right = ProductTree::with_precision(right, left.node.len() - 1);
+        let node = mul(&left.node, &right.node);
+        let node_rev: Polynomial = node.iter().rev().cloned().collect();
+        let inv = inv_series(&node_rev, precision);
+        ProductTree{ node, inv, points: Polynomial::new(), children: Some(::std::boxed::Box::new((left, right))) }
+    }
+    /// Returns the product `prod (Y - x_i)` of the tree.
+    pub fn polynomial(&self) -> &[ExtensionFieldElement] {
+        &self.node
+    }
+    /// Compute the values `f(x_i)`, in the order of the points.
+    pub fn eval(&self, f: &[ExtensionFieldElement]) -> ::std::vec::Vec<ExtensionFieldElement> {
+        match self.children {
+            Some(ref children) => {
+                let r = self.reduce(f);
+                let mut values = children.0.eval(&r);
+                values.extend(children.1.eval(&r));
+                values
+            }
+            None => self.points.iter().map(|x| eval(f, x)).collect(),
+        }
+    }
+    /// Compute the product `prod f(x_i)`, which is the resultant of
+    /// `prod (Y - x_i)` and `f`.
+    pub fn product_of_values(&self, f: &[ExtensionFieldElement]) -> ExtensionFieldElement {
+        match self.children {
+            Some(ref children) => {
+                let r = self.reduce(f);
+                &children.0.product_of_values(&r) * &children.1.product_of_values(&r)
+            }
+            None => self.points.iter().fold(ExtensionFieldElement::one(), |acc, x| &acc * &eval(f, x)),
+        }
+    }
+    // Compute f mod node, for an inner node.
+    fn reduce(&self, f: &[ExtensionFieldElement]) -> Polynomial {
+        if f.len() < self.node.len() + self.inv.len() {
+            rem_with_inverse(f, &self.node, &self.inv)
+        } else {
+            divrem(f, &self.node).1
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn random_polynomial(len: usize) -> Polynomial {
+        let mut rng = rand::thread_rng();
+        (0..len).map(|_| ExtensionFieldElement::random(&mut rng)).collect()
+    }
+
+    fn same_polynomial(f: &[ExtensionFieldElement], g: &[ExtensionFieldElement]) -> bool {
+        sub(f, g).is_empty()
+    }
+
+    // Whether xs and ys are equal as multisets.
+    fn same_multiset(xs: &[ExtensionFieldElement], ys: &[ExtensionFieldElement]) -> bool {
+        let mut ys: Vec<ExtensionFieldElement, 4> = ys.iter().cloned().collect();
+        for x in xs.iter() {
+            match ys.iter().position(|y| y.vartime_eq(x)) {
+                Some(i) => { ys.swap_remove(i); }
+                None => return false,
+            }
+        }
+        ys.is_empty()
+    }
+
+    #[test]
+    fn karatsuba_versus_schoolbook() {
+        for &(m, n) in [(1, 1), (2, 7), (5, 5), (8, 3), (11, 6), (4, 0)].iter() {
+            let f = random_polynomial(m);
+            let g = random_polynomial(n);
+            let h = mul_schoolbook(&f, &g);
+            assert_eq!(h.len(), if n == 0 { 0 } else { m + n - 1 });
+            assert!(same_polynomial(&h, &mul_karatsuba(&f, &g)));
+            assert!(same_polynomial(&h, &mul(&g, &f)));
+        }
+    }
+
+    #[test]
+    fn division_with_remainder() {
+        let f = random_polynomial(12);
+        let g = random_polynomial(5);
+        let (q, r) = divrem(&f, &g);
+        assert_eq!(q.len(), 8);
+        assert!(r.len() < 5);
+        assert!(same_polynomial(&f, &add(&mul(&q, &g), &r)));
+
+        // Trailing zeros of the divisor are ignored.
+        let g_padded: Polynomial = g.iter().cloned().chain(Some(ExtensionFieldElement::zero())).collect();
+        assert!(same_polynomial(&r, &divrem(&f, &g_padded).1));
+        assert!(divrem(&g, &f).0.is_empty());
+    }
+
+    #[test]
+    fn gcds_and_resultants() {
+        let mut rng = rand::thread_rng();
+        let xs = random_polynomial(3);
+        let ys = random_polynomial(2);
+        let c = ExtensionFieldElement::random(&mut rng);
+        let common = linear(&ExtensionFieldElement::random(&mut rng));
+        let f = scale(&product(&xs.iter().map(linear).collect::<::std::vec::Vec<_>>()), &c);
+        let g = product(&ys.iter().map(linear).collect::<::std::vec::Vec<_>>());
+
+        assert!(same_polynomial(&gcd(&mul(&f, &common), &mul(&g, &common)), &common));
+        assert!(same_polynomial(&gcd(&f, &g), &[ExtensionFieldElement::one()]));
+        assert!(resultant(&mul(&f, &common), &mul(&g, &common)).vartime_eq(&ExtensionFieldElement::zero()));
+
+        // Res(f, g) = c^2 prod (x_i - y_k) = (-1)^6 Res(g, f).
+        let mut expected = &c * &c;
+        for x in xs.iter() {
+            for y in ys.iter() {
+                expected = &expected * &(x - y);
+            }
+        }
+        assert!(resultant(&f, &g).vartime_eq(&expected));
+        assert!(resultant(&g, &f).vartime_eq(&expected));
+        // Res(f, c) = c^3 and Res(f, 0) = 0.
+        assert!(resultant(&f, &[c]).vartime_eq(&(&(&c * &c) * &c)));
+        assert!(resultant(&f, &[]).vartime_eq(&ExtensionFieldElement::zero()));
+    }
+
+    #[test]
+    fn roots_of_split_polynomials() {
+        let mut rng = rand::thread_rng();
+        let r = [ExtensionFieldElement::random(&mut rng), ExtensionFieldElement::random(&mut rng), ExtensionFieldElement::zero()];
+        // 5 (Y - r0)^2 (Y - r1)(Y - 0).
+        let f = product(&[linear(&r[0]), linear(&r[0]), linear(&r[1]), linear(&r[2])]);
+        let found = roots(&scale(&f, &ExtensionFieldElement::from_u64(5)));
+        assert!(same_multiset(&found, &[r[0], r[0], r[1], r[2]]));
+
+        // Y^2 + 1 = (Y - i)(Y + i), but Y^2 - (4 + i) has no roots in F_{p^2}, as
+        // 4 + i is not a square.
+        let i = ExtensionFieldElement::new(&PrimeFieldElement::zero(), &PrimeFieldElement::one());
+        let one = ExtensionFieldElement::one();
+        let u = &ExtensionFieldElement::from_u64(4) + &i;
+        assert!(same_multiset(&roots(&[one, ExtensionFieldElement::zero(), one]), &[i, -(&i)]));
+        assert!(roots(&[-(&u), ExtensionFieldElement::zero(), one]).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn multipoint_evaluation() {
+        for &(n, len) in [(1, 3), (4, 9), (9, 5), (13, 13), (13, 40)].iter() {
+            let xs = random_polynomial(n);
+            let f = random_polynomial(len);
+            let tree = ProductTree::new(&xs);
+            let values = tree.eval(&f);
+            assert_eq!(values.len(), n);
+            for (x, v) in xs.iter().zip(values.iter()) {
+                assert!(eval(&f, x).vartime_eq(v));
+            }
+            assert!(tree.product_of_values(&f).vartime_eq(&resultant(tree.polynomial(), &f)));
+        }
+    }
+}
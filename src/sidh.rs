@@ -42,7 +42,9 @@ use core::fmt::Debug;
 
 use rand_core::{CryptoRng, RngCore};
 use rand::thread_rng;
-use heapless::Vec;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 #[cfg(test)]
 use quickcheck::{Arbitrary, Gen, QuickCheck};
@@ -54,9 +56,6 @@ pub const PUBLIC_KEY_SIZE: usize = 564;
 /// The shared secret size, in bytes.
 pub const SHARED_SECRET_SIZE: usize = 188;
 
-const MAX_INT_POINTS_ALICE: usize = 8;
-const MAX_INT_POINTS_BOB: usize = 10;
-
 
 const MAX_ALICE: usize = 185;
 /// Alice's isogeny strategy.
@@ -166,102 +165,80 @@ impl Arbitrary for SIDHSecretKeyAlice {
 impl SIDHSecretKeyAlice {
     /// Compute the corresponding public key for the given secret key.
     pub fn public_key(&self) -> SIDHPublicKeyAlice {
-        let mut xP = ProjectivePoint::from_affine_prime_field(&AFFINE_X_PB);  // = ( x_P : 1) = x(P_B)
-        let mut xQ = ProjectivePoint::from_affine_prime_field(&AFFINE_X_PB);  //
-        xQ.X = -(&xQ.X);                                                      // = (-x_P : 1) = x(Q_B)
-        let mut xQmP = ProjectivePoint::distort_and_difference(&AFFINE_X_PB); // = x(Q_B - P_B)
-
-        let mut xR = ProjectivePoint::secret_point(&AFFINE_X_PA, &AFFINE_Y_PA, &self.scalar[..]);
-
-        // Starting curve has a = 0, so (A:C) = (0,1).
-        let current_curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() }; 
-        let (mut current_curve, firstPhi) = FirstFourIsogeny::compute_first_four_isogeny(&current_curve);
-
-        xP = firstPhi.eval(&xP);
-        xQ = firstPhi.eval(&xQ);
-        xQmP = firstPhi.eval(&xQmP);
-        xR = firstPhi.eval(&xR);
-        
-        let mut points: Vec<ProjectivePoint, MAX_INT_POINTS_ALICE> = Vec::new();
-        let mut indices: Vec<usize, MAX_INT_POINTS_ALICE> = Vec::new();
-        let mut i: usize = 0;
-        let mut phi: FourIsogeny;
-        for j in 1..MAX_ALICE {
-            while i < MAX_ALICE-j {
-                points.push(xR).unwrap();
-                indices.push(i).unwrap();
-                let k = ALICE_ISOGENY_STRATEGY[MAX_ALICE-i-j];
-                xR = xR.pow2k(&current_curve, (2*k) as u32);
-                i = i + k as usize;
-            }
-            assign!{(current_curve, phi) = FourIsogeny::compute_four_isogeny(&xR)};
-
-            for k in 0..points.len() {
-                points[k] = phi.eval(&points[k]);
-            }
-
-            xP = phi.eval(&xP);
-            xQ = phi.eval(&xQ);
-            xQmP = phi.eval(&xQmP);
-
-            // Pop xR from pointsm and i from indices.
-            xR = points.pop().unwrap();
-            i = indices.pop().unwrap();
-        }
-
-        assign!{(current_curve, phi) = FourIsogeny::compute_four_isogeny(&xR)};
-
-        xP = phi.eval(&xP);
-        xQ = phi.eval(&xQ);
-        xQmP = phi.eval(&xQmP);
-
-        let (invZP, invZQ, invZQmP) = ExtensionFieldElement::batch3_inv(&xP.Z, &xQ.Z, &xQmP.Z);
-        let affine_xP = &xP.X * &invZP;
-        let affine_xQ = &xQ.X * &invZQ;
-        let affine_xQmP = &xQmP.X * &invZQmP;
-
-        SIDHPublicKeyAlice{ affine_xP, affine_xQ, affine_xQmP }
+        let xR = ProjectivePoint::secret_point(&AFFINE_X_PA, &AFFINE_Y_PA, &self.scalar[..]);
+        alice_public_key(&xR, &mut no_blinding)
+    }
+    /// Compute the corresponding public key as `public_key` does, randomizing the
+    /// projective representations of the points at the entry of the scalar
+    /// multiplication and after each isogeny with randomness from `rng`.
+    ///
+    /// This opt-in hardening against differential power analysis does not
+    /// change the result.
+    pub fn public_key_randomized<R: RngCore + CryptoRng>(&self, rng: &mut R) -> SIDHPublicKeyAlice {
+        let xR = ProjectivePoint::secret_point_randomized(&AFFINE_X_PA, &AFFINE_Y_PA, &self.scalar[..], rng);
+        alice_public_key(&xR, &mut |xS: &mut ProjectivePoint| *xS = xS.randomize(rng))
     }
     /// Compute (Alice's view of) a shared secret using Alice's secret key and Bob's public key.
     pub fn shared_secret(&self, bob_public: &SIDHPublicKeyBob) -> [u8; SHARED_SECRET_SIZE] {
-        let current_curve = ProjectiveCurveParameters::recover_curve_parameters(&bob_public.affine_xP, &bob_public.affine_xQ, &bob_public.affine_xQmP);
-        let xP = ProjectivePoint::from_affine(&bob_public.affine_xP);
-        let xQ = ProjectivePoint::from_affine(&bob_public.affine_xQ);
-        let xQmP = ProjectivePoint::from_affine(&bob_public.affine_xQmP);
-        let mut xR = ProjectivePoint::right_to_left_ladder(&xP, &xQ, &xQmP, &current_curve, &self.scalar[..]);
-
-        let (mut current_curve, firstPhi) = FirstFourIsogeny::compute_first_four_isogeny(&current_curve);
-        xR = firstPhi.eval(&xR);
-
-        let mut points: Vec<ProjectivePoint, MAX_INT_POINTS_ALICE> = Vec::new();
-        let mut indices: Vec<usize, MAX_INT_POINTS_ALICE> = Vec::new();
-        let mut i: usize = 0;
-        let mut phi: FourIsogeny;
-        for j in 1..MAX_ALICE {
-            while i < MAX_ALICE-j {
-                points.push(xR).unwrap();
-                indices.push(i).unwrap();
-                let k = ALICE_ISOGENY_STRATEGY[MAX_ALICE-i-j];
-                xR = xR.pow2k(&current_curve, (2*k) as u32);
-                i = i + k as usize;
-            }
-            assign!{(current_curve, phi) = FourIsogeny::compute_four_isogeny(&xR)};
-
-            for k in 0..points.len() {
-                points[k] = phi.eval(&points[k]);
-            }
-
-            // Pop xR from pointsm and i from indices.
-		    xR = points.pop().unwrap();
-            i = indices.pop().unwrap();
-        }
+        alice_shared_secret(self, bob_public, &mut no_blinding)
+    }
+    /// Compute (Alice's view of) a shared secret as `shared_secret` does,
+    /// randomizing the projective representations of the points at the entry of
+    /// the ladder and after each isogeny with randomness from `rng`.
+    ///
+    /// This opt-in hardening against differential power analysis does not
+    /// change the result.
+    pub fn shared_secret_randomized<R: RngCore + CryptoRng>(&self, bob_public: &SIDHPublicKeyBob, rng: &mut R) -> [u8; SHARED_SECRET_SIZE] {
+        alice_shared_secret(self, bob_public, &mut |xS: &mut ProjectivePoint| *xS = xS.randomize(rng))
+    }
+}
 
-        assign!{(current_curve, phi) = FourIsogeny::compute_four_isogeny(&xR)};
+// Compute Alice's public key from her secret point x(R) = x(P_A + [m]Q_A),
+// applying blind to the images of Bob's basis before the walk and after each
+// isogeny.
+fn alice_public_key<B>(xR: &ProjectivePoint, blind: &mut B) -> SIDHPublicKeyAlice
+    where B: FnMut(&mut ProjectivePoint)
+{
+    let xP = ProjectivePoint::from_affine_prime_field(&AFFINE_X_PB);      // = ( x_P : 1) = x(P_B)
+    let mut xQ = ProjectivePoint::from_affine_prime_field(&AFFINE_X_PB);  //
+    xQ.X = -(&xQ.X);                                                      // = (-x_P : 1) = x(Q_B)
+    let xQmP = ProjectivePoint::distort_and_difference(&AFFINE_X_PB);     // = x(Q_B - P_B)
+
+    // Starting curve has a = 0, so (A:C) = (0,1).
+    let current_curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+    let mut torsion = [xP, xQ, xQmP];
+    for xS in torsion.iter_mut() {
+        blind(xS);
+    }
+    four_isogeny_walk(&current_curve, xR, &ALICE_ISOGENY_STRATEGY, &mut torsion, blind);
+    let [xP, xQ, xQmP] = torsion;
+
+    let (invZP, invZQ, invZQmP) = ExtensionFieldElement::batch3_inv(&xP.Z, &xQ.Z, &xQmP.Z);
+    let affine_xP = &xP.X * &invZP;
+    let affine_xQ = &xQ.X * &invZQ;
+    let affine_xQmP = &xQmP.X * &invZQmP;
+
+    SIDHPublicKeyAlice{ affine_xP, affine_xQ, affine_xQmP }
+}
 
-        let j_inv = current_curve.j_invariant();
-        let shared_secret = j_inv.to_bytes();
-        shared_secret
-    }
+// Compute Alice's view of a shared secret, applying blind to Bob's points
+// before the ladder and to the kernel points after each isogeny.
+fn alice_shared_secret<B>(alice_secret: &SIDHSecretKeyAlice, bob_public: &SIDHPublicKeyBob, blind: &mut B) -> [u8; SHARED_SECRET_SIZE]
+    where B: FnMut(&mut ProjectivePoint)
+{
+    let current_curve = ProjectiveCurveParameters::recover_curve_parameters(&bob_public.affine_xP, &bob_public.affine_xQ, &bob_public.affine_xQmP);
+    let mut xP = ProjectivePoint::from_affine(&bob_public.affine_xP);
+    let mut xQ = ProjectivePoint::from_affine(&bob_public.affine_xQ);
+    let mut xQmP = ProjectivePoint::from_affine(&bob_public.affine_xQmP);
+    blind(&mut xP);
+    blind(&mut xQ);
+    blind(&mut xQmP);
+    let xR = ProjectivePoint::right_to_left_ladder(&xP, &xQ, &xQmP, &current_curve, &alice_secret.scalar[..]);
+
+    let codomain = four_isogeny_walk(&current_curve, &xR, &ALICE_ISOGENY_STRATEGY, &mut [], blind);
+
+    let j_inv = codomain.j_invariant();
+    j_inv.to_bytes()
 }
 
 /// Bob's secret key.
@@ -288,99 +265,103 @@ impl Arbitrary for SIDHSecretKeyBob {
 impl SIDHSecretKeyBob {
     /// Compute the public key corresponding to the secret key.
     pub fn public_key(&self) -> SIDHPublicKeyBob {
-        let mut xP = ProjectivePoint::from_affine_prime_field(&AFFINE_X_PA);  // = ( x_P : 1) = x(P_A)
-        let mut xQ = ProjectivePoint::from_affine_prime_field(&AFFINE_X_PA);  //
-        xQ.X = -(&xQ.X);                                                      // = (-x_P : 1) = x(Q_A)
-        let mut xQmP = ProjectivePoint::distort_and_difference(&AFFINE_X_PA); // = x(Q_B - P_B)
-
-        let mut xR = ProjectivePoint::secret_point(&AFFINE_X_PB, &AFFINE_Y_PB, &self.scalar[..]);
-
-        // Starting curve has a = 0, so (A:C) = (0,1).
-        let mut current_curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
-
-        let mut points: Vec<ProjectivePoint, MAX_INT_POINTS_BOB> = Vec::new();
-        let mut indices: Vec<usize, MAX_INT_POINTS_BOB> = Vec::new();
-        let mut i: usize = 0;
-        let mut phi: ThreeIsogeny;
-        for j in 1..MAX_BOB {
-            while i < MAX_BOB-j {
-                points.push(xR).unwrap();
-                indices.push(i).unwrap();
-                let k = BOB_ISOGENY_STRATEGY[MAX_BOB-i-j];
-                xR = xR.pow3k(&current_curve, k as u32);
-                i = i + k as usize;
-            }
-             assign!{(current_curve, phi) = ThreeIsogeny::compute_three_isogeny(&xR)};
-
-            for k in 0..points.len() {
-                points[k] = phi.eval(&points[k]);
-            }
-
-            xP = phi.eval(&xP);
-            xQ = phi.eval(&xQ);
-            xQmP = phi.eval(&xQmP);
-
-            // Pop xR from points and i from indices.
-		    xR = points.pop().unwrap();
-            i = indices.pop().unwrap();
-        }
-
-        assign!{(current_curve, phi) = ThreeIsogeny::compute_three_isogeny(&xR)};
-
-        xP = phi.eval(&xP);
-        xQ = phi.eval(&xQ);
-        xQmP = phi.eval(&xQmP);
-
-        let (invZP, invZQ, invZQmP) = ExtensionFieldElement::batch3_inv(&xP.Z, &xQ.Z, &xQmP.Z);
-        let affine_xP = &xP.X * &invZP;
-        let affine_xQ = &xQ.X * &invZQ;
-        let affine_xQmP = &xQmP.X * &invZQmP;
-
-        SIDHPublicKeyBob{ affine_xP, affine_xQ, affine_xQmP }
+        let xR = ProjectivePoint::secret_point(&AFFINE_X_PB, &AFFINE_Y_PB, &self.scalar[..]);
+        bob_public_key(&xR, &mut no_blinding)
+    }
+    /// Compute the public key as `public_key` does, randomizing the projective
+    /// representations of the points at the entry of the scalar multiplication
+    /// and after each isogeny with randomness from `rng`.
+    ///
+    /// This opt-in hardening against differential power analysis does not
+    /// change the result.
+    pub fn public_key_randomized<R: RngCore + CryptoRng>(&self, rng: &mut R) -> SIDHPublicKeyBob {
+        let xR = ProjectivePoint::secret_point_randomized(&AFFINE_X_PB, &AFFINE_Y_PB, &self.scalar[..], rng);
+        bob_public_key(&xR, &mut |xS: &mut ProjectivePoint| *xS = xS.randomize(rng))
     }
     /// Compute (Bob's view of) a shared secret using Bob's secret key and Alice's public key.
     pub fn shared_secret(&self, alice_public: &SIDHPublicKeyAlice) -> [u8; SHARED_SECRET_SIZE] {
-        let mut current_curve = ProjectiveCurveParameters::recover_curve_parameters(&alice_public.affine_xP, &alice_public.affine_xQ, &alice_public.affine_xQmP);
-        let xP = ProjectivePoint::from_affine(&alice_public.affine_xP);
-        let xQ = ProjectivePoint::from_affine(&alice_public.affine_xQ);
-        let xQmP = ProjectivePoint::from_affine(&alice_public.affine_xQmP);
-        let mut xR = ProjectivePoint::right_to_left_ladder(&xP, &xQ, &xQmP, &current_curve, &self.scalar[..]);
-
-        let mut points: Vec<ProjectivePoint, MAX_INT_POINTS_BOB> = Vec::new();
-        let mut indices: Vec<usize, MAX_INT_POINTS_BOB> = Vec::new();
-        let mut i: usize = 0;
-        let mut phi: ThreeIsogeny;
-        for j in 1..MAX_BOB {
-            while i < MAX_BOB-j {
-                points.push(xR).unwrap();
-                indices.push(i).unwrap();
-                let k = BOB_ISOGENY_STRATEGY[MAX_BOB-i-j];
-                xR = xR.pow3k(&current_curve, k as u32);
-                i = i + k as usize;
-            }
-            assign!{(current_curve, phi) = ThreeIsogeny::compute_three_isogeny(&xR)};
-
-            for k in 0..points.len() {
-                points[k] = phi.eval(&points[k]);
-            }
-
-            // Pop xR from points and i from indices.
-		    xR = points.pop().unwrap();
-            i = indices.pop().unwrap();
-        }
+        bob_shared_secret(self, alice_public, &mut no_blinding)
+    }
+    /// Compute (Bob's view of) a shared secret as `shared_secret` does,
+    /// randomizing the projective representations of the points at the entry of
+    /// the ladder and after each isogeny with randomness from `rng`.
+    ///
+    /// This opt-in hardening against differential power analysis does not
+    /// change the result.
+    pub fn shared_secret_randomized<R: RngCore + CryptoRng>(&self, alice_public: &SIDHPublicKeyAlice, rng: &mut R) -> [u8; SHARED_SECRET_SIZE] {
+        bob_shared_secret(self, alice_public, &mut |xS: &mut ProjectivePoint| *xS = xS.randomize(rng))
+    }
+}
 
-        assign!{(current_curve, phi) = ThreeIsogeny::compute_three_isogeny(&xR)};
+// Compute Bob's public key from his secret point x(R) = x(P_B + [m]Q_B),
+// applying blind to the images of Alice's basis before the walk and after each
+// isogeny.
+fn bob_public_key<B>(xR: &ProjectivePoint, blind: &mut B) -> SIDHPublicKeyBob
+    where B: FnMut(&mut ProjectivePoint)
+{
+    let xP = ProjectivePoint::from_affine_prime_field(&AFFINE_X_PA);      // = ( x_P : 1) = x(P_A)
+    let mut xQ = ProjectivePoint::from_affine_prime_field(&AFFINE_X_PA);  //
+    xQ.X = -(&xQ.X);                                                      // = (-x_P : 1) = x(Q_A)
+    let xQmP = ProjectivePoint::distort_and_difference(&AFFINE_X_PA);     // = x(Q_A - P_A)
+
+    // Starting curve has a = 0, so (A:C) = (0,1).
+    let current_curve = ProjectiveCurveParameters{ A: ExtensionFieldElement::zero(), C: ExtensionFieldElement::one() };
+    let mut torsion = [xP, xQ, xQmP];
+    for xS in torsion.iter_mut() {
+        blind(xS);
+    }
+    three_isogeny_walk(&current_curve, xR, &BOB_ISOGENY_STRATEGY, &mut torsion, blind);
+    let [xP, xQ, xQmP] = torsion;
+
+    let (invZP, invZQ, invZQmP) = ExtensionFieldElement::batch3_inv(&xP.Z, &xQ.Z, &xQmP.Z);
+    let affine_xP = &xP.X * &invZP;
+    let affine_xQ = &xQ.X * &invZQ;
+    let affine_xQmP = &xQmP.X * &invZQmP;
+
+    SIDHPublicKeyBob{ affine_xP, affine_xQ, affine_xQmP }
+}
 
-        let j_inv = current_curve.j_invariant();
-        let shared_secret = j_inv.to_bytes();
-        shared_secret
-    }
+// Compute Bob's view of a shared secret, applying blind to Alice's points
+// before the ladder and to the kernel points after each isogeny.
+fn bob_shared_secret<B>(bob_secret: &SIDHSecretKeyBob, alice_public: &SIDHPublicKeyAlice, blind: &mut B) -> [u8; SHARED_SECRET_SIZE]
+    where B: FnMut(&mut ProjectivePoint)
+{
+    let current_curve = ProjectiveCurveParameters::recover_curve_parameters(&alice_public.affine_xP, &alice_public.affine_xQ, &alice_public.affine_xQmP);
+    let mut xP = ProjectivePoint::from_affine(&alice_public.affine_xP);
+    let mut xQ = ProjectivePoint::from_affine(&alice_public.affine_xQ);
+    let mut xQmP = ProjectivePoint::from_affine(&alice_public.affine_xQmP);
+    blind(&mut xP);
+    blind(&mut xQ);
+    blind(&mut xQmP);
+    let xR = ProjectivePoint::right_to_left_ladder(&xP, &xQ, &xQmP, &current_curve, &bob_secret.scalar[..]);
+
+    let codomain = three_isogeny_walk(&current_curve, &xR, &BOB_ISOGENY_STRATEGY, &mut [], blind);
+
+    let j_inv = codomain.j_invariant();
+    j_inv.to_bytes()
 }
 
 /// Generate a keypair for "Alice". Note that because this library does not
 /// implement SIDH validation, each keypair should be used for at most one
 /// shared secret computation.
 pub fn generate_alice_keypair<R: RngCore + CryptoRng>(rng: &mut R) -> (SIDHPublicKeyAlice, SIDHSecretKeyAlice) {
+    let secret_key = generate_alice_secret_key(rng);
+    let public_key = secret_key.public_key();
+
+    (public_key, secret_key)
+}
+
+/// Generate a keypair for "Alice" as `generate_alice_keypair` does, computing the
+/// public key with `public_key_randomized`.
+pub fn generate_alice_keypair_randomized<R: RngCore + CryptoRng>(rng: &mut R) -> (SIDHPublicKeyAlice, SIDHSecretKeyAlice) {
+    let secret_key = generate_alice_secret_key(rng);
+    let public_key = secret_key.public_key_randomized(rng);
+
+    (public_key, secret_key)
+}
+
+/// Generate a uniformly random secret key for "Alice".
+fn generate_alice_secret_key<R: RngCore + CryptoRng>(rng: &mut R) -> SIDHSecretKeyAlice {
     let mut scalar = [0u8; SECRET_KEY_SIZE];
     rng.fill_bytes(&mut scalar[..]);
 
@@ -392,16 +373,30 @@ pub fn generate_alice_keypair<R: RngCore + CryptoRng>(rng: &mut R) -> (SIDHPubli
     // We actually want scalar in 2*(0,2^371), but the above procedure
 	// generates 0 with probability 2^(-371), which isn't worth checking
 	// for.
-    let secret_key = SIDHSecretKeyAlice{ scalar };
-    let public_key = secret_key.public_key();
-
-    (public_key, secret_key)
+    SIDHSecretKeyAlice{ scalar }
 }
 
 /// Generate a keypair for "Bob". Note that because this library does not
 /// implement SIDH validation, each keypair should be used for at most one
 /// shared secret computation.
 pub fn generate_bob_keypair<R: RngCore + CryptoRng>(rng: &mut R) -> (SIDHPublicKeyBob, SIDHSecretKeyBob) {
+    let secret_key = generate_bob_secret_key(rng);
+    let public_key = secret_key.public_key();
+
+    (public_key, secret_key)
+}
+
+/// Generate a keypair for "Bob" as `generate_bob_keypair` does, computing the
+/// public key with `public_key_randomized`.
+pub fn generate_bob_keypair_randomized<R: RngCore + CryptoRng>(rng: &mut R) -> (SIDHPublicKeyBob, SIDHSecretKeyBob) {
+    let secret_key = generate_bob_secret_key(rng);
+    let public_key = secret_key.public_key_randomized(rng);
+
+    (public_key, secret_key)
+}
+
+/// Generate a uniformly random secret key for "Bob".
+fn generate_bob_secret_key<R: RngCore + CryptoRng>(rng: &mut R) -> SIDHSecretKeyBob {
     let mut scalar = [0u8; SECRET_KEY_SIZE];
     // Perform rejection sampling to obtain a random value in [0,3^238]:
     let mut ok: u32 = 1;
@@ -423,10 +418,41 @@ pub fn generate_bob_keypair<R: RngCore + CryptoRng>(rng: &mut R) -> (SIDHPublicK
     // We actually want scalar in 2*(0,2^371), but the above procedure
 	// generates 0 with probability 3^(-238), which isn't worth checking
 	// for.
-    let secret_key = SIDHSecretKeyBob{ scalar };
-    let public_key = secret_key.public_key();
+    SIDHSecretKeyBob{ scalar }
+}
 
-    (public_key, secret_key)
+/// Generate `n` keypairs for "Alice", e.g. to pre-generate ephemeral keys.
+///
+/// The secret keys are sampled sequentially from `rng`, and with the `parallel`
+/// feature enabled, the public keys are computed concurrently. Each keypair
+/// should still be used for at most one shared secret computation.
+#[cfg(feature = "std")]
+pub fn generate_alice_keypairs<R: RngCore + CryptoRng>(rng: &mut R, n: usize) -> ::std::vec::Vec<(SIDHPublicKeyAlice, SIDHSecretKeyAlice)> {
+    let secret_keys: ::std::vec::Vec<SIDHSecretKeyAlice> = (0..n).map(|_| generate_alice_secret_key(rng)).collect();
+
+    #[cfg(feature = "parallel")]
+    let keypairs = secret_keys.par_iter().map(|secret_key| (secret_key.public_key(), *secret_key)).collect();
+    #[cfg(not(feature = "parallel"))]
+    let keypairs = secret_keys.iter().map(|secret_key| (secret_key.public_key(), *secret_key)).collect();
+
+    keypairs
+}
+
+/// Generate `n` keypairs for "Bob", e.g. to pre-generate ephemeral keys.
+///
+/// The secret keys are sampled sequentially from `rng`, and with the `parallel`
+/// feature enabled, the public keys are computed concurrently. Each keypair
+/// should still be used for at most one shared secret computation.
+#[cfg(feature = "std")]
+pub fn generate_bob_keypairs<R: RngCore + CryptoRng>(rng: &mut R, n: usize) -> ::std::vec::Vec<(SIDHPublicKeyBob, SIDHSecretKeyBob)> {
+    let secret_keys: ::std::vec::Vec<SIDHSecretKeyBob> = (0..n).map(|_| generate_bob_secret_key(rng)).collect();
+
+    #[cfg(feature = "parallel")]
+    let keypairs = secret_keys.par_iter().map(|secret_key| (secret_key.public_key(), *secret_key)).collect();
+    #[cfg(not(feature = "parallel"))]
+    let keypairs = secret_keys.iter().map(|secret_key| (secret_key.public_key(), *secret_key)).collect();
+
+    keypairs
 }
 
 
@@ -531,8 +557,7 @@ mod test {
         assign!{(current_curve, phi) = FourIsogeny::compute_four_isogeny(&xR)};
 
         let j_inv = current_curve.j_invariant();
-        let shared_secret = j_inv.to_bytes();
-        shared_secret
+        j_inv.to_bytes()
     }
     // Perform Bob's key agreement, using the slow but simple multiplication-based strategy.
     //
@@ -557,8 +582,7 @@ mod test {
         assign!{(current_curve, phi) = ThreeIsogeny::compute_three_isogeny(&xR)};
 
         let j_inv = current_curve.j_invariant();
-        let shared_secret = j_inv.to_bytes();
-        shared_secret
+        j_inv.to_bytes()
     }
 
     #[test]
@@ -606,6 +630,40 @@ mod test {
         QuickCheck::new().quickcheck(shared_secrets_match as fn(SIDHSecretKeyAlice, SIDHSecretKeyBob) -> bool);
     }
 
+    #[test]
+    fn randomized_keypairs_and_shared_secrets() {
+        let mut rng = thread_rng();
+        let (alice_public, alice_secret) = generate_alice_keypair_randomized(&mut rng);
+        let (bob_public, bob_secret) = generate_bob_keypair_randomized(&mut rng);
+        assert!(alice_public.to_bytes().iter().zip(alice_secret.public_key().to_bytes().iter()).all(|(a, b)| a == b));
+        assert!(bob_public.to_bytes().iter().zip(bob_secret.public_key().to_bytes().iter()).all(|(a, b)| a == b));
+
+        let alice_shared_secret = alice_secret.shared_secret_randomized(&bob_public, &mut rng);
+        let bob_shared_secret = bob_secret.shared_secret_randomized(&alice_public, &mut rng);
+        assert!(alice_shared_secret.iter().zip(alice_secret.shared_secret(&bob_public).iter()).all(|(a, b)| a == b));
+        assert!(alice_shared_secret.iter().zip(bob_shared_secret.iter()).all(|(a, b)| a == b));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn batch_keypairs() {
+        let mut rng = thread_rng();
+        let alice_keypairs = generate_alice_keypairs(&mut rng, 3);
+        let bob_keypairs = generate_bob_keypairs(&mut rng, 3);
+        assert_eq!(alice_keypairs.len(), 3);
+        assert_eq!(bob_keypairs.len(), 3);
+
+        for ((alice_public, alice_secret), (bob_public, bob_secret)) in alice_keypairs.iter().zip(bob_keypairs.iter()) {
+            assert!(alice_public.to_bytes().iter().zip(alice_secret.public_key().to_bytes().iter()).all(|(a, b)| a == b));
+            assert!(bob_public.to_bytes().iter().zip(bob_secret.public_key().to_bytes().iter()).all(|(a, b)| a == b));
+
+            let alice_shared_secret = alice_secret.shared_secret(bob_public);
+            let bob_shared_secret = bob_secret.shared_secret(alice_public);
+            assert!(alice_shared_secret.iter().zip(bob_shared_secret.iter()).all(|(a, b)| a == b));
+        }
+        assert_ne!(&alice_keypairs[0].1.scalar[..], &alice_keypairs[1].1.scalar[..]);
+    }
+
     #[test]
     fn alice_keygen_fast_vs_slow() {
         // m_A = 2*randint(0,2^371)
@@ -782,6 +840,13 @@ mod bench {
         b.iter(|| generate_alice_keypair(&mut rng))
     }
 
+    #[bench]
+    fn alice_keygen_randomized(b: &mut Bencher) {
+        let mut rng = thread_rng();
+
+        b.iter(|| generate_alice_keypair_randomized(&mut rng))
+    }
+
     #[bench]
     fn alice_keygen_slow(b: &mut Bencher) {
         // m_A = 2*randint(0,2^371)
@@ -816,6 +881,16 @@ mod bench {
         b.iter(|| alice_secret.shared_secret(&SHARED_SECRET_BOB_PUBLIC));
     }
 
+    #[bench]
+    fn shared_secret_alice_randomized(b: &mut Bencher) {
+        // m_A = 2*randint(0,2^371)
+        let m_A: [u8; 48] = [248, 31, 9, 39, 165, 125, 79, 135, 70, 97, 87, 231, 221, 204, 245, 38, 150, 198, 187, 184, 199, 148, 156, 18, 137, 71, 248, 83, 111, 170, 138, 61, 112, 25, 188, 197, 132, 151, 1, 0, 207, 178, 24, 72, 171, 22, 11, 0];
+        let alice_secret = SIDHSecretKeyAlice{ scalar: m_A };
+        let mut rng = thread_rng();
+
+        b.iter(|| alice_secret.shared_secret_randomized(&SHARED_SECRET_BOB_PUBLIC, &mut rng));
+    }
+
     #[bench]
     fn shared_secret_alice_slow(b: &mut Bencher) {
         // m_A = 2*randint(0,2^371)
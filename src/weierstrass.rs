@@ -0,0 +1,272 @@
+// This file is part of sidh-rs.
+// Copyright (c) 2017 Erkan Tairi
+// See LICENSE for licensing information.
+//
+// Author:
+// - Erkan Tairi <erkan.tairi@gmail.com>
+//
+
+//! This module contains short Weierstrass curves `y^2 = x^3 + ax + b` over
+//! `F_{p^2}`, and the isomorphisms between them and the Montgomery curves
+//! `E_(A:C) : Cy^2 = Cx^3 + Ax^2 + Cx` of the `curve` module.
+//!
+//! This allows curves and points to be cross-checked with, or exported to,
+//! generic elliptic-curve software, which usually works with Weierstrass
+//! models, as is done in `sage/kummer_weierstrass_equivalence.sage`. Nothing
+//! here is constant time.
+//!
+//! The Montgomery curve `y^2 = x^3 + A'x^2 + x`, with `A' = A/C`, is isomorphic to
+//! the Weierstrass curve with `a = 1 - A'^2/3` and `b = A'(2A'^2 - 9)/27`, via
+//! `(x, y) -> (x + A'/3, y)`. Conversely, a Weierstrass curve has a Montgomery
+//! model if the cubic `x^3 + ax + b` has a root `alpha` such that `3alpha^2 + a`
+//! is a fourth power `s^4`, and then `(x, y) -> ((x - alpha)/s^2, y/s^3)` maps it
+//! onto the Montgomery curve with `A' = 3alpha/s^2`.
+
+use field::ExtensionFieldElement;
+use curve::{MontgomeryPoint, ProjectiveCurveParameters, ProjectivePoint};
+use poly;
+
+use core::fmt::Debug;
+use core::ops::Neg;
+
+/// A short Weierstrass curve `y^2 = x^3 + ax + b`.
+#[derive(Copy, Clone, PartialEq)]
+pub struct WeierstrassCurve {
+    pub a: ExtensionFieldElement,
+    pub b: ExtensionFieldElement,
+}
+
+impl Debug for WeierstrassCurve {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "WeierstrassCurve(a: {:?}\nb: {:?})", &self.a, &self.b)
+    }
+}
+
+impl WeierstrassCurve {
+    /// Construct the Weierstrass model of the Montgomery curve `E_(A:C)`.
+    pub fn from_montgomery(curve: &ProjectiveCurveParameters) -> WeierstrassCurve {
+        let a_m = &curve.A * &curve.C.inv();       // = A/C
+        let a_m2 = a_m.square();
+        let three = ExtensionFieldElement::from_u64(3);
+        let a = &(&three - &a_m2) * &three.inv();   // = (3 - (A/C)^2)/3
+        let mut b = &(&a_m2 + &a_m2) - &ExtensionFieldElement::from_u64(9);
+        b = &(&b * &a_m) * &ExtensionFieldElement::from_u64(27).inv(); // = (A/C)(2(A/C)^2 - 9)/27
+        WeierstrassCurve{ a, b }
+    }
+    /// Compute the j-invariant `1728 * 4a^3 / (4a^3 + 27b^2)`.
+    pub fn j_invariant(&self) -> ExtensionFieldElement {
+        let a3 = &self.a.square() * &self.a;
+        let a3_4 = &(&a3 + &a3) + &(&a3 + &a3);                                  // = 4a^3
+        let b2_27 = &ExtensionFieldElement::from_u64(27) * &self.b.square();    // = 27b^2
+        &(&ExtensionFieldElement::from_u64(1728) * &a3_4) * &(&a3_4 + &b2_27).inv()
+    }
+    /// Find a Montgomery model of this curve, if it has one.
+    ///
+    /// This requires a root `alpha` of `x^3 + ax + b` in `F_{p^2}` such that `3alpha^2 + a`
+    /// is a fourth power. Every curve constructed with `from_montgomery`, and every
+    /// supersingular curve with `E(F_{p^2}) = (Z/(p+1)Z)^2`, has such a root.
+    pub fn to_montgomery(&self) -> Option<MontgomeryModel> {
+        let zero = ExtensionFieldElement::zero();
+        let roots = poly::roots(&[self.b, self.a, zero, ExtensionFieldElement::one()]);
+
+        for alpha in roots.iter() {
+            let t = &(&ExtensionFieldElement::from_u64(3) * &alpha.square()) + &self.a; // = 3alpha^2 + a
+            if t.vartime_eq(&zero) || !t.is_square() {
+                continue;
+            }
+            // Since -1 is a square in F_{p^2}, either both square roots of t are
+            // squares, or neither is.
+            let s2 = t.sqrt();
+            if !s2.is_square() {
+                continue;
+            }
+            let s = s2.sqrt();
+            let curve = ProjectiveCurveParameters{ A: &ExtensionFieldElement::from_u64(3) * alpha, C: s2 };
+            return Some(MontgomeryModel{ curve, alpha: *alpha, s, s2, s3: &s2 * &s });
+        }
+        None
+    }
+}
+
+/// An isomorphism `(x, y) -> ((x - alpha)/s^2, y/s^3)` from a short Weierstrass
+/// curve onto the Montgomery curve `E_(3alpha : s^2)`.
+#[derive(Copy, Clone)]
+pub struct MontgomeryModel {
+    /// The Montgomery curve `E_(3alpha : s^2)`.
+    pub curve: ProjectiveCurveParameters,
+    alpha: ExtensionFieldElement,
+    s: ExtensionFieldElement,
+    s2: ExtensionFieldElement,
+    s3: ExtensionFieldElement,
+}
+
+impl MontgomeryModel {
+    /// Map a point on the Weierstrass curve to the Montgomery curve.
+    pub fn map_point(&self, P: &WeierstrassPoint) -> MontgomeryPoint {
+        // (X/Z - alpha)/s^2 = s(X - alpha*Z)/s^3*Z and Y/s^3*Z.
+        let X = &self.s * &(&P.X - &(&self.alpha * &P.Z));
+        MontgomeryPoint{ X, Y: P.Y, Z: &self.s3 * &P.Z }
+    }
+    /// Map the affine x-coordinate of a point on the Weierstrass curve to the
+    /// x-coordinate of its image on the Montgomery curve.
+    pub fn map_affine_x(&self, x: &ExtensionFieldElement) -> ProjectivePoint {
+        ProjectivePoint{ X: x - &self.alpha, Z: self.s2 }
+    }
+}
+
+/// A point `(X:Y:Z)` on a short Weierstrass curve. The identity is `(0:1:0)`.
+#[derive(Copy, Clone, PartialEq)]
+pub struct WeierstrassPoint {
+    pub X: ExtensionFieldElement,
+    pub Y: ExtensionFieldElement,
+    pub Z: ExtensionFieldElement,
+}
+
+impl Debug for WeierstrassPoint {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+        write!(f, "WeierstrassPoint(X: {:?}\nY: {:?}\nZ: {:?})", &self.X, &self.Y, &self.Z)
+    }
+}
+
+impl Neg for &WeierstrassPoint {
+    type Output = WeierstrassPoint;
+    fn neg(self) -> WeierstrassPoint {
+        WeierstrassPoint{ X: self.X, Y: -&self.Y, Z: self.Z }
+    }
+}
+
+impl WeierstrassPoint {
+    /// Construct the identity `(0:1:0)`.
+    pub fn identity() -> WeierstrassPoint {
+        WeierstrassPoint{ X: ExtensionFieldElement::zero(), Y: ExtensionFieldElement::one(), Z: ExtensionFieldElement::zero() }
+    }
+    /// Construct the point `(x:y:1)`. The caller is responsible for it lying on the curve.
+    pub fn from_affine(x: &ExtensionFieldElement, y: &ExtensionFieldElement) -> WeierstrassPoint {
+        WeierstrassPoint{ X: *x, Y: *y, Z: ExtensionFieldElement::one() }
+    }
+    /// Returns the affine coordinates `(x, y)`. The identity has none, so the output
+    /// is undefined for it.
+    pub fn to_affine(&self) -> (ExtensionFieldElement, ExtensionFieldElement) {
+        let z_inv = self.Z.inv();
+        (&self.X * &z_inv, &self.Y * &z_inv)
+    }
+    /// Map a point on the Montgomery curve `E_(A:C)` to its Weierstrass model
+    /// `WeierstrassCurve::from_montgomery(curve)`.
+    pub fn from_montgomery(curve: &ProjectiveCurveParameters, P: &MontgomeryPoint) -> WeierstrassPoint {
+        // (X/Z + A/3C, Y/Z) = (3CX + AZ : 3CY : 3CZ).
+        let C3 = &ExtensionFieldElement::from_u64(3) * &curve.C;
+        WeierstrassPoint{ X: &(&C3 * &P.X) + &(&curve.A * &P.Z), Y: &C3 * &P.Y, Z: &C3 * &P.Z }
+    }
+    /// Map the x-coordinate of a point on the Montgomery curve `E_(A:C)` to the
+    /// affine x-coordinate of its image on the Weierstrass model. The output is
+    /// undefined for the identity.
+    pub fn affine_x_from_montgomery(curve: &ProjectiveCurveParameters, xP: &ProjectivePoint) -> ExtensionFieldElement {
+        let C3 = &ExtensionFieldElement::from_u64(3) * &curve.C;
+        &(&(&C3 * &xP.X) + &(&curve.A * &xP.Z)) * &(&C3 * &xP.Z).inv()
+    }
+    /// Returns true if this is the identity.
+    pub fn is_identity(&self) -> bool {
+        self.Z.vartime_eq(&ExtensionFieldElement::zero())
+    }
+    /// Returns true if both sides are equal.
+    pub fn vartime_eq(&self, _rhs: &WeierstrassPoint) -> bool {
+        (&self.X * &_rhs.Z).vartime_eq(&(&self.Z * &_rhs.X)) &&
+        (&self.Y * &_rhs.Z).vartime_eq(&(&self.Z * &_rhs.Y))
+    }
+    /// Returns true if the point satisfies `Y^2Z = X^3 + aXZ^2 + bZ^3`.
+    pub fn is_on_curve(&self, curve: &WeierstrassCurve) -> bool {
+        let (X, Y, Z) = (&self.X, &self.Y, &self.Z);
+        let Z2 = Z.square();
+        let lhs = &Y.square() * Z;                                      // = Y^2Z
+        let rhs = &(X * &(&X.square() + &(&curve.a * &Z2))) + &(&(&curve.b * &Z2) * Z); // = X^3 + aXZ^2 + bZ^3
+        lhs.vartime_eq(&rhs)
+    }
+    /// Compute `P + Q`, handling all special cases.
+    pub fn add(&self, other: &WeierstrassPoint, curve: &WeierstrassCurve) -> WeierstrassPoint {
+        if self.is_identity() {
+            return *other;
+        }
+        if other.is_identity() {
+            return *self;
+        }
+        let (x1, y1) = self.to_affine();
+        let (x2, y2) = other.to_affine();
+        let lambda = if x1.vartime_eq(&x2) {
+            if !y1.vartime_eq(&y2) || y1.vartime_eq(&ExtensionFieldElement::zero()) {
+                return WeierstrassPoint::identity();
+            }
+            // The tangent has slope (3x^2 + a)/2y.
+            let x1_sq = x1.square();
+            &(&(&(&x1_sq + &x1_sq) + &x1_sq) + &curve.a) * &(&y1 + &y1).inv()
+        } else {
+            &(&y2 - &y1) * &(&x2 - &x1).inv()
+        };
+        let x3 = &(&lambda.square() - &x1) - &x2;
+        let y3 = &(&lambda * &(&x1 - &x3)) - &y1;
+        WeierstrassPoint::from_affine(&x3, &y3)
+    }
+    /// Compute `[2]P`.
+    pub fn double(&self, curve: &WeierstrassCurve) -> WeierstrassPoint {
+        self.add(self, curve)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    #[test]
+    fn j_invariant_versus_montgomery() {
+        fn j_invariants_agree(curve: ProjectiveCurveParameters) -> bool {
+            WeierstrassCurve::from_montgomery(&curve).j_invariant().vartime_eq(&curve.j_invariant())
+        }
+        QuickCheck::new().max_tests(16).quickcheck(j_invariants_agree as fn(ProjectiveCurveParameters) -> bool);
+    }
+
+    #[test]
+    fn montgomery_to_weierstrass_is_a_homomorphism() {
+        let e0 = ProjectiveCurveParameters::from_affine(&ExtensionFieldElement::zero());
+        let w0 = WeierstrassCurve::from_montgomery(&e0);
+        let (xP, yP, xQ, yQ) = e0.generate_2_torsion_basis();
+        let P = MontgomeryPoint::from_affine(&xP, &yP);
+        let Q = MontgomeryPoint::from_affine(&xQ, &yQ);
+        let wP = WeierstrassPoint::from_montgomery(&e0, &P);
+        let wQ = WeierstrassPoint::from_montgomery(&e0, &Q);
+        assert!(wP.is_on_curve(&w0) && wQ.is_on_curve(&w0));
+
+        let wPQ = WeierstrassPoint::from_montgomery(&e0, &P.add(&Q, &e0));
+        assert!(wPQ.vartime_eq(&wP.add(&wQ, &w0)));
+        let wP2 = WeierstrassPoint::from_montgomery(&e0, &P.double(&e0));
+        assert!(wP2.vartime_eq(&wP.double(&w0)));
+        assert!(WeierstrassPoint::from_montgomery(&e0, &MontgomeryPoint::identity()).is_identity());
+
+        let x = WeierstrassPoint::affine_x_from_montgomery(&e0, &P.to_projective_point());
+        assert!(x.vartime_eq(&wP.to_affine().0));
+    }
+
+    #[test]
+    fn weierstrass_to_montgomery_round_trip() {
+        // E_(12:-2) has a = -6, and is 4-isogenous to E_0.
+        let e1 = ProjectiveCurveParameters{ A: ExtensionFieldElement::from_u64(12), C: -(&ExtensionFieldElement::from_u64(2)) };
+        let w1 = WeierstrassCurve::from_montgomery(&e1);
+        let model = w1.to_montgomery().unwrap();
+        assert!(model.curve.j_invariant().vartime_eq(&e1.j_invariant()));
+
+        let (xP, yP, xQ, yQ) = e1.generate_3_torsion_basis();
+        let wP = WeierstrassPoint::from_montgomery(&e1, &MontgomeryPoint::from_affine(&xP, &yP));
+        let wQ = WeierstrassPoint::from_montgomery(&e1, &MontgomeryPoint::from_affine(&xQ, &yQ));
+        let P = model.map_point(&wP);
+        let Q = model.map_point(&wQ);
+        assert!(P.is_on_curve(&model.curve) && Q.is_on_curve(&model.curve));
+        assert!(model.map_point(&wP.add(&wQ, &w1)).vartime_eq(&P.add(&Q, &model.curve)));
+        assert!(model.map_affine_x(&wP.to_affine().0).vartime_eq(&P.to_projective_point()));
+
+        // Scaling (a, b) to (u^4 a, u^6 b) gives an isomorphic curve, which must
+        // also have a Montgomery model.
+        let u2 = ExtensionFieldElement::from_u64(5).square();
+        let w2 = WeierstrassCurve{ a: &w1.a * &u2.square(), b: &w1.b * &(&u2.square() * &u2) };
+        let model = w2.to_montgomery().unwrap();
+        assert!(model.curve.j_invariant().vartime_eq(&e1.j_invariant()));
+    }
+}